@@ -1,37 +1,111 @@
 use crate::migrate;
+use crate::opt::ConnectOpts;
+use anyhow::bail;
 use console::style;
 use promptly::{prompt, ReadlineError};
 use sqlx::any::Any;
-use sqlx::migrate::MigrateDatabase;
+use sqlx::migrate::{Migrate, MigrateDatabase, MigrateError, Seeder};
+use std::collections::HashMap;
+use std::path::Path;
 
-pub async fn create(uri: &str) -> anyhow::Result<()> {
-    if !Any::database_exists(uri).await? {
-        Any::create_database(uri).await?;
+pub async fn create(connect_opts: &ConnectOpts) -> anyhow::Result<()> {
+    // the database may not exist yet, so wait for the server without connecting to it
+    let exists = crate::retry_connect_errors(connect_opts, Any::database_exists).await?;
+
+    if !exists {
+        Any::create_database(connect_opts.required_db_url()?).await?;
     }
 
     Ok(())
 }
 
-pub async fn drop(uri: &str, confirm: bool) -> anyhow::Result<()> {
-    if confirm && !ask_to_continue(uri) {
+pub async fn drop(connect_opts: &ConnectOpts, confirm: bool) -> anyhow::Result<()> {
+    if confirm && !ask_to_continue(connect_opts.required_db_url()?) {
         return Ok(());
     }
 
-    if Any::database_exists(uri).await? {
-        Any::drop_database(uri).await?;
+    let exists = crate::retry_connect_errors(connect_opts, Any::database_exists).await?;
+
+    if exists {
+        Any::drop_database(connect_opts.required_db_url()?).await?;
     }
 
     Ok(())
 }
 
-pub async fn reset(migration_source: &str, uri: &str, confirm: bool) -> anyhow::Result<()> {
-    drop(uri, confirm).await?;
-    setup(migration_source, uri).await
+pub async fn reset(
+    migration_source: &str,
+    table_name: &str,
+    seed_source: Option<&str>,
+    connect_opts: &ConnectOpts,
+    confirm: bool,
+) -> anyhow::Result<()> {
+    drop(connect_opts, confirm).await?;
+    setup(migration_source, table_name, seed_source, connect_opts).await
+}
+
+pub async fn setup(
+    migration_source: &str,
+    table_name: &str,
+    seed_source: Option<&str>,
+    connect_opts: &ConnectOpts,
+) -> anyhow::Result<()> {
+    create(connect_opts).await?;
+    migrate::run(
+        migration_source,
+        table_name,
+        connect_opts,
+        false,
+        false,
+        None,
+    )
+    .await?;
+
+    if let Some(seed_source) = seed_source {
+        seed(seed_source, connect_opts, false).await?;
+    }
+
+    Ok(())
 }
 
-pub async fn setup(migration_source: &str, uri: &str) -> anyhow::Result<()> {
-    create(uri).await?;
-    migrate::run(migration_source, uri, false, false).await
+pub async fn seed(
+    seed_source: &str,
+    connect_opts: &ConnectOpts,
+    rerun: bool,
+) -> anyhow::Result<()> {
+    let seeder = Seeder::new(Path::new(seed_source)).await?;
+    let mut conn = crate::connect(connect_opts).await?;
+
+    conn.ensure_seeds_table().await?;
+
+    let applied_seeds: HashMap<_, _> = conn
+        .list_applied_seeds()
+        .await?
+        .into_iter()
+        .map(|s| (s.name.clone(), s))
+        .collect();
+
+    for seed in seeder.iter() {
+        if let Some(applied_seed) = applied_seeds.get(&*seed.name) {
+            if !rerun {
+                if seed.checksum != applied_seed.checksum {
+                    bail!(MigrateError::SeedMismatch(seed.name.to_string()));
+                }
+
+                continue;
+            }
+        }
+
+        let elapsed = conn.apply_seed(seed).await?;
+
+        println!(
+            "Applied seed {} {}",
+            style(&seed.name).cyan(),
+            style(format!("({:?})", elapsed)).dim()
+        );
+    }
+
+    Ok(())
 }
 
 fn ask_to_continue(uri: &str) -> bool {
@@ -0,0 +1,502 @@
+use crate::opt::ConnectOpts;
+use anyhow::{bail, Context};
+use console::style;
+use sqlx::any::{AnyConnectOptions, AnyKind};
+use sqlx::migrate::{Migrate, Schema};
+use sqlx::{AnyConnection, Column, Executor, TypeInfo};
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::fs;
+use std::str::FromStr;
+
+const HEADER: &str =
+    "// Generated by `sqlx generate`. Changes will be lost when it is run again.\n";
+
+/// A column of a table or view, or an attribute of a composite type.
+struct Field {
+    name: String,
+    /// The Rust type of the field, or the name of the database type if there is none.
+    ty: Result<String, String>,
+    nullable: bool,
+}
+
+pub async fn run(
+    connect_opts: &ConnectOpts,
+    table_name: &str,
+    output: Option<&str>,
+) -> anyhow::Result<()> {
+    let kind = AnyConnectOptions::from_str(connect_opts.required_db_url()?)?.kind();
+    let mut conn = crate::connect(connect_opts).await?;
+    let schema = conn.schema(table_name).await?;
+
+    // user-defined types are referred to by the Rust type we generate for them
+    let custom_types: HashMap<String, String> = schema
+        .enums
+        .iter()
+        .map(|enum_| &enum_.name)
+        .chain(schema.composites.iter().map(|composite| &composite.name))
+        .map(|name| (name.clone(), type_name(name)))
+        .collect();
+
+    let mut items = Vec::new();
+
+    for enum_ in &schema.enums {
+        items.push(render_enum(&enum_.name, &enum_.variants));
+    }
+
+    for composite in &schema.composites {
+        let sql = format!(
+            "SELECT (NULL::{}).*",
+            schema.dialect.quote_ident(&composite.name)
+        );
+
+        let mut fields = describe(&mut conn, kind, &sql, &schema, None, &custom_types)
+            .await
+            .with_context(|| format!("Failed to describe type `{}`", composite.name))?;
+
+        // attributes of a composite type cannot be declared `NOT NULL`
+        for field in &mut fields {
+            field.nullable = true;
+        }
+
+        items.push(render_struct(&composite.name, &fields, true)?);
+    }
+
+    let relations = schema
+        .tables
+        .iter()
+        .map(|table| (&table.name, true))
+        .chain(schema.views.iter().map(|view| (&view.name, false)));
+
+    for (name, is_table) in relations {
+        let sql = format!("SELECT * FROM {}", schema.dialect.quote_ident(name));
+        let table = if is_table { Some(&**name) } else { None };
+
+        let fields = describe(&mut conn, kind, &sql, &schema, table, &custom_types)
+            .await
+            .with_context(|| format!("Failed to describe `{}`", name))?;
+
+        items.push(render_struct(name, &fields, false)?);
+    }
+
+    let mut code = HEADER.to_string();
+
+    for item in items {
+        code.push('\n');
+        code.push_str(&item);
+    }
+
+    match output {
+        Some(path) => {
+            fs::write(path, code).with_context(|| format!("Failed to write `{}`", path))?;
+            println!("Generated {}", style(path).cyan());
+        }
+
+        None => print!("{}", code),
+    }
+
+    Ok(())
+}
+
+async fn describe(
+    conn: &mut AnyConnection,
+    kind: AnyKind,
+    sql: &str,
+    schema: &Schema,
+    table: Option<&str>,
+    custom_types: &HashMap<String, String>,
+) -> anyhow::Result<Vec<Field>> {
+    let describe = conn.describe(sql).await?;
+
+    let fields = describe
+        .columns()
+        .iter()
+        .enumerate()
+        .map(|(i, column)| {
+            let type_name = column.type_info().name();
+
+            // SQLite reports `INTEGER` for both `i32` and `i64` columns so we need the declared
+            // type to tell them apart
+            let declared = table
+                .and_then(|table| schema.table(table))
+                .and_then(|table| table.column(column.name()))
+                .map(|column| &*column.data_type);
+
+            let ty = rust_type(kind, type_name, declared)
+                .map(str::to_owned)
+                .or_else(|| custom_types.get(type_name).cloned())
+                .ok_or_else(|| type_name.to_owned());
+
+            Field {
+                name: column.name().to_owned(),
+                ty,
+                // like `query!()`, we assume a column is nullable unless we know otherwise
+                nullable: describe.nullable(i).unwrap_or(true),
+            }
+        })
+        .collect();
+
+    Ok(fields)
+}
+
+// these should match the types `sqlx-macros/src/database/*.rs` picks for `query!()`,
+// preferring `chrono` and `bigdecimal` where there is a choice
+fn rust_type(kind: AnyKind, type_name: &str, declared: Option<&str>) -> Option<&'static str> {
+    match kind {
+        #[cfg(feature = "postgres")]
+        AnyKind::Postgres => postgres_type(type_name),
+
+        #[cfg(feature = "mysql")]
+        AnyKind::MySql => mysql_type(type_name),
+
+        #[cfg(feature = "sqlite")]
+        AnyKind::Sqlite => sqlite_type(type_name, declared),
+
+        #[cfg(feature = "mssql")]
+        AnyKind::Mssql => None,
+    }
+}
+
+#[cfg(feature = "postgres")]
+fn postgres_type(type_name: &str) -> Option<&'static str> {
+    Some(match type_name {
+        "BOOL" => "bool",
+        "\"CHAR\"" => "i8",
+        "INT2" => "i16",
+        "INT4" => "i32",
+        "INT8" => "i64",
+        "OID" => "u32",
+        "FLOAT4" => "f32",
+        "FLOAT8" => "f64",
+        "TEXT" | "VARCHAR" | "CHAR" | "NAME" => "String",
+        "BYTEA" => "Vec<u8>",
+        "INTERVAL" => "sqlx::postgres::types::PgInterval",
+        "MONEY" => "sqlx::postgres::types::PgMoney",
+        "UUID" => "sqlx::types::Uuid",
+        "TIME" => "sqlx::types::chrono::NaiveTime",
+        "DATE" => "sqlx::types::chrono::NaiveDate",
+        "TIMESTAMP" => "sqlx::types::chrono::NaiveDateTime",
+        "TIMESTAMPTZ" => "sqlx::types::chrono::DateTime<sqlx::types::chrono::Utc>",
+        "TIMETZ" => {
+            "sqlx::postgres::types::PgTimeTz<sqlx::types::chrono::NaiveTime, sqlx::types::chrono::FixedOffset>"
+        }
+        "NUMERIC" => "sqlx::types::BigDecimal",
+        "INET" | "CIDR" => "sqlx::types::ipnetwork::IpNetwork",
+        "MACADDR" => "sqlx::types::mac_address::MacAddress",
+        "JSON" | "JSONB" => "serde_json::Value",
+        "BIT" | "VARBIT" => "sqlx::types::BitVec",
+
+        "BOOL[]" => "Vec<bool>",
+        "\"CHAR\"[]" => "Vec<i8>",
+        "INT2[]" => "Vec<i16>",
+        "INT4[]" => "Vec<i32>",
+        "INT8[]" => "Vec<i64>",
+        "OID[]" => "Vec<u32>",
+        "FLOAT4[]" => "Vec<f32>",
+        "FLOAT8[]" => "Vec<f64>",
+        "TEXT[]" | "VARCHAR[]" | "CHAR[]" | "NAME[]" => "Vec<String>",
+        "BYTEA[]" => "Vec<Vec<u8>>",
+        "MONEY[]" => "Vec<sqlx::postgres::types::PgMoney>",
+        "UUID[]" => "Vec<sqlx::types::Uuid>",
+        "TIME[]" => "Vec<sqlx::types::chrono::NaiveTime>",
+        "DATE[]" => "Vec<sqlx::types::chrono::NaiveDate>",
+        "TIMESTAMP[]" => "Vec<sqlx::types::chrono::NaiveDateTime>",
+        "TIMESTAMPTZ[]" => "Vec<sqlx::types::chrono::DateTime<sqlx::types::chrono::Utc>>",
+        "NUMERIC[]" => "Vec<sqlx::types::BigDecimal>",
+        "INET[]" | "CIDR[]" => "Vec<sqlx::types::ipnetwork::IpNetwork>",
+        "MACADDR[]" => "Vec<sqlx::types::mac_address::MacAddress>",
+        "JSON[]" | "JSONB[]" => "Vec<serde_json::Value>",
+
+        "INT4RANGE" => "sqlx::postgres::types::PgRange<i32>",
+        "INT8RANGE" => "sqlx::postgres::types::PgRange<i64>",
+        "NUMRANGE" => "sqlx::postgres::types::PgRange<sqlx::types::BigDecimal>",
+        "DATERANGE" => "sqlx::postgres::types::PgRange<sqlx::types::chrono::NaiveDate>",
+        "TSRANGE" => "sqlx::postgres::types::PgRange<sqlx::types::chrono::NaiveDateTime>",
+        "TSTZRANGE" => {
+            "sqlx::postgres::types::PgRange<sqlx::types::chrono::DateTime<sqlx::types::chrono::Utc>>"
+        }
+
+        "INT4RANGE[]" => "Vec<sqlx::postgres::types::PgRange<i32>>",
+        "INT8RANGE[]" => "Vec<sqlx::postgres::types::PgRange<i64>>",
+        "NUMRANGE[]" => "Vec<sqlx::postgres::types::PgRange<sqlx::types::BigDecimal>>",
+        "DATERANGE[]" => "Vec<sqlx::postgres::types::PgRange<sqlx::types::chrono::NaiveDate>>",
+        "TSRANGE[]" => "Vec<sqlx::postgres::types::PgRange<sqlx::types::chrono::NaiveDateTime>>",
+        "TSTZRANGE[]" => {
+            "Vec<sqlx::postgres::types::PgRange<sqlx::types::chrono::DateTime<sqlx::types::chrono::Utc>>>"
+        }
+
+        _ => return None,
+    })
+}
+
+#[cfg(feature = "mysql")]
+fn mysql_type(type_name: &str) -> Option<&'static str> {
+    Some(match type_name {
+        // `query!()` has no mapping for `bool` in MySQL
+        "TINYINT" | "BOOLEAN" => "i8",
+        "SMALLINT" => "i16",
+        "INT" | "MEDIUMINT" => "i32",
+        "BIGINT" => "i64",
+        "TINYINT UNSIGNED" => "u8",
+        "SMALLINT UNSIGNED" | "YEAR" => "u16",
+        "INT UNSIGNED" | "MEDIUMINT UNSIGNED" => "u32",
+        "BIGINT UNSIGNED" | "BIT" => "u64",
+        "FLOAT" => "f32",
+        "DOUBLE" => "f64",
+        "CHAR" | "VARCHAR" | "TINYTEXT" | "TEXT" | "MEDIUMTEXT" | "LONGTEXT" | "ENUM" => "String",
+        "BINARY" | "VARBINARY" | "TINYBLOB" | "BLOB" | "MEDIUMBLOB" | "LONGBLOB" => "Vec<u8>",
+        "TIME" => "sqlx::types::chrono::NaiveTime",
+        "DATE" => "sqlx::types::chrono::NaiveDate",
+        "DATETIME" => "sqlx::types::chrono::NaiveDateTime",
+        "TIMESTAMP" => "sqlx::types::chrono::DateTime<sqlx::types::chrono::Utc>",
+        "DECIMAL" => "sqlx::types::BigDecimal",
+        "JSON" => "serde_json::Value",
+        _ => return None,
+    })
+}
+
+#[cfg(feature = "sqlite")]
+fn sqlite_type(type_name: &str, declared: Option<&str>) -> Option<&'static str> {
+    Some(match type_name {
+        "BOOLEAN" => "bool",
+        // the same rule `SqliteTypeInfo` uses to choose between `i32` and `i64`
+        "INTEGER" if matches!(declared, Some(d) if d.eq_ignore_ascii_case("int4")) => "i32",
+        "INTEGER" => "i64",
+        "REAL" => "f64",
+        "TEXT" => "String",
+        "BLOB" => "Vec<u8>",
+        "DATETIME" => "sqlx::types::chrono::NaiveDateTime",
+        _ => return None,
+    })
+}
+
+fn render_enum(name: &str, variants: &[String]) -> String {
+    let mut code = String::new();
+
+    code.push_str("#[derive(Debug, Clone, Copy, PartialEq, Eq, sqlx::Type)]\n");
+    let _ = writeln!(
+        code,
+        "#[sqlx(type_name = {:?}, rename_all = \"snake_case\")]",
+        name
+    );
+    let _ = writeln!(code, "pub enum {} {{", type_name(name));
+
+    for variant in variants {
+        // anything `rename_all` would not turn back into the same label needs an explicit rename
+        let is_snake_case = !variant.is_empty()
+            && !variant.starts_with('_')
+            && !variant.ends_with('_')
+            && !variant.contains("__")
+            && variant.chars().all(|c| c.is_ascii_lowercase() || c == '_');
+
+        if !is_snake_case {
+            let _ = writeln!(code, "    #[sqlx(rename = {:?})]", variant);
+        }
+
+        let _ = writeln!(code, "    {},", type_name(variant));
+    }
+
+    code.push_str("}\n");
+    code
+}
+
+fn render_struct(name: &str, fields: &[Field], composite: bool) -> anyhow::Result<String> {
+    let mut code = String::new();
+
+    if composite {
+        code.push_str("#[derive(Debug, Clone, sqlx::Type)]\n");
+        let _ = writeln!(code, "#[sqlx(type_name = {:?})]", name);
+    } else {
+        code.push_str("#[derive(Debug, Clone, sqlx::FromRow)]\n");
+    }
+
+    let _ = writeln!(code, "pub struct {} {{", type_name(name));
+
+    for field in fields {
+        let ident = field_name(&field.name);
+
+        let ty = match &field.ty {
+            Ok(ty) => ty,
+            // the attributes of a composite type are decoded by position, so none can be left out
+            Err(db_type) if composite => bail!(
+                "attribute `{}` of type `{}` has type `{}`, which has no supported Rust type",
+                field.name,
+                name,
+                db_type
+            ),
+            Err(db_type) => {
+                let _ = writeln!(
+                    code,
+                    "    // `{}` has type `{}`, which has no supported Rust type",
+                    field.name, db_type
+                );
+                continue;
+            }
+        };
+
+        // the attributes of a composite type are decoded by position
+        if !composite && ident.trim_start_matches("r#") != field.name {
+            let _ = writeln!(code, "    #[sqlx(rename = {:?})]", field.name);
+        }
+
+        if field.nullable {
+            let _ = writeln!(code, "    pub {}: Option<{}>,", ident, ty);
+        } else {
+            let _ = writeln!(code, "    pub {}: {},", ident, ty);
+        }
+    }
+
+    code.push_str("}\n");
+    Ok(code)
+}
+
+/// Convert a database name to a Rust type name, e.g. `user_posts` to `UserPosts`.
+fn type_name(name: &str) -> String {
+    let mut ident = String::new();
+
+    for word in words(name) {
+        let mut chars = word.chars();
+
+        if let Some(first) = chars.next() {
+            ident.extend(first.to_uppercase());
+            ident.push_str(&chars.as_str().to_lowercase());
+        }
+    }
+
+    if ident.is_empty() || ident.starts_with(|c: char| c.is_ascii_digit()) {
+        ident.insert(0, '_');
+    }
+
+    if ident == "Self" {
+        ident.push('_');
+    }
+
+    ident
+}
+
+/// Convert a database name to a Rust field name, e.g. `createdAt` to `created_at`.
+fn field_name(name: &str) -> String {
+    let mut ident = words(name)
+        .map(|word| word.to_lowercase())
+        .collect::<Vec<_>>()
+        .join("_");
+
+    if ident.is_empty() || ident.starts_with(|c: char| c.is_ascii_digit()) {
+        ident.insert(0, '_');
+    }
+
+    match &*ident {
+        // these cannot be raw identifiers
+        "self" | "super" | "crate" => ident.push('_'),
+        _ if KEYWORDS.contains(&&*ident) => ident.insert_str(0, "r#"),
+        _ => {}
+    }
+
+    ident
+}
+
+/// Split a name into words at underscores, other punctuation and lower-to-upper case changes.
+fn words(name: &str) -> impl Iterator<Item = &str> {
+    let mut words = Vec::new();
+    let mut start = None;
+    let mut prev_lower = false;
+
+    for (i, c) in name.char_indices() {
+        if !c.is_alphanumeric() {
+            if let Some(s) = start.take() {
+                words.push(&name[s..i]);
+            }
+
+            prev_lower = false;
+            continue;
+        }
+
+        if c.is_uppercase() && prev_lower {
+            if let Some(s) = start {
+                words.push(&name[s..i]);
+            }
+
+            start = Some(i);
+        }
+
+        if start.is_none() {
+            start = Some(i);
+        }
+
+        prev_lower = c.is_lowercase() || c.is_ascii_digit();
+    }
+
+    if let Some(s) = start {
+        words.push(&name[s..]);
+    }
+
+    words.into_iter()
+}
+
+const KEYWORDS: &[&str] = &[
+    "abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "do", "dyn",
+    "else", "enum", "extern", "false", "final", "fn", "for", "if", "impl", "in", "let", "loop",
+    "macro", "match", "mod", "move", "mut", "override", "priv", "pub", "ref", "return", "static",
+    "struct", "trait", "true", "try", "type", "typeof", "unsafe", "unsized", "use", "virtual",
+    "where", "while", "yield",
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn render_struct_works() {
+        let fields = vec![
+            Field {
+                name: "id".into(),
+                ty: Ok("i64".into()),
+                nullable: false,
+            },
+            Field {
+                name: "displayName".into(),
+                ty: Ok("String".into()),
+                nullable: true,
+            },
+            Field {
+                name: "type".into(),
+                ty: Ok("Mood".into()),
+                nullable: false,
+            },
+            Field {
+                name: "location".into(),
+                ty: Err("POINT".into()),
+                nullable: true,
+            },
+        ];
+
+        assert_eq!(
+            render_struct("user_accounts", &fields, false).unwrap(),
+            r#"#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct UserAccounts {
+    pub id: i64,
+    #[sqlx(rename = "displayName")]
+    pub display_name: Option<String>,
+    pub r#type: Mood,
+    // `location` has type `POINT`, which has no supported Rust type
+}
+"#
+        );
+
+        // a composite type cannot leave out an attribute, as they are decoded by position
+        assert!(render_struct("user_account", &fields, true).is_err());
+
+        assert_eq!(
+            render_enum("mood", &["sad".into(), "so_so".into(), "Happy".into()]),
+            r#"#[derive(Debug, Clone, Copy, PartialEq, Eq, sqlx::Type)]
+#[sqlx(type_name = "mood", rename_all = "snake_case")]
+pub enum Mood {
+    Sad,
+    SoSo,
+    #[sqlx(rename = "Happy")]
+    Happy,
+}
+"#
+        );
+    }
+}
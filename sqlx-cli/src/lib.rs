@@ -1,69 +1,289 @@
 use anyhow::Result;
+use futures::Future;
+use sqlx::config::Config;
+use sqlx::{AnyConnection, Connection};
+use std::path::Path;
+use std::time::{Duration, Instant};
+use std::{cmp, env, io};
 
-use crate::opt::{Command, DatabaseCommand, MigrateCommand};
+use crate::opt::{Command, ConnectOpts, DatabaseCommand, MigrateCommand, SchemaCommand};
 
 mod database;
+mod generate;
+mod lint;
 // mod migration;
 // mod migrator;
 mod migrate;
 mod opt;
 mod prepare;
+mod query;
+mod schema;
 
 pub use crate::opt::Opt;
 
 pub async fn run(opt: Opt) -> Result<()> {
+    let config = Config::read_from(&env::current_dir()?)?;
+    let table_name = config.migrate.table_name();
+
     match opt.command {
-        Command::Migrate(migrate) => match migrate.command {
-            MigrateCommand::Add {
-                description,
-                reversible,
-            } => migrate::add(&migrate.source, &description, reversible).await?,
-            MigrateCommand::Run {
-                dry_run,
-                ignore_missing,
-                database_url,
-            } => migrate::run(&migrate.source, &database_url, dry_run, ignore_missing).await?,
-            MigrateCommand::Revert {
-                dry_run,
-                ignore_missing,
-                database_url,
-            } => migrate::revert(&migrate.source, &database_url, dry_run, ignore_missing).await?,
-            MigrateCommand::Info { database_url } => {
-                migrate::info(&migrate.source, &database_url).await?
+        Command::Migrate(migrate) => {
+            let source = migrate.source(&config).to_owned();
+            let source = &*source;
+
+            match migrate.command {
+                MigrateCommand::Add {
+                    description,
+                    reversible,
+                } => migrate::add(source, &description, reversible).await?,
+                MigrateCommand::Run {
+                    dry_run,
+                    ignore_missing,
+                    dump_schema,
+                    mut connect_opts,
+                } => {
+                    connect_opts.populate_db_url(&config);
+
+                    migrate::run(
+                        source,
+                        table_name,
+                        &connect_opts,
+                        dry_run,
+                        ignore_missing,
+                        dump_schema.as_deref(),
+                    )
+                    .await?
+                }
+                MigrateCommand::Revert {
+                    dry_run,
+                    ignore_missing,
+                    mut connect_opts,
+                } => {
+                    connect_opts.populate_db_url(&config);
+
+                    migrate::revert(source, table_name, &connect_opts, dry_run, ignore_missing)
+                        .await?
+                }
+                MigrateCommand::Info { mut connect_opts } => {
+                    connect_opts.populate_db_url(&config);
+
+                    migrate::info(source, table_name, &connect_opts).await?
+                }
+                MigrateCommand::Squash {
+                    up_to,
+                    allow_data_loss,
+                    scratch_database_url,
+                    mut connect_opts,
+                } => {
+                    connect_opts.populate_db_url(&config);
+
+                    migrate::squash(
+                        source,
+                        table_name,
+                        up_to,
+                        allow_data_loss,
+                        scratch_database_url.as_deref(),
+                        &connect_opts,
+                    )
+                    .await?
+                }
+                MigrateCommand::Lint {
+                    since,
+                    mut connect_opts,
+                } => {
+                    connect_opts.populate_db_url(&config);
+
+                    migrate::lint(source, connect_opts.required_db_url()?, since).await?
+                }
+                MigrateCommand::BuildScript { force } => migrate::build_script(source, force)?,
             }
-            MigrateCommand::BuildScript { force } => migrate::build_script(&migrate.source, force)?,
-        },
+        }
 
         Command::Database(database) => match database.command {
-            DatabaseCommand::Create { database_url } => database::create(&database_url).await?,
-            DatabaseCommand::Drop { yes, database_url } => {
-                database::drop(&database_url, !yes).await?
+            DatabaseCommand::Create { mut connect_opts } => {
+                connect_opts.populate_db_url(&config);
+
+                database::create(&connect_opts).await?
+            }
+            DatabaseCommand::Drop {
+                yes,
+                mut connect_opts,
+            } => {
+                connect_opts.populate_db_url(&config);
+
+                database::drop(&connect_opts, !yes).await?
             }
             DatabaseCommand::Reset {
                 yes,
                 source,
-                database_url,
-            } => database::reset(&source, &database_url, !yes).await?,
+                seed,
+                seed_source,
+                mut connect_opts,
+            } => {
+                connect_opts.populate_db_url(&config);
+
+                let source = source
+                    .as_deref()
+                    .unwrap_or_else(|| config.migrate.migrations_dir());
+                let seed_source = if seed { Some(&*seed_source) } else { None };
+
+                database::reset(source, table_name, seed_source, &connect_opts, !yes).await?
+            }
             DatabaseCommand::Setup {
                 source,
-                database_url,
-            } => database::setup(&source, &database_url).await?,
+                seed,
+                seed_source,
+                mut connect_opts,
+            } => {
+                connect_opts.populate_db_url(&config);
+
+                let source = source
+                    .as_deref()
+                    .unwrap_or_else(|| config.migrate.migrations_dir());
+                let seed_source = if seed { Some(&*seed_source) } else { None };
+
+                database::setup(source, table_name, seed_source, &connect_opts).await?
+            }
+            DatabaseCommand::Seed {
+                source,
+                rerun,
+                mut connect_opts,
+            } => {
+                connect_opts.populate_db_url(&config);
+
+                database::seed(&source, &connect_opts, rerun).await?
+            }
         },
 
+        Command::Schema(schema) => match schema.command {
+            SchemaCommand::Diff {
+                from,
+                to,
+                migration,
+                source,
+                mut connect_opts,
+            } => {
+                connect_opts.populate_db_url(&config);
+
+                let source = source
+                    .as_deref()
+                    .unwrap_or_else(|| config.migrate.migrations_dir());
+
+                let from = match from {
+                    Some(from) => connect_opts.with_db_url(&from),
+                    None => connect_opts.clone(),
+                };
+
+                schema::diff(
+                    &from,
+                    &connect_opts.with_db_url(&to),
+                    migration.as_deref(),
+                    source,
+                    table_name,
+                )
+                .await?
+            }
+        },
+
+        // like the query macros, `--list` reads the database URL from the environment
         Command::Prepare {
-            check: false,
             merged,
+            list: true,
             args,
-            database_url,
-        } => prepare::run(&database_url, merged, args)?,
+            ..
+        } => prepare::list(merged, args)?,
 
         Command::Prepare {
-            check: true,
+            check,
             merged,
+            split,
+            prune,
             args,
-            database_url,
-        } => prepare::check(&database_url, merged, args)?,
+            mut connect_opts,
+            ..
+        } => {
+            connect_opts.populate_db_url(&config);
+
+            let url = connect_opts.required_db_url()?;
+            let dir = Path::new(config.macros.offline_dir.as_deref().unwrap_or(""));
+
+            if check {
+                prepare::check(url, dir, merged, split, args)?
+            } else {
+                prepare::run(url, dir, merged, split, prune, args)?
+            }
+        }
+
+        Command::Generate {
+            output,
+            mut connect_opts,
+        } => {
+            connect_opts.populate_db_url(&config);
+
+            generate::run(&connect_opts, table_name, output.as_deref()).await?
+        }
+
+        Command::Query {
+            command,
+            mut connect_opts,
+        } => {
+            connect_opts.populate_db_url(&config);
+
+            query::run(&connect_opts, command.as_deref()).await?
+        }
     };
 
     Ok(())
 }
+
+/// Connect to the database, retrying for up to `opts.connect_timeout` seconds.
+async fn connect(opts: &ConnectOpts) -> anyhow::Result<AnyConnection> {
+    retry_connect_errors(opts, AnyConnection::connect).await
+}
+
+/// Run an operation against the database server at `opts.database_url`, retrying it with
+/// backoff for up to `opts.connect_timeout` seconds while the server refuses connections.
+async fn retry_connect_errors<'a, F, Fut, T>(
+    opts: &'a ConnectOpts,
+    mut connect: F,
+) -> anyhow::Result<T>
+where
+    F: FnMut(&'a str) -> Fut,
+    Fut: Future<Output = sqlx::Result<T>> + 'a,
+{
+    let url = opts.required_db_url()?;
+    let deadline = Instant::now() + Duration::from_secs(opts.connect_timeout);
+    let mut delay = Duration::from_millis(100);
+
+    loop {
+        match connect(url).await {
+            Err(e) if is_starting_up(&e) && Instant::now() < deadline => {
+                tokio::time::sleep(cmp::min(
+                    delay,
+                    deadline.saturating_duration_since(Instant::now()),
+                ))
+                .await;
+                delay = cmp::min(delay * 2, Duration::from_secs(5));
+            }
+            result => return Ok(result?),
+        }
+    }
+}
+
+// errors from a database server that is not (yet) accepting connections, as opposed to
+// errors like failed authentication that retrying would not fix
+fn is_starting_up(error: &sqlx::Error) -> bool {
+    match error {
+        sqlx::Error::Io(e) => matches!(
+            e.kind(),
+            io::ErrorKind::ConnectionRefused
+                | io::ErrorKind::ConnectionReset
+                | io::ErrorKind::ConnectionAborted
+                | io::ErrorKind::UnexpectedEof
+        ),
+
+        // `cannot_connect_now` in Postgres: "the database system is starting up"
+        sqlx::Error::Database(e) => e.code().as_deref() == Some("57P03"),
+
+        _ => false,
+    }
+}
@@ -0,0 +1,714 @@
+//! A lexical linter for migration scripts that flags operations which are risky to run against
+//! a live database.
+//!
+//! A finding can be silenced by putting a comment like `-- sqlx-lint: allow(drop-column)`
+//! anywhere in the statement that triggers it, including the comment lines directly above it.
+
+use std::collections::HashSet;
+use std::fmt::{self, Display, Formatter};
+
+const ALLOW_DIRECTIVE: &str = "sqlx-lint:";
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Rule {
+    AddNotNullColumn,
+    CreateIndexNonConcurrently,
+    RenameColumn,
+    DropColumn,
+    AlterColumnType,
+}
+
+impl Rule {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Rule::AddNotNullColumn => "add-not-null-column",
+            Rule::CreateIndexNonConcurrently => "create-index-non-concurrently",
+            Rule::RenameColumn => "rename-column",
+            Rule::DropColumn => "drop-column",
+            Rule::AlterColumnType => "alter-column-type",
+        }
+    }
+
+    fn explanation(&self) -> &'static str {
+        match self {
+            Rule::AddNotNullColumn => {
+                "adding a NOT NULL column without a default fails if the table has rows"
+            }
+            Rule::CreateIndexNonConcurrently => {
+                "creating an index without CONCURRENTLY blocks writes to the table until it is built"
+            }
+            Rule::RenameColumn => {
+                "renaming a column breaks application instances still using the old name"
+            }
+            Rule::DropColumn => {
+                "dropping a column breaks application instances still reading it"
+            }
+            Rule::AlterColumnType => {
+                "changing the type of a column may rewrite the whole table while holding an exclusive lock"
+            }
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Finding {
+    /// The 1-based line of the statement in the migration script.
+    pub line: usize,
+    pub rule: Rule,
+    pub table: String,
+    pub column: Option<String>,
+}
+
+impl Display for Finding {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match &self.column {
+            Some(column) => write!(f, "`{}.{}`: ", self.table, column)?,
+            None => write!(f, "`{}`: ", self.table)?,
+        }
+
+        f.write_str(self.rule.explanation())
+    }
+}
+
+/// Lint a single migration script.
+///
+/// `postgres` enables the checks that only apply to PostgreSQL.
+pub fn lint(sql: &str, postgres: bool) -> Vec<Finding> {
+    let mut findings = Vec::new();
+
+    // operations on tables created in the same script cannot affect existing rows or readers
+    let mut created_tables = HashSet::new();
+
+    for statement in split_statements(sql) {
+        let mut statement_findings = Vec::new();
+        lint_statement(
+            &statement,
+            postgres,
+            &mut created_tables,
+            &mut statement_findings,
+        );
+
+        findings.extend(
+            statement_findings
+                .into_iter()
+                .filter(|finding| !statement.allowed.iter().any(|r| r == finding.rule.name())),
+        );
+    }
+
+    findings
+}
+
+/// The line of the first statement of a migration script that inserts, changes or deletes data
+/// instead of changing the schema, if any.
+pub fn first_data_statement(sql: &str) -> Option<usize> {
+    const DATA: &[&str] = &["INSERT", "UPDATE", "DELETE", "COPY", "MERGE", "REPLACE"];
+
+    split_statements(sql)
+        .into_iter()
+        .find(|statement| match statement.tokens.first() {
+            Some(first) if first.is_any_word(DATA) => true,
+            // a common table expression that modifies data
+            Some(first) if first.is_word("WITH") => {
+                statement.tokens.iter().any(|t| t.is_any_word(DATA))
+            }
+            _ => false,
+        })
+        .map(|statement| statement.line)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token {
+    Word(String),
+    Quoted(String),
+    Literal,
+    Punct(char),
+}
+
+impl Token {
+    fn is_word(&self, keyword: &str) -> bool {
+        matches!(self, Token::Word(word) if word.eq_ignore_ascii_case(keyword))
+    }
+
+    fn is_any_word(&self, keywords: &[&str]) -> bool {
+        keywords.iter().any(|keyword| self.is_word(keyword))
+    }
+
+    fn name(&self) -> Option<String> {
+        match self {
+            // unquoted identifiers are case-insensitive
+            Token::Word(word) => Some(word.to_ascii_lowercase()),
+            Token::Quoted(name) => Some(name.clone()),
+            _ => None,
+        }
+    }
+}
+
+struct Statement {
+    line: usize,
+    tokens: Vec<Token>,
+    allowed: Vec<String>,
+}
+
+fn split_statements(sql: &str) -> Vec<Statement> {
+    let chars: Vec<char> = sql.chars().collect();
+    let mut statements = Vec::new();
+
+    let mut line = 1;
+    let mut current = Statement {
+        line: 0,
+        tokens: Vec::new(),
+        allowed: Vec::new(),
+    };
+
+    // the line of the last `;`, so that a comment trailing it applies to the statement it ends
+    let mut terminated_at = None;
+
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        let start_line = line;
+
+        let token = match c {
+            '\n' => {
+                line += 1;
+                i += 1;
+                continue;
+            }
+
+            c if c.is_whitespace() => {
+                i += 1;
+                continue;
+            }
+
+            '-' if chars.get(i + 1) == Some(&'-') => {
+                let end = find(&chars, i, |c| c == '\n').unwrap_or(chars.len());
+                let comment = collect(&chars[i + 2..end]);
+                parse_allowed(
+                    &comment,
+                    allowed_for(&mut statements, &mut current, terminated_at, line),
+                );
+                i = end;
+                continue;
+            }
+
+            '/' if chars.get(i + 1) == Some(&'*') => {
+                let end = find_seq(&chars, i + 2, "*/").unwrap_or(chars.len());
+                let comment = collect(&chars[i + 2..end]);
+                parse_allowed(
+                    &comment,
+                    allowed_for(&mut statements, &mut current, terminated_at, line),
+                );
+                line += comment.matches('\n').count();
+                i = end + 2;
+                continue;
+            }
+
+            ';' => {
+                i += 1;
+
+                if !current.tokens.is_empty() {
+                    statements.push(current);
+                    terminated_at = Some(line);
+                }
+
+                current = Statement {
+                    line: 0,
+                    tokens: Vec::new(),
+                    allowed: Vec::new(),
+                };
+
+                continue;
+            }
+
+            '\'' | '"' | '`' => {
+                let (text, end) = quoted(&chars, i, c);
+                line += text.matches('\n').count();
+                i = end;
+
+                if c == '\'' {
+                    Token::Literal
+                } else {
+                    Token::Quoted(text)
+                }
+            }
+
+            '$' => match dollar_quote_tag(&chars, i) {
+                Some(tag) => {
+                    let end = find_seq(&chars, i + tag.len(), &tag).unwrap_or(chars.len());
+                    line += collect(&chars[i..end]).matches('\n').count();
+                    i = end + tag.len();
+                    Token::Literal
+                }
+
+                None => {
+                    i += 1;
+                    Token::Punct('$')
+                }
+            },
+
+            c if c.is_alphanumeric() || c == '_' => {
+                let end = find(&chars, i, |c| {
+                    !(c.is_alphanumeric() || c == '_' || c == '$')
+                })
+                .unwrap_or(chars.len());
+                let word = collect(&chars[i..end]);
+                i = end;
+                Token::Word(word)
+            }
+
+            c => {
+                i += 1;
+                Token::Punct(c)
+            }
+        };
+
+        if current.tokens.is_empty() {
+            current.line = start_line;
+        }
+
+        current.tokens.push(token);
+    }
+
+    if !current.tokens.is_empty() {
+        statements.push(current);
+    }
+
+    statements
+}
+
+fn allowed_for<'a>(
+    statements: &'a mut [Statement],
+    current: &'a mut Statement,
+    terminated_at: Option<usize>,
+    line: usize,
+) -> &'a mut Vec<String> {
+    match statements.last_mut() {
+        Some(last) if current.tokens.is_empty() && terminated_at == Some(line) => &mut last.allowed,
+        _ => &mut current.allowed,
+    }
+}
+
+fn collect(chars: &[char]) -> String {
+    chars.iter().collect()
+}
+
+fn find(chars: &[char], from: usize, pred: impl Fn(char) -> bool) -> Option<usize> {
+    (from..chars.len()).find(|&i| pred(chars[i]))
+}
+
+fn find_seq(chars: &[char], from: usize, seq: &str) -> Option<usize> {
+    let seq: Vec<char> = seq.chars().collect();
+    (from..chars.len()).find(|&i| chars[i..].starts_with(&seq))
+}
+
+// returns the contents of a quoted string or identifier and the index after the closing quote;
+// a doubled quote character is an escaped quote
+fn quoted(chars: &[char], start: usize, quote: char) -> (String, usize) {
+    let mut text = String::new();
+    let mut i = start + 1;
+
+    while i < chars.len() {
+        if chars[i] == quote {
+            if chars.get(i + 1) == Some(&quote) {
+                text.push(quote);
+                i += 2;
+                continue;
+            }
+
+            return (text, i + 1);
+        }
+
+        text.push(chars[i]);
+        i += 1;
+    }
+
+    (text, i)
+}
+
+// `$$` or `$tag$` opening a Postgres dollar-quoted string
+fn dollar_quote_tag(chars: &[char], start: usize) -> Option<String> {
+    let end = find(chars, start + 1, |c| !(c.is_alphanumeric() || c == '_'))?;
+
+    if chars[end] == '$' && !matches!(chars.get(start + 1), Some(c) if c.is_ascii_digit()) {
+        Some(collect(&chars[start..=end]))
+    } else {
+        None
+    }
+}
+
+// parses `sqlx-lint: allow(rule-a, rule-b)` out of a comment
+fn parse_allowed(comment: &str, allowed: &mut Vec<String>) {
+    let directive = match comment.find(ALLOW_DIRECTIVE) {
+        Some(i) => comment[i + ALLOW_DIRECTIVE.len()..].trim_start(),
+        None => return,
+    };
+
+    let rules = directive
+        .strip_prefix("allow(")
+        .and_then(|rest| rest.split(')').next());
+
+    if let Some(rules) = rules {
+        allowed.extend(rules.split(',').map(|rule| rule.trim().to_owned()));
+    }
+}
+
+fn lint_statement(
+    statement: &Statement,
+    postgres: bool,
+    created_tables: &mut HashSet<String>,
+    findings: &mut Vec<Finding>,
+) {
+    let tokens = &statement.tokens[..];
+    let line = statement.line;
+
+    match tokens.first() {
+        Some(first) if first.is_any_word(&["CREATE", "ALTER"]) => (),
+        _ => return,
+    }
+
+    // CREATE [TEMPORARY] TABLE [IF NOT EXISTS] <name>
+    if let Some(i) = tokens.iter().position(|t| t.is_word("TABLE")) {
+        if tokens[0].is_word("CREATE") {
+            if let Some(name) = table_name(&tokens[i + 1..]) {
+                created_tables.insert(name);
+            }
+
+            return;
+        }
+    }
+
+    // CREATE [UNIQUE] INDEX [CONCURRENTLY] [IF NOT EXISTS] [<name>] ON [ONLY] <table>
+    if tokens[0].is_word("CREATE") {
+        let is_index = tokens
+            .iter()
+            .take_while(|t| !t.is_word("ON"))
+            .any(|t| t.is_word("INDEX"));
+
+        if !is_index || !postgres {
+            return;
+        }
+
+        let concurrently = tokens.iter().any(|t| t.is_word("CONCURRENTLY"));
+
+        let table = tokens
+            .iter()
+            .position(|t| t.is_word("ON"))
+            .and_then(|i| table_name(&tokens[i + 1..]));
+
+        if let Some(table) = table {
+            if !concurrently && !created_tables.contains(&table) {
+                findings.push(Finding {
+                    line,
+                    rule: Rule::CreateIndexNonConcurrently,
+                    table,
+                    column: None,
+                });
+            }
+        }
+
+        return;
+    }
+
+    // ALTER TABLE [IF EXISTS] [ONLY] <name> <action> [, <action> ...]
+    if !matches!(tokens.get(1), Some(t) if t.is_word("TABLE")) {
+        return;
+    }
+
+    let (table, rest) = match split_table_name(&tokens[2..]) {
+        Some(split) => split,
+        None => return,
+    };
+
+    if created_tables.contains(&table) {
+        return;
+    }
+
+    for action in split_top_level(rest) {
+        lint_alter_action(action, line, &table, findings);
+    }
+}
+
+fn lint_alter_action(action: &[Token], line: usize, table: &str, findings: &mut Vec<Finding>) {
+    let mut finding = |rule, column: Option<String>| {
+        findings.push(Finding {
+            line,
+            rule,
+            table: table.to_owned(),
+            column,
+        })
+    };
+
+    let first = match action.first() {
+        Some(first) => first,
+        None => return,
+    };
+
+    const NOT_A_COLUMN: &[&str] = &[
+        "CONSTRAINT",
+        "PRIMARY",
+        "UNIQUE",
+        "FOREIGN",
+        "CHECK",
+        "INDEX",
+        "KEY",
+        "FULLTEXT",
+        "SPATIAL",
+        "EXCLUDE",
+    ];
+
+    if first.is_word("ADD") {
+        let column = column_name(&action[1..], NOT_A_COLUMN);
+
+        let not_null = action
+            .windows(2)
+            .any(|w| w[0].is_word("NOT") && w[1].is_word("NULL"));
+
+        let has_default = action
+            .iter()
+            .any(|t| t.is_any_word(&["DEFAULT", "GENERATED", "AUTO_INCREMENT"]));
+
+        if let Some(column) = column {
+            // the serial types of Postgres default to the next value of a sequence
+            let serial = action.windows(2).any(|w| {
+                w[0].name().as_ref() == Some(&column)
+                    && w[1].is_any_word(&[
+                        "SERIAL",
+                        "BIGSERIAL",
+                        "SMALLSERIAL",
+                        "SERIAL2",
+                        "SERIAL4",
+                        "SERIAL8",
+                    ])
+            });
+
+            if not_null && !has_default && !serial {
+                finding(Rule::AddNotNullColumn, Some(column));
+            }
+        }
+    } else if first.is_word("DROP") {
+        if let Some(column) = column_name(&action[1..], NOT_A_COLUMN) {
+            finding(Rule::DropColumn, Some(column));
+        }
+    } else if first.is_word("RENAME") {
+        // `RENAME TO <table>` renames the table itself
+        if let Some(column) = column_name(&action[1..], &["TO", "CONSTRAINT", "INDEX", "KEY"]) {
+            finding(Rule::RenameColumn, Some(column));
+        }
+    } else if first.is_word("ALTER") {
+        let changes_type = action.iter().any(|t| t.is_word("TYPE"));
+
+        if let (true, Some(column)) = (changes_type, column_name(&action[1..], NOT_A_COLUMN)) {
+            finding(Rule::AlterColumnType, Some(column));
+        }
+    } else if first.is_word("MODIFY") {
+        if let Some(column) = column_name(&action[1..], NOT_A_COLUMN) {
+            finding(Rule::AlterColumnType, Some(column));
+        }
+    } else if first.is_word("CHANGE") {
+        // CHANGE [COLUMN] <old name> <new name> <definition>
+        let rest = match action.get(1) {
+            Some(t) if t.is_word("COLUMN") => &action[2..],
+            _ => &action[1..],
+        };
+
+        let old = rest.first().and_then(Token::name);
+        let new = rest.get(1).and_then(Token::name);
+
+        if let (Some(old), Some(new)) = (old, new) {
+            if old != new {
+                finding(Rule::RenameColumn, Some(old.clone()));
+            }
+
+            finding(Rule::AlterColumnType, Some(old));
+        }
+    }
+}
+
+// the name of the column an `ADD`, `DROP`, `RENAME`, or `ALTER` action applies to,
+// skipping the optional `COLUMN` and `IF [NOT] EXISTS` keywords
+fn column_name(tokens: &[Token], not_a_column: &[&str]) -> Option<String> {
+    let mut tokens = tokens;
+
+    if tokens.first()?.is_any_word(not_a_column) {
+        return None;
+    }
+
+    if tokens.first()?.is_word("COLUMN") {
+        tokens = &tokens[1..];
+    }
+
+    while tokens.first()?.is_any_word(&["IF", "NOT", "EXISTS"]) {
+        tokens = &tokens[1..];
+    }
+
+    tokens.first()?.name()
+}
+
+fn table_name(tokens: &[Token]) -> Option<String> {
+    split_table_name(tokens).map(|(name, _)| name)
+}
+
+// skips `IF [NOT] EXISTS` and `ONLY`, then reads a possibly schema-qualified name,
+// returning the unqualified name and the remaining tokens
+fn split_table_name(tokens: &[Token]) -> Option<(String, &[Token])> {
+    let mut tokens = tokens;
+
+    while tokens
+        .first()?
+        .is_any_word(&["IF", "NOT", "EXISTS", "ONLY"])
+    {
+        tokens = &tokens[1..];
+    }
+
+    let mut name = tokens.first()?.name()?;
+    tokens = &tokens[1..];
+
+    while let (Some(Token::Punct('.')), Some(next)) = (tokens.first(), tokens.get(1)) {
+        name = next.name()?;
+        tokens = &tokens[2..];
+    }
+
+    Some((name, tokens))
+}
+
+// splits on commas that are not nested in parentheses
+fn split_top_level(tokens: &[Token]) -> Vec<&[Token]> {
+    let mut parts = Vec::new();
+    let mut depth = 0usize;
+    let mut start = 0;
+
+    for (i, token) in tokens.iter().enumerate() {
+        match token {
+            Token::Punct('(') => depth += 1,
+            Token::Punct(')') => depth = depth.saturating_sub(1),
+            Token::Punct(',') if depth == 0 => {
+                parts.push(&tokens[start..i]);
+                start = i + 1;
+            }
+            _ => (),
+        }
+    }
+
+    parts.push(&tokens[start..]);
+    parts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rules(sql: &str, postgres: bool) -> Vec<(usize, &'static str)> {
+        lint(sql, postgres)
+            .into_iter()
+            .map(|finding| (finding.line, finding.rule.name()))
+            .collect()
+    }
+
+    #[test]
+    fn it_flags_risky_operations() {
+        let sql = r#"
+ALTER TABLE users ADD COLUMN age INT NOT NULL;
+ALTER TABLE users ADD COLUMN city TEXT NOT NULL DEFAULT 'unknown';
+CREATE INDEX users_age ON users (age);
+CREATE INDEX CONCURRENTLY users_city ON users (city);
+ALTER TABLE users RENAME COLUMN name TO full_name;
+ALTER TABLE public.users DROP COLUMN legacy, ALTER COLUMN id TYPE BIGINT;
+ALTER TABLE users DROP CONSTRAINT users_age_check, RENAME TO people;
+"#;
+
+        assert_eq!(
+            rules(sql, true),
+            vec![
+                (2, "add-not-null-column"),
+                (4, "create-index-non-concurrently"),
+                (6, "rename-column"),
+                (7, "drop-column"),
+                (7, "alter-column-type"),
+            ]
+        );
+
+        // `CONCURRENTLY` is Postgres-only
+        assert!(!rules(sql, false).contains(&(4, "create-index-non-concurrently")));
+    }
+
+    #[test]
+    fn it_treats_serial_columns_as_having_a_default() {
+        let sql = r#"
+ALTER TABLE users ADD COLUMN id SERIAL NOT NULL;
+ALTER TABLE users ADD COLUMN big_id bigserial NOT NULL;
+ALTER TABLE users ADD COLUMN small_id SmallSerial NOT NULL;
+ALTER TABLE users ADD COLUMN serial INT NOT NULL;
+ALTER TABLE users ADD COLUMN IF NOT EXISTS "Serial" serial8 NOT NULL;
+"#;
+
+        assert_eq!(rules(sql, true), vec![(5, "add-not-null-column")]);
+    }
+
+    #[test]
+    fn it_flags_mysql_column_changes() {
+        let sql = "ALTER TABLE `users` MODIFY `age` BIGINT; ALTER TABLE users CHANGE name full_name TEXT;";
+
+        assert_eq!(
+            rules(sql, false),
+            vec![
+                (1, "alter-column-type"),
+                (1, "rename-column"),
+                (1, "alter-column-type"),
+            ]
+        );
+    }
+
+    #[test]
+    fn it_ignores_tables_created_in_the_same_script() {
+        let sql = r#"
+CREATE TABLE IF NOT EXISTS "Users" (id BIGINT PRIMARY KEY);
+ALTER TABLE "Users" ADD COLUMN name TEXT NOT NULL;
+CREATE INDEX users_name ON "Users" (name);
+"#;
+
+        assert!(rules(sql, true).is_empty());
+    }
+
+    #[test]
+    fn it_respects_allow_comments() {
+        let sql = r#"
+-- sqlx-lint: allow(drop-column)
+ALTER TABLE users DROP COLUMN legacy;
+
+ALTER TABLE users DROP COLUMN other; /* sqlx-lint: allow(drop-column, rename-column) */
+
+ALTER TABLE users DROP COLUMN flagged;
+"#;
+
+        assert_eq!(rules(sql, true), vec![(7, "drop-column")]);
+    }
+
+    #[test]
+    fn it_skips_strings_and_comments() {
+        let sql = r#"
+-- ALTER TABLE users DROP COLUMN commented;
+CREATE FUNCTION f() RETURNS void AS $$ ALTER TABLE users DROP COLUMN quoted; $$ LANGUAGE sql;
+INSERT INTO notes (body) VALUES ('ALTER TABLE users DROP COLUMN text;');
+"#;
+
+        assert!(rules(sql, true).is_empty());
+    }
+
+    #[test]
+    fn it_finds_data_statements() {
+        let sql = r#"
+CREATE TABLE roles (id INT PRIMARY KEY REFERENCES x (id) ON DELETE CASCADE ON UPDATE CASCADE);
+-- INSERT INTO roles VALUES (0);
+CREATE FUNCTION f() RETURNS void AS $$ DELETE FROM roles; $$ LANGUAGE sql;
+INSERT INTO roles (id) VALUES (1);
+"#;
+
+        assert_eq!(first_data_statement(sql), Some(5));
+        assert_eq!(
+            first_data_statement("WITH gone AS (DELETE FROM roles RETURNING id) SELECT 1;"),
+            Some(1)
+        );
+        assert_eq!(first_data_statement("CREATE TABLE t (id INT);"), None);
+    }
+}
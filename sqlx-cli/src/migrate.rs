@@ -1,8 +1,15 @@
+use crate::lint;
+use crate::opt::ConnectOpts;
 use anyhow::{bail, Context};
 use chrono::Utc;
 use console::style;
-use sqlx::migrate::{AppliedMigration, Migrate, MigrateError, MigrationType, Migrator};
+use sqlx::any::Any;
+use sqlx::migrate::{
+    AppliedMigration, Migrate, MigrateDatabase, MigrateError, Migration, MigrationType, Migrator,
+    Schema,
+};
 use sqlx::{AnyConnection, Connection};
+use std::borrow::Cow;
 use std::collections::{HashMap, HashSet};
 use std::fs::{self, File};
 use std::io::Write;
@@ -14,6 +21,7 @@ fn create_file(
     file_prefix: &str,
     description: &str,
     migration_type: MigrationType,
+    content: &str,
 ) -> anyhow::Result<()> {
     use std::path::PathBuf;
 
@@ -30,7 +38,7 @@ fn create_file(
 
     let mut file = File::create(&path).context("Failed to create migration file")?;
 
-    file.write_all(migration_type.file_content().as_bytes())?;
+    file.write_all(content.as_bytes())?;
 
     Ok(())
 }
@@ -63,12 +71,14 @@ pub async fn add(
             &file_prefix,
             description,
             MigrationType::ReversibleUp,
+            MigrationType::ReversibleUp.file_content(),
         )?;
         create_file(
             migration_source,
             &file_prefix,
             description,
             MigrationType::ReversibleDown,
+            MigrationType::ReversibleDown.file_content(),
         )?;
     } else {
         create_file(
@@ -76,6 +86,7 @@ pub async fn add(
             &file_prefix,
             description,
             MigrationType::Simple,
+            MigrationType::Simple.file_content(),
         )?;
     }
 
@@ -107,14 +118,62 @@ See: https://docs.rs/sqlx/0.5/sqlx/macro.migrate.html
     Ok(())
 }
 
-pub async fn info(migration_source: &str, uri: &str) -> anyhow::Result<()> {
+/// Create a new migration with the given scripts, reversible if the existing migrations are.
+pub(crate) async fn add_with_sql(
+    migration_source: &str,
+    description: &str,
+    up_sql: &str,
+    down_sql: &str,
+) -> anyhow::Result<()> {
+    fs::create_dir_all(migration_source).context("Unable to create migrations directory")?;
+
+    let migrator = Migrator::new(Path::new(migration_source)).await?;
+    let reversible = migrator
+        .iter()
+        .any(|migration| migration.migration_type.is_reversible());
+
+    let file_prefix = Utc::now().format("%Y%m%d%H%M%S").to_string();
+
+    if reversible {
+        create_file(
+            migration_source,
+            &file_prefix,
+            description,
+            MigrationType::ReversibleUp,
+            up_sql,
+        )?;
+        create_file(
+            migration_source,
+            &file_prefix,
+            description,
+            MigrationType::ReversibleDown,
+            down_sql,
+        )?;
+    } else {
+        create_file(
+            migration_source,
+            &file_prefix,
+            description,
+            MigrationType::Simple,
+            up_sql,
+        )?;
+    }
+
+    Ok(())
+}
+
+pub async fn info(
+    migration_source: &str,
+    table_name: &str,
+    connect_opts: &ConnectOpts,
+) -> anyhow::Result<()> {
     let migrator = Migrator::new(Path::new(migration_source)).await?;
-    let mut conn = AnyConnection::connect(uri).await?;
+    let mut conn = crate::connect(connect_opts).await?;
 
-    conn.ensure_migrations_table().await?;
+    conn.ensure_migrations_table_in(table_name).await?;
 
     let applied_migrations: HashMap<_, _> = conn
-        .list_applied_migrations()
+        .list_applied_migrations_in(table_name)
         .await?
         .into_iter()
         .map(|m| (m.version, m))
@@ -136,6 +195,302 @@ pub async fn info(migration_source: &str, uri: &str) -> anyhow::Result<()> {
     Ok(())
 }
 
+// the first line of the migration written by `squash`, so it can be recognized when `squash`
+// is run again to mark it as applied in other databases
+const SQUASHED_HEADER: &str = "-- generated by `sqlx migrate squash`";
+
+pub async fn squash(
+    migration_source: &str,
+    table_name: &str,
+    up_to: i64,
+    allow_data_loss: bool,
+    scratch_uri: Option<&str>,
+    connect_opts: &ConnectOpts,
+) -> anyhow::Result<()> {
+    let migrator = Migrator::new(Path::new(migration_source)).await?;
+
+    let squashed: Vec<&Migration> = migrator.iter().filter(|m| m.version <= up_to).collect();
+
+    if !squashed.iter().any(|m| m.version == up_to) {
+        bail!("no migration with version {} found", up_to);
+    }
+
+    let is_squashed = squashed
+        .iter()
+        .all(|m| m.version == up_to && m.sql.starts_with(SQUASHED_HEADER));
+
+    // the squashed migration only recreates the schema, and the original files are removed
+    if !is_squashed && !allow_data_loss {
+        for migration in &squashed {
+            if migration.migration_type.is_down_migration() {
+                continue;
+            }
+
+            if let Some(line) = lint::first_data_statement(&migration.sql) {
+                bail!(
+                    "migration {} ({}) changes data on line {}, which the squashed migration \
+                     would not include; move the data elsewhere or pass --allow-data-loss",
+                    migration.version,
+                    migration.description,
+                    line
+                );
+            }
+        }
+    }
+
+    // check the database before touching any files so it is not left behind
+    let mut conn = match connect_opts.database_url {
+        Some(_) => Some(connect_for_baseline(connect_opts, table_name, up_to).await?),
+        None => None,
+    };
+
+    // the squashed migration with the files that replace the original ones, if not squashed yet
+    let (baseline, files) = if is_squashed {
+        let baseline = squashed
+            .iter()
+            .find(|m| !m.migration_type.is_down_migration())
+            .map(|m| (*m).clone())
+            .context("squashed migration not found")?;
+
+        (baseline, Vec::new())
+    } else {
+        let scratch_uri =
+            scratch_uri.context("--scratch-database-url is required to squash migrations")?;
+
+        let scratch_opts = connect_opts.with_db_url(scratch_uri);
+        let schema = squashed_schema(&scratch_opts, table_name, &squashed).await?;
+
+        let sql = format!(
+            "{} from the migrations up to and including version {}\n\
+             -- data inserted by those migrations is not included\n\n{}",
+            SQUASHED_HEADER, up_to, schema
+        );
+
+        let files = if squashed[0].migration_type.is_reversible() {
+            vec![
+                (MigrationType::ReversibleUp, sql),
+                (
+                    MigrationType::ReversibleDown,
+                    format!(
+                        "{}\n-- the squashed migrations cannot be reverted\n",
+                        SQUASHED_HEADER
+                    ),
+                ),
+            ]
+        } else {
+            vec![(MigrationType::Simple, sql)]
+        };
+
+        // the same as reading the file back, so its checksum matches what `run` will compute
+        let baseline = Migration::new(
+            up_to,
+            Cow::Borrowed("squashed"),
+            files[0].0,
+            Cow::Owned(files[0].1.clone()),
+        );
+
+        (baseline, files)
+    };
+
+    // mark the baseline as applied before touching any files, so a failure leaves the
+    // original migrations in place; running `squash` again picks up where it failed
+    if let Some(conn) = &mut conn {
+        let applied = conn.list_applied_migrations_in(table_name).await?;
+
+        match applied.iter().find(|m| m.version == up_to) {
+            Some(applied) if applied.checksum == baseline.checksum => {
+                println!(
+                    "Squashed migration {} is already applied",
+                    style(up_to).cyan()
+                );
+            }
+            Some(_) => {
+                conn.apply_baseline(table_name, &baseline).await?;
+
+                println!(
+                    "Marked {}/{} {} as applied in place of the original migrations",
+                    style(baseline.version).cyan(),
+                    style(baseline.migration_type.label()).green(),
+                    baseline.description,
+                );
+            }
+            // a new database applies the squashed migration like any other
+            None => (),
+        }
+    }
+
+    if !files.is_empty() {
+        remove_migration_files(migration_source, up_to)?;
+
+        for (migration_type, sql) in &files {
+            write_squashed_file(migration_source, up_to, *migration_type, sql)?;
+        }
+
+        println!(
+            "Squashed {} migrations into version {}",
+            squashed
+                .iter()
+                .filter(|m| !m.migration_type.is_down_migration())
+                .count(),
+            style(up_to).cyan()
+        );
+    }
+
+    Ok(())
+}
+
+async fn connect_for_baseline(
+    connect_opts: &ConnectOpts,
+    table_name: &str,
+    up_to: i64,
+) -> anyhow::Result<AnyConnection> {
+    let mut conn = crate::connect(connect_opts).await?;
+
+    conn.ensure_migrations_table_in(table_name).await?;
+
+    if let Some(version) = conn.dirty_version_in(table_name).await? {
+        bail!(MigrateError::dirty(version, table_name));
+    }
+
+    let applied = conn.list_applied_migrations_in(table_name).await?;
+
+    let partially_applied =
+        applied.iter().any(|m| m.version < up_to) && !applied.iter().any(|m| m.version == up_to);
+
+    if partially_applied {
+        bail!(
+            "the database at {} has not applied all migrations up to version {}; run them before squashing",
+            connect_opts.required_db_url()?,
+            up_to
+        );
+    }
+
+    Ok(conn)
+}
+
+async fn squashed_schema(
+    scratch_opts: &ConnectOpts,
+    table_name: &str,
+    migrations: &[&Migration],
+) -> anyhow::Result<Schema> {
+    let scratch_uri = scratch_opts.required_db_url()?;
+
+    if crate::retry_connect_errors(scratch_opts, Any::database_exists).await? {
+        bail!(
+            "the scratch database at {} already exists; it must not exist as it will be dropped",
+            scratch_uri
+        );
+    }
+
+    Any::create_database(scratch_uri).await?;
+
+    let schema = async {
+        let mut conn = crate::connect(scratch_opts).await?;
+
+        conn.ensure_migrations_table_in(table_name).await?;
+
+        for migration in migrations {
+            if !migration.migration_type.is_down_migration() {
+                conn.apply_in(table_name, migration).await?;
+            }
+        }
+
+        let schema = conn.schema(table_name).await?;
+        conn.close().await?;
+
+        Ok::<_, anyhow::Error>(schema)
+    }
+    .await;
+
+    Any::drop_database(scratch_uri).await?;
+
+    schema
+}
+
+fn remove_migration_files(migration_source: &str, up_to: i64) -> anyhow::Result<()> {
+    for entry in fs::read_dir(migration_source)? {
+        let path = entry?.path();
+
+        let file_name = match path.file_name() {
+            Some(file_name) => file_name.to_string_lossy(),
+            None => continue,
+        };
+
+        // same format as the migration source: <VERSION>_<DESCRIPTION>.sql
+        let parts = file_name.splitn(2, '_').collect::<Vec<_>>();
+
+        if parts.len() != 2 || !parts[1].ends_with(".sql") {
+            continue;
+        }
+
+        if matches!(parts[0].parse::<i64>(), Ok(version) if version <= up_to) {
+            fs::remove_file(&path)
+                .with_context(|| format!("Failed to remove {}", path.display()))?;
+        }
+    }
+
+    Ok(())
+}
+
+fn write_squashed_file(
+    migration_source: &str,
+    version: i64,
+    migration_type: MigrationType,
+    sql: &str,
+) -> anyhow::Result<()> {
+    let path = Path::new(migration_source).join(format!(
+        "{}_squashed{}",
+        version,
+        migration_type.suffix()
+    ));
+
+    println!("Creating {}", style(path.display()).cyan());
+
+    fs::write(&path, sql).context("Failed to create migration file")?;
+
+    Ok(())
+}
+
+pub async fn lint(migration_source: &str, uri: &str, since: Option<i64>) -> anyhow::Result<()> {
+    let migrator = Migrator::new(Path::new(migration_source)).await?;
+    let postgres = crate::prepare::get_db_kind(uri)? == "PostgreSQL";
+
+    let mut count = 0;
+
+    for migration in migrator.iter() {
+        // reverting a migration is expected to undo its changes destructively
+        if migration.migration_type.is_down_migration()
+            || matches!(since, Some(since) if migration.version < since)
+        {
+            continue;
+        }
+
+        for finding in lint::lint(&migration.sql, postgres) {
+            count += 1;
+
+            println!(
+                "{}/{} {} (line {}): {}",
+                style(migration.version).cyan(),
+                style(finding.rule.name()).yellow(),
+                migration.description,
+                finding.line,
+                finding,
+            );
+        }
+    }
+
+    if count > 0 {
+        bail!(
+            "found {} unsafe operation(s); add `-- sqlx-lint: allow(<rule>)` above a statement to allow it",
+            count
+        );
+    }
+
+    println!("No unsafe operations found");
+
+    Ok(())
+}
+
 fn validate_applied_migrations(
     applied_migrations: &[AppliedMigration],
     migrator: &Migrator,
@@ -158,21 +513,23 @@ fn validate_applied_migrations(
 
 pub async fn run(
     migration_source: &str,
-    uri: &str,
+    table_name: &str,
+    connect_opts: &ConnectOpts,
     dry_run: bool,
     ignore_missing: bool,
+    dump_schema: Option<&str>,
 ) -> anyhow::Result<()> {
     let migrator = Migrator::new(Path::new(migration_source)).await?;
-    let mut conn = AnyConnection::connect(uri).await?;
+    let mut conn = crate::connect(connect_opts).await?;
 
-    conn.ensure_migrations_table().await?;
+    conn.ensure_migrations_table_in(table_name).await?;
 
-    let version = conn.dirty_version().await?;
+    let version = conn.dirty_version_in(table_name).await?;
     if let Some(version) = version {
-        bail!(MigrateError::Dirty(version));
+        bail!(MigrateError::dirty(version, table_name));
     }
 
-    let applied_migrations = conn.list_applied_migrations().await?;
+    let applied_migrations = conn.list_applied_migrations_in(table_name).await?;
     validate_applied_migrations(&applied_migrations, &migrator, ignore_missing)?;
 
     let applied_migrations: HashMap<_, _> = applied_migrations
@@ -196,7 +553,7 @@ pub async fn run(
                 let elapsed = if dry_run {
                     Duration::new(0, 0)
                 } else {
-                    conn.apply(migration).await?
+                    conn.apply_in(table_name, migration).await?
                 };
                 let text = if dry_run { "Can apply" } else { "Applied" };
 
@@ -212,26 +569,37 @@ pub async fn run(
         }
     }
 
+    // the schema would not reflect the pending migrations on a dry run
+    if let (Some(path), false) = (dump_schema, dry_run) {
+        let schema = conn.schema(table_name).await?;
+
+        fs::write(path, schema.to_string())
+            .with_context(|| format!("Failed to write schema to `{}`", path))?;
+
+        println!("Dumped schema to {}", style(path).cyan());
+    }
+
     Ok(())
 }
 
 pub async fn revert(
     migration_source: &str,
-    uri: &str,
+    table_name: &str,
+    connect_opts: &ConnectOpts,
     dry_run: bool,
     ignore_missing: bool,
 ) -> anyhow::Result<()> {
     let migrator = Migrator::new(Path::new(migration_source)).await?;
-    let mut conn = AnyConnection::connect(uri).await?;
+    let mut conn = crate::connect(connect_opts).await?;
 
-    conn.ensure_migrations_table().await?;
+    conn.ensure_migrations_table_in(table_name).await?;
 
-    let version = conn.dirty_version().await?;
+    let version = conn.dirty_version_in(table_name).await?;
     if let Some(version) = version {
-        bail!(MigrateError::Dirty(version));
+        bail!(MigrateError::dirty(version, table_name));
     }
 
-    let applied_migrations = conn.list_applied_migrations().await?;
+    let applied_migrations = conn.list_applied_migrations_in(table_name).await?;
     validate_applied_migrations(&applied_migrations, &migrator, ignore_missing)?;
 
     let applied_migrations: HashMap<_, _> = applied_migrations
@@ -251,7 +619,7 @@ pub async fn revert(
             let elapsed = if dry_run {
                 Duration::new(0, 0)
             } else {
-                conn.revert(migration).await?
+                conn.revert_in(table_name, migration).await?
             };
             let text = if dry_run { "Can apply" } else { "Applied" };
 
@@ -1,4 +1,7 @@
+use anyhow::Context;
 use clap::Clap;
+use sqlx::config::Config;
+use std::env;
 
 #[derive(Clap, Debug)]
 pub struct Opt {
@@ -14,7 +17,7 @@ pub enum Command {
     /// Generate query metadata to support offline compile-time verification.
     ///
     /// Saves metadata for all invocations of `query!` and related macros to `sqlx-data.json`
-    /// in the current directory, overwriting if needed.
+    /// (or `sqlx-data/` with `--split`) in the current directory, overwriting if needed.
     ///
     /// During project compilation, the absence of the `DATABASE_URL` environment variable or
     /// the presence of `SQLX_OFFLINE` (with a value of `true` or `1`) will constrain the
@@ -30,17 +33,64 @@ pub enum Command {
         #[clap(long)]
         merged: bool,
 
+        /// Write one file per query into a `sqlx-data/` directory instead of a single
+        /// `sqlx-data.json`, to avoid merge conflicts. Implied if `sqlx-data/` already exists.
+        #[clap(long)]
+        split: bool,
+
+        /// Also remove the data of queries that no longer exist from the `sqlx-data.json` or
+        /// `sqlx-data/` of every other package in the workspace. Requires `--merged`, so that
+        /// the queries of all packages are known.
+        #[clap(long, requires = "merged", conflicts_with = "check")]
+        prune: bool,
+
+        /// List every invocation of `query!` and related macros as `file:line`, with the hash
+        /// of its query and whether it was checked against the database (online), against the
+        /// saved query metadata (offline) or could not be checked (missing). Exits with 1 if any
+        /// query is missing. No query metadata is written.
+        #[clap(long, conflicts_with_all = &["check", "split", "prune"])]
+        list: bool,
+
         /// Arguments to be passed to `cargo rustc ...`.
         #[clap(last = true)]
         args: Vec<String>,
 
-        /// Location of the DB, by default will be read from the DATABASE_URL env var
-        #[clap(long, short = 'D', env)]
-        database_url: String,
+        #[clap(flatten)]
+        connect_opts: ConnectOpts,
+    },
+
+    /// Generate Rust types for the tables, views and user-defined types of your database.
+    ///
+    /// Emits a `#[derive(sqlx::FromRow)]` struct for each table and view, and a
+    /// `#[derive(sqlx::Type)]` enum or struct for each Postgres enum or composite type.
+    /// Column types are chosen the same way as for `query!()`.
+    #[clap(alias = "gen")]
+    Generate {
+        /// File to write the generated code to. Printed to stdout if omitted.
+        #[clap(long, short = 'o')]
+        output: Option<String>,
+
+        #[clap(flatten)]
+        connect_opts: ConnectOpts,
+    },
+
+    /// Run SQL against your database in an interactive shell.
+    ///
+    /// Statements are read from stdin and rows are printed as tables. Use `\describe <sql>` to
+    /// see the columns, types and nullability that `query!()` infers for a query.
+    Query {
+        /// Run the given statements (or `\describe <sql>`) and exit.
+        #[clap(long, short = 'c')]
+        command: Option<String>,
+
+        #[clap(flatten)]
+        connect_opts: ConnectOpts,
     },
 
     #[clap(alias = "mig")]
     Migrate(MigrateOpt),
+
+    Schema(SchemaOpt),
 }
 
 /// Group of commands for creating and dropping your database.
@@ -54,9 +104,8 @@ pub struct DatabaseOpt {
 pub enum DatabaseCommand {
     /// Creates the database specified in your DATABASE_URL.
     Create {
-        /// Location of the DB, by default will be read from the DATABASE_URL env var
-        #[clap(long, short = 'D', env)]
-        database_url: String,
+        #[clap(flatten)]
+        connect_opts: ConnectOpts,
     },
 
     /// Drops the database specified in your DATABASE_URL.
@@ -66,45 +115,119 @@ pub enum DatabaseCommand {
         #[clap(short)]
         yes: bool,
 
-        /// Location of the DB, by default will be read from the DATABASE_URL env var
-        #[clap(long, short = 'D', env)]
-        database_url: String,
+        #[clap(flatten)]
+        connect_opts: ConnectOpts,
     },
 
     /// Drops the database specified in your DATABASE_URL, re-creates it, and runs any pending migrations.
+    /// With `--seed`, also applies any seeds.
     Reset {
         /// Automatic confirmation. Without this option, you will be prompted before dropping
         /// your database.
         #[clap(short)]
         yes: bool,
 
-        /// Path to folder containing migrations.
-        #[clap(long, default_value = "migrations")]
-        source: String,
+        /// Path to folder containing migrations, by default `migrations` or the
+        /// `migrations-dir` in `sqlx.toml`.
+        #[clap(long)]
+        source: Option<String>,
 
-        /// Location of the DB, by default will be read from the DATABASE_URL env var
-        #[clap(long, short = 'D', env)]
-        database_url: String,
+        /// Apply the seeds in `--seed-source` after running migrations.
+        #[clap(long)]
+        seed: bool,
+
+        /// Path to folder containing seeds.
+        #[clap(long, default_value = "seeds")]
+        seed_source: String,
+
+        #[clap(flatten)]
+        connect_opts: ConnectOpts,
     },
 
     /// Creates the database specified in your DATABASE_URL and runs any pending migrations.
+    /// With `--seed`, also applies any seeds.
     Setup {
-        /// Path to folder containing migrations.
-        #[clap(long, default_value = "migrations")]
+        /// Path to folder containing migrations, by default `migrations` or the
+        /// `migrations-dir` in `sqlx.toml`.
+        #[clap(long)]
+        source: Option<String>,
+
+        /// Apply the seeds in `--seed-source` after running migrations.
+        #[clap(long)]
+        seed: bool,
+
+        /// Path to folder containing seeds.
+        #[clap(long, default_value = "seeds")]
+        seed_source: String,
+
+        #[clap(flatten)]
+        connect_opts: ConnectOpts,
+    },
+
+    /// Applies the seeds that have not been applied to the database yet.
+    ///
+    /// Seeds are `.sql`, `.csv` or `.json` files that insert data, applied in order of their
+    /// file names and tracked in the `_sqlx_seeds` table, apart from migrations.
+    Seed {
+        /// Path to folder containing seeds.
+        #[clap(long, default_value = "seeds")]
         source: String,
 
-        /// Location of the DB, by default will be read from the DATABASE_URL env var
-        #[clap(long, short = 'D', env)]
-        database_url: String,
+        /// Apply seeds again even if they were already applied.
+        #[clap(long)]
+        rerun: bool,
+
+        #[clap(flatten)]
+        connect_opts: ConnectOpts,
+    },
+}
+
+/// Group of commands for inspecting the schema of your databases.
+#[derive(Clap, Debug)]
+pub struct SchemaOpt {
+    #[clap(subcommand)]
+    pub command: SchemaCommand,
+}
+
+#[derive(Clap, Debug)]
+pub enum SchemaCommand {
+    /// Compare the schemas of two databases of the same kind.
+    ///
+    /// Lists the enums, tables, columns, constraints, indexes and views that would have to be
+    /// added (+), removed (-) or changed (~) to turn the schema of the first database into that
+    /// of the second, and exits with 1 if there are any.
+    Diff {
+        /// Location of the database to compare from, by default the database URL of
+        /// `--database-url`.
+        #[clap(long)]
+        from: Option<String>,
+
+        /// Location of the database to compare to.
+        #[clap(long)]
+        to: String,
+
+        /// Instead of failing, create a migration with this description that applies the
+        /// differences to the first database.
+        #[clap(long)]
+        migration: Option<String>,
+
+        /// Path to folder containing migrations, by default `migrations` or the
+        /// `migrations-dir` in `sqlx.toml`.
+        #[clap(long)]
+        source: Option<String>,
+
+        #[clap(flatten)]
+        connect_opts: ConnectOpts,
     },
 }
 
 /// Group of commands for creating and running migrations.
 #[derive(Clap, Debug)]
 pub struct MigrateOpt {
-    /// Path to folder containing migrations.
-    #[clap(long, default_value = "migrations")]
-    pub source: String,
+    /// Path to folder containing migrations, by default `migrations` or the
+    /// `migrations-dir` in `sqlx.toml`.
+    #[clap(long)]
+    pub source: Option<String>,
 
     #[clap(subcommand)]
     pub command: MigrateCommand,
@@ -133,9 +256,15 @@ pub enum MigrateCommand {
         #[clap(long)]
         ignore_missing: bool,
 
-        /// Location of the DB, by default will be read from the DATABASE_URL env var
-        #[clap(long, short = 'D', env)]
-        database_url: String,
+        /// After running migrations, write the resulting database schema as DDL to this file.
+        ///
+        /// The schema is introspected through the database itself, so no external tools
+        /// like `pg_dump` or `mysqldump` are required.
+        #[clap(long)]
+        dump_schema: Option<String>,
+
+        #[clap(flatten)]
+        connect_opts: ConnectOpts,
     },
 
     /// Revert the latest migration with a down file.
@@ -148,16 +277,57 @@ pub enum MigrateCommand {
         #[clap(long)]
         ignore_missing: bool,
 
-        /// Location of the DB, by default will be read from the DATABASE_URL env var
-        #[clap(long, short = 'D', env)]
-        database_url: String,
+        #[clap(flatten)]
+        connect_opts: ConnectOpts,
     },
 
     /// List all available migrations.
     Info {
-        /// Location of the DB, by default will be read from the DATABASE_URL env var
-        #[clap(long, env)]
-        database_url: String,
+        #[clap(flatten)]
+        connect_opts: ConnectOpts,
+    },
+
+    /// Replace all migrations up to and including a version with a single migration
+    /// that creates the resulting schema.
+    ///
+    /// The schema is built by applying the original migrations to a scratch database.
+    /// Only the database at `--database-url` is marked as having applied the squashed migration
+    /// instead. Every other database that applied the original migrations, including those
+    /// migrated by an application embedding `migrate!()`, fails with a missing or mismatched
+    /// version until this is run again against it once the migrations are squashed.
+    ///
+    /// Data inserted or changed by the original migrations is not carried over, so migrations
+    /// that do so are refused unless `--allow-data-loss` is given.
+    Squash {
+        /// Version of the last migration to squash.
+        #[clap(long)]
+        up_to: i64,
+
+        /// Squash migrations that insert, change or delete data, which is lost.
+        #[clap(long)]
+        allow_data_loss: bool,
+
+        /// Location of a scratch database to build the schema in.
+        /// It must not exist yet; it is created and dropped again.
+        #[clap(long)]
+        scratch_database_url: Option<String>,
+
+        #[clap(flatten)]
+        connect_opts: ConnectOpts,
+    },
+
+    /// Check migrations for operations that are unsafe to run against a live database,
+    /// such as dropping or renaming columns.
+    ///
+    /// A finding can be allowed by adding a comment like `-- sqlx-lint: allow(drop-column)`
+    /// above the statement.
+    Lint {
+        /// Only check migrations with this version or newer.
+        #[clap(long)]
+        since: Option<i64>,
+
+        #[clap(flatten)]
+        connect_opts: ConnectOpts,
     },
 
     /// Generate a `build.rs` to trigger recompilation when a new migration is added.
@@ -169,3 +339,61 @@ pub enum MigrateCommand {
         force: bool,
     },
 }
+
+/// Arguments for connecting to the database.
+#[derive(Clap, Debug, Clone)]
+pub struct ConnectOpts {
+    /// Location of the DB, by default will be read from the DATABASE_URL env var,
+    /// or the env var named by `database-url-var` in `sqlx.toml`
+    #[clap(long, short = 'D')]
+    pub database_url: Option<String>,
+
+    /// The maximum time, in seconds, to try connecting to the database server before
+    /// returning an error. Connections are retried while the server refuses them, e.g.
+    /// because it is still starting up, but not if authentication fails.
+    #[clap(long, default_value = "10", env = "SQLX_CONNECT_TIMEOUT")]
+    pub connect_timeout: u64,
+
+    // the env var the database URL is read from, for error messages
+    #[clap(skip)]
+    database_url_var: String,
+}
+
+impl ConnectOpts {
+    /// Read the database URL from the environment if it was not given on the command line.
+    pub fn populate_db_url(&mut self, config: &Config) {
+        let var = config.common.database_url_var();
+
+        if self.database_url.is_none() {
+            self.database_url = env::var(var).ok();
+        }
+
+        self.database_url_var = var.to_owned();
+    }
+
+    /// The same options for connecting to the database at `url` instead.
+    pub fn with_db_url(&self, url: &str) -> Self {
+        ConnectOpts {
+            database_url: Some(url.to_owned()),
+            ..self.clone()
+        }
+    }
+
+    pub fn required_db_url(&self) -> anyhow::Result<&str> {
+        self.database_url.as_deref().with_context(|| {
+            format!(
+                "the `--database-url` option or the `{}` environment variable must be set",
+                self.database_url_var
+            )
+        })
+    }
+}
+
+impl MigrateOpt {
+    /// The migrations folder given on the command line or in `sqlx.toml`.
+    pub fn source<'a>(&'a self, config: &'a Config) -> &'a str {
+        self.source
+            .as_deref()
+            .unwrap_or_else(|| config.migrate.migrations_dir())
+    }
+}
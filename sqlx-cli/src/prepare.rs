@@ -3,11 +3,12 @@ use console::style;
 use remove_dir_all::remove_dir_all;
 use serde::Deserialize;
 use sqlx::any::{AnyConnectOptions, AnyKind};
+use sqlx::config::Config;
 use std::collections::BTreeMap;
 use std::fs::File;
 use std::io::{BufReader, BufWriter};
 use std::path::{Path, PathBuf};
-use std::process::Command;
+use std::process::{Command, ExitStatus};
 use std::str::FromStr;
 use std::time::SystemTime;
 use std::{env, fs};
@@ -22,8 +23,30 @@ struct DataFile {
     data: QueryData,
 }
 
-pub fn run(url: &str, merge: bool, cargo_args: Vec<String>) -> anyhow::Result<()> {
+// one file per query, named by its hash, to avoid merge conflicts in a single large file
+const DATA_DIR: &str = "sqlx-data";
+const DATA_FILE: &str = "sqlx-data.json";
+
+// the contents of a single file in `DATA_DIR`
+#[derive(serde::Serialize, serde::Deserialize)]
+struct QueryDataFile {
+    db: String,
+    #[serde(flatten)]
+    data: JsonObject,
+}
+
+/// `dir` is the directory to write the query data to, which is empty for the current directory.
+pub fn run(
+    url: &str,
+    dir: &Path,
+    merge: bool,
+    split: bool,
+    prune: bool,
+    cargo_args: Vec<String>,
+) -> anyhow::Result<()> {
     let db_kind = get_db_kind(url)?;
+    let data_dir = dir.join(DATA_DIR);
+    let data_file = dir.join(DATA_FILE);
     let data = run_prepare_step(merge, cargo_args)?;
 
     if data.is_empty() {
@@ -33,37 +56,157 @@ pub fn run(url: &str, merge: bool, cargo_args: Vec<String>) -> anyhow::Result<()
         );
     }
 
-    serde_json::to_writer_pretty(
-        BufWriter::new(
-            File::create("sqlx-data.json").context("failed to create/open `sqlx-data.json`")?,
-        ),
-        &DataFile {
-            db: db_kind.to_owned(),
-            data,
-        },
-    )
-    .context("failed to write to `sqlx-data.json`")?;
-
-    println!(
-        "query data written to `sqlx-data.json` in the current directory; \
-         please check this into version control"
-    );
+    if split || data_dir.is_dir() {
+        write_data_dir(&data_dir, db_kind, &data)?;
+
+        // the macros would prefer the directory anyway
+        if data_file.exists() {
+            fs::remove_file(&data_file)
+                .with_context(|| format!("failed to remove `{}`", data_file.display()))?;
+        }
+
+        println!(
+            "query data written to `{}/`; please check this directory into version control",
+            data_dir.display()
+        );
+    } else {
+        serde_json::to_writer_pretty(
+            BufWriter::new(
+                File::create(&data_file)
+                    .with_context(|| format!("failed to create/open `{}`", data_file.display()))?,
+            ),
+            &DataFile {
+                db: db_kind.to_owned(),
+                data: data.clone(),
+            },
+        )
+        .with_context(|| format!("failed to write to `{}`", data_file.display()))?;
+
+        println!(
+            "query data written to `{}`; please check this into version control",
+            data_file.display()
+        );
+    }
+
+    if prune {
+        prune_workspace(&data)?;
+    }
 
     Ok(())
 }
 
-pub fn check(url: &str, merge: bool, cargo_args: Vec<String>) -> anyhow::Result<()> {
-    let db_kind = get_db_kind(url)?;
-    let data = run_prepare_step(merge, cargo_args)?;
+/// Build the project with every query macro invocation saving its location, the hash of its
+/// query and whether it was checked against the database or the offline data, and list them.
+pub fn list(merge: bool, cargo_args: Vec<String>) -> anyhow::Result<()> {
+    #[derive(Deserialize)]
+    struct CallSite {
+        // only recorded with the `query-locations` feature of sqlx
+        file: Option<String>,
+        line: Option<usize>,
+        hash: String,
+        source: String,
+    }
 
-    let data_file = File::open("sqlx-data.json").context(
-        "failed to open `sqlx-data.json`; you may need to run `cargo sqlx prepare` first",
+    let cargo = cargo()?;
+    let metadata = cargo_metadata(&cargo)?;
+
+    // queries that could not be checked fail the build, but are still listed as missing
+    build(
+        &cargo,
+        &metadata,
+        merge,
+        cargo_args,
+        &[("SQLX_LIST_QUERIES", "true")],
     )?;
 
+    let pattern = metadata.target_directory.join("sqlx/call-*.json");
+
+    let mut call_sites = Vec::new();
+
+    for path in glob::glob(
+        pattern
+            .to_str()
+            .context("CARGO_TARGET_DIR not valid UTF-8")?,
+    )? {
+        let path = path?;
+        let call_site: CallSite = serde_json::from_slice(&fs::read(&path)?)
+            .with_context(|| format!("failed to read `{}`", path.display()))?;
+
+        call_sites.push(call_site);
+
+        // lazily remove the file, we don't care too much if we can't
+        let _ = fs::remove_file(&path);
+    }
+
+    if call_sites.is_empty() {
+        println!(
+            "{} no queries found; please ensure that the `offline` feature is enabled in sqlx",
+            style("warning:").yellow()
+        );
+
+        return Ok(());
+    }
+
+    call_sites.sort_by(|a, b| (&a.file, a.line, &a.hash).cmp(&(&b.file, b.line, &b.hash)));
+
+    let mut missing = 0;
+
+    for call_site in &call_sites {
+        let source = match &*call_site.source {
+            "online" => style(&call_site.source).green(),
+            "offline" => style(&call_site.source).yellow(),
+            _ => {
+                missing += 1;
+                style(&call_site.source).red()
+            }
+        };
+
+        let location = match (&call_site.file, call_site.line) {
+            (Some(file), Some(line)) => format!("{}:{}", file, line),
+            _ => "<unknown location>".to_string(),
+        };
+
+        println!("{}  {}  {}", location, call_site.hash, source);
+    }
+
+    if missing > 0 {
+        bail!(
+            "{} of {} queries could not be checked against the database or the offline data",
+            missing,
+            call_sites.len()
+        );
+    }
+
+    Ok(())
+}
+
+pub fn check(
+    url: &str,
+    dir: &Path,
+    merge: bool,
+    split: bool,
+    cargo_args: Vec<String>,
+) -> anyhow::Result<()> {
+    let db_kind = get_db_kind(url)?;
+    let data_dir = dir.join(DATA_DIR);
+    let data_file = dir.join(DATA_FILE);
+    let data = run_prepare_step(merge, cargo_args)?;
+
     let DataFile {
         db: expected_db,
         data: saved_data,
-    } = serde_json::from_reader(BufReader::new(data_file))?;
+    } = if split || data_dir.is_dir() {
+        read_data_dir(&data_dir, db_kind)?
+    } else {
+        let data_file = File::open(&data_file).with_context(|| {
+            format!(
+                "failed to open `{}`; you may need to run `cargo sqlx prepare` first",
+                data_file.display()
+            )
+        })?;
+
+        serde_json::from_reader(BufReader::new(data_file))?
+    };
 
     if db_kind != expected_db {
         bail!(
@@ -74,75 +217,401 @@ pub fn check(url: &str, merge: bool, cargo_args: Vec<String>) -> anyhow::Result<
     }
 
     if data != saved_data {
+        print!("{}", describe_changes(&saved_data, &data));
         bail!("`cargo sqlx prepare` needs to be rerun")
     }
 
     Ok(())
 }
 
-fn run_prepare_step(merge: bool, cargo_args: Vec<String>) -> anyhow::Result<QueryData> {
-    anyhow::ensure!(
-        Path::new("Cargo.toml").exists(),
-        r#"Failed to read `Cargo.toml`.
-hint: This command only works in the manifest directory of a Cargo package."#
-    );
+// a report of the queries that were added, removed or changed since the data was saved,
+// so that the `query!()` invocation responsible can be found
+fn describe_changes(saved: &QueryData, current: &QueryData) -> String {
+    let mut report = String::new();
+
+    for (hash, query_data) in current {
+        match saved.get(hash) {
+            None => push_query("added", hash, query_data, &mut report),
+            Some(saved_data) if saved_data != query_data => {
+                push_query("changed", hash, query_data, &mut report);
+
+                for change in describe_query_changes(saved_data, query_data) {
+                    report.push_str("    - ");
+                    report.push_str(&change);
+                    report.push('\n');
+                }
+
+                report.push('\n');
+            }
+            Some(_) => (),
+        }
+    }
 
-    // path to the Cargo executable
-    let cargo = env::var("CARGO")
-        .context("`prepare` subcommand may only be invoked as `cargo sqlx prepare`")?;
+    for (hash, query_data) in saved {
+        if !current.contains_key(hash) {
+            push_query("removed", hash, query_data, &mut report);
+        }
+    }
 
-    let output = Command::new(&cargo)
-        .args(&["metadata", "--format-version=1"])
-        .output()
-        .context("Could not fetch metadata")?;
+    report
+}
 
-    #[derive(Deserialize)]
-    struct Metadata {
-        target_directory: PathBuf,
+fn push_query(kind: &str, hash: &str, query_data: &serde_json::Value, report: &mut String) {
+    report.push_str(&format!("{} query {}:\n", kind, hash));
+
+    if let Some(query) = query_data["query"].as_str() {
+        for line in query.trim().lines() {
+            report.push_str("    ");
+            report.push_str(line);
+            report.push('\n');
+        }
+    }
+
+    report.push('\n');
+}
+
+// the differences in the described columns and parameters of the same query
+fn describe_query_changes(saved: &serde_json::Value, current: &serde_json::Value) -> Vec<String> {
+    let saved_columns = columns(&saved["describe"]);
+    let current_columns = columns(&current["describe"]);
+
+    let mut changes = Vec::new();
+
+    for (name, (type_info, nullable)) in &current_columns {
+        match saved_columns
+            .iter()
+            .find(|(saved_name, _)| saved_name == name)
+        {
+            None => changes.push(format!(
+                "column `{}` added: {}, {}",
+                name, type_info, nullable
+            )),
+            Some((_, (saved_type_info, saved_nullable))) => {
+                if saved_type_info != type_info {
+                    changes.push(format!(
+                        "column `{}` type: {} -> {}",
+                        name, saved_type_info, type_info
+                    ));
+                }
+
+                if saved_nullable != nullable {
+                    changes.push(format!(
+                        "column `{}` nullability: {} -> {}",
+                        name, saved_nullable, nullable
+                    ));
+                }
+            }
+        }
     }
 
-    let metadata: Metadata =
-        serde_json::from_slice(&output.stdout).context("Invalid `cargo metadata` output")?;
+    for (name, _) in &saved_columns {
+        if !current_columns
+            .iter()
+            .any(|(current_name, _)| current_name == name)
+        {
+            changes.push(format!("column `{}` removed", name));
+        }
+    }
 
-    // try removing the target/sqlx directory before running, as stale files
-    // have repeatedly caused issues in the past.
-    let _ = remove_dir_all(metadata.target_directory.join("sqlx"));
+    if changes.is_empty() && saved_columns != current_columns {
+        changes.push("columns reordered".to_owned());
+    }
 
-    let check_status = if merge {
-        let check_status = Command::new(&cargo).arg("clean").status()?;
+    let saved_parameters = &saved["describe"]["parameters"];
+    let current_parameters = &current["describe"]["parameters"];
 
-        if !check_status.success() {
-            bail!("`cargo clean` failed with status: {}", check_status);
+    if saved_parameters != current_parameters {
+        changes.push(format!(
+            "parameters: {} -> {}",
+            parameters(saved_parameters),
+            parameters(current_parameters)
+        ));
+    }
+
+    if changes.is_empty() {
+        changes.push("description changed".to_owned());
+    }
+
+    changes
+}
+
+// the name, type and nullability of each column in a serialized `Describe`
+fn columns(describe: &serde_json::Value) -> Vec<(String, (String, &'static str))> {
+    let empty = Vec::new();
+    let columns = describe["columns"].as_array().unwrap_or(&empty);
+
+    columns
+        .iter()
+        .enumerate()
+        .map(|(i, column)| {
+            let name = column["name"].as_str().unwrap_or_default().to_owned();
+
+            let nullable = match describe["nullable"][i].as_bool() {
+                Some(true) => "nullable",
+                Some(false) => "not null",
+                None => "unknown nullability",
+            };
+
+            (name, (type_name(&column["type_info"]), nullable))
+        })
+        .collect()
+}
+
+// parameters are either a list of types or just their number, if the database can't tell
+fn parameters(parameters: &serde_json::Value) -> String {
+    if let Some(types) = parameters["Left"].as_array() {
+        let types: Vec<String> = types.iter().map(type_name).collect();
+        format!("({})", types.join(", "))
+    } else {
+        format!("{} unknown", parameters["Right"])
+    }
+}
+
+fn type_name(type_info: &serde_json::Value) -> String {
+    match type_info.as_str() {
+        Some(name) => name.to_owned(),
+        // the serialized form of some types is a structure
+        None => type_info.to_string(),
+    }
+}
+
+fn write_data_dir(data_dir: &Path, db_kind: &str, data: &QueryData) -> anyhow::Result<()> {
+    fs::create_dir_all(data_dir)
+        .with_context(|| format!("failed to create `{}/`", data_dir.display()))?;
+
+    for (hash, query_data) in data {
+        let query_data = match query_data {
+            serde_json::Value::Object(query_data) => query_data.clone(),
+            _ => bail!("expected query data for {} to be an object", hash),
+        };
+
+        let path = data_dir_path(data_dir, hash);
+
+        serde_json::to_writer_pretty(
+            BufWriter::new(
+                File::create(&path)
+                    .with_context(|| format!("failed to create/open `{}`", path.display()))?,
+            ),
+            &QueryDataFile {
+                db: db_kind.to_owned(),
+                data: query_data,
+            },
+        )
+        .with_context(|| format!("failed to write to `{}`", path.display()))?;
+    }
+
+    // prune the files of queries that no longer exist
+    for (hash, path) in data_dir_files(data_dir)? {
+        if !data.contains_key(&hash) {
+            fs::remove_file(&path)
+                .with_context(|| format!("failed to remove `{}`", path.display()))?;
         }
+    }
 
-        Command::new(&cargo)
-            .arg("check")
-            .args(cargo_args)
-            .env(
-                "RUSTFLAGS",
-                format!(
-                    "--cfg __sqlx_recompile_trigger=\"{}\"",
-                    SystemTime::UNIX_EPOCH.elapsed()?.as_millis()
-                ),
-            )
-            .env("SQLX_OFFLINE", "false")
-            .status()?
+    Ok(())
+}
+
+fn read_data_dir(data_dir: &Path, db_kind: &str) -> anyhow::Result<DataFile> {
+    let mut db = None;
+    let mut data = QueryData::new();
+
+    for (hash, path) in data_dir_files(data_dir)? {
+        let file =
+            File::open(&path).with_context(|| format!("failed to open `{}`", path.display()))?;
+
+        let query_data: QueryDataFile = serde_json::from_reader(BufReader::new(file))
+            .with_context(|| format!("failed to read `{}`", path.display()))?;
+
+        match &db {
+            Some(db) if *db != query_data.db => bail!(
+                "`{}` contains data for {}, but other files contain data for {}",
+                path.display(),
+                query_data.db,
+                db
+            ),
+            Some(_) => (),
+            None => db = Some(query_data.db),
+        }
+
+        data.insert(hash, serde_json::Value::Object(query_data.data));
+    }
+
+    Ok(DataFile {
+        // an empty directory is valid for any database
+        db: db.unwrap_or_else(|| db_kind.to_owned()),
+        data,
+    })
+}
+
+fn data_dir_path(data_dir: &Path, hash: &str) -> PathBuf {
+    data_dir.join(format!("query-{}.json", hash))
+}
+
+// the hashes and paths of all query data files in `DATA_DIR`
+fn data_dir_files(data_dir: &Path) -> anyhow::Result<Vec<(String, PathBuf)>> {
+    let mut files = Vec::new();
+
+    for entry in fs::read_dir(data_dir)
+        .with_context(|| format!("failed to read `{}/`", data_dir.display()))?
+    {
+        let path = entry?.path();
+
+        let hash = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .and_then(|name| name.strip_prefix("query-"))
+            .and_then(|name| name.strip_suffix(".json"));
+
+        if let Some(hash) = hash {
+            files.push((hash.to_owned(), path.clone()));
+        }
+    }
+
+    Ok(files)
+}
+
+// removes the data of queries that no longer exist from the offline data of every package in the
+// workspace, which is left behind e.g. in the packages' own data after switching to `--merged`
+fn prune_workspace(data: &QueryData) -> anyhow::Result<()> {
+    let metadata = cargo_metadata(&cargo()?)?;
+
+    let mut dirs = Vec::new();
+
+    for dir in metadata
+        .packages
+        .iter()
+        .filter_map(|package| package.manifest_path.parent())
+        .chain(Some(&*metadata.workspace_root))
+    {
+        let config = Config::read_from(dir)?;
+
+        dirs.push(match &config.macros.offline_dir {
+            Some(offline_dir) => dir.join(offline_dir),
+            None => dir.to_owned(),
+        });
+    }
+
+    dirs.sort();
+    dirs.dedup();
+
+    for dir in dirs {
+        prune_data_file(&dir.join(DATA_FILE), data)?;
+        prune_data_dir(&dir.join(DATA_DIR), data)?;
+    }
+
+    Ok(())
+}
+
+fn prune_data_file(path: &Path, data: &QueryData) -> anyhow::Result<()> {
+    if !path.exists() {
+        return Ok(());
+    }
+
+    let file = File::open(path).with_context(|| format!("failed to open `{}`", path.display()))?;
+
+    let mut data_file: DataFile = serde_json::from_reader(BufReader::new(file))
+        .with_context(|| format!("failed to read `{}`", path.display()))?;
+
+    let count = data_file.data.len();
+    data_file.data.retain(|hash, _| data.contains_key(hash));
+    let pruned = count - data_file.data.len();
+
+    if pruned == 0 {
+        return Ok(());
+    }
+
+    if data_file.data.is_empty() {
+        fs::remove_file(path).with_context(|| format!("failed to remove `{}`", path.display()))?;
     } else {
-        Command::new(&cargo)
-            .arg("rustc")
-            .args(cargo_args)
-            .arg("--")
-            .arg("--emit")
-            .arg("dep-info,metadata")
-            // set an always-changing cfg so we can consistently trigger recompile
-            .arg("--cfg")
-            .arg(format!(
-                "__sqlx_recompile_trigger=\"{}\"",
-                SystemTime::UNIX_EPOCH.elapsed()?.as_millis()
-            ))
-            .env("SQLX_OFFLINE", "false")
-            .status()?
-    };
+        serde_json::to_writer_pretty(
+            BufWriter::new(
+                File::create(path)
+                    .with_context(|| format!("failed to create/open `{}`", path.display()))?,
+            ),
+            &data_file,
+        )
+        .with_context(|| format!("failed to write to `{}`", path.display()))?;
+    }
+
+    println!("pruned {} queries from `{}`", pruned, path.display());
+
+    Ok(())
+}
+
+fn prune_data_dir(data_dir: &Path, data: &QueryData) -> anyhow::Result<()> {
+    if !data_dir.is_dir() {
+        return Ok(());
+    }
+
+    let files = data_dir_files(data_dir)?;
+    let mut pruned = 0;
+
+    for (hash, path) in &files {
+        if !data.contains_key(hash) {
+            fs::remove_file(path)
+                .with_context(|| format!("failed to remove `{}`", path.display()))?;
+
+            pruned += 1;
+        }
+    }
+
+    if pruned == 0 {
+        return Ok(());
+    }
+
+    if pruned == files.len() {
+        // ignore any other files in the directory
+        let _ = fs::remove_dir(data_dir);
+    }
+
+    println!("pruned {} queries from `{}/`", pruned, data_dir.display());
+
+    Ok(())
+}
+
+#[derive(Deserialize)]
+struct Metadata {
+    target_directory: PathBuf,
+    workspace_root: PathBuf,
+    packages: Vec<Package>,
+}
+
+#[derive(Deserialize)]
+struct Package {
+    manifest_path: PathBuf,
+}
+
+// path to the Cargo executable
+fn cargo() -> anyhow::Result<String> {
+    anyhow::ensure!(
+        Path::new("Cargo.toml").exists(),
+        r#"Failed to read `Cargo.toml`.
+hint: This command only works in the manifest directory of a Cargo package."#
+    );
+
+    env::var("CARGO").context("`prepare` subcommand may only be invoked as `cargo sqlx prepare`")
+}
+
+fn cargo_metadata(cargo: &str) -> anyhow::Result<Metadata> {
+    let output = Command::new(cargo)
+        .args(["metadata", "--format-version=1", "--no-deps"])
+        .output()
+        .context("Could not fetch metadata")?;
+
+    serde_json::from_slice(&output.stdout).context("Invalid `cargo metadata` output")
+}
+
+fn run_prepare_step(merge: bool, cargo_args: Vec<String>) -> anyhow::Result<QueryData> {
+    let cargo = cargo()?;
+    let metadata = cargo_metadata(&cargo)?;
+
+    let check_status = build(
+        &cargo,
+        &metadata,
+        merge,
+        cargo_args,
+        &[("SQLX_OFFLINE", "false")],
+    )?;
 
     if !check_status.success() {
         bail!("`cargo check` failed with status: {}", check_status);
@@ -183,7 +652,58 @@ hint: This command only works in the manifest directory of a Cargo package."#
     Ok(data)
 }
 
-fn get_db_kind(url: &str) -> anyhow::Result<&'static str> {
+// compiles the package, or the whole workspace with `merge`, so that every query macro runs again
+fn build(
+    cargo: &str,
+    metadata: &Metadata,
+    merge: bool,
+    cargo_args: Vec<String>,
+    envs: &[(&str, &str)],
+) -> anyhow::Result<ExitStatus> {
+    // try removing the target/sqlx directory before running, as stale files
+    // have repeatedly caused issues in the past.
+    let _ = remove_dir_all(metadata.target_directory.join("sqlx"));
+
+    let status = if merge {
+        let check_status = Command::new(cargo).arg("clean").status()?;
+
+        if !check_status.success() {
+            bail!("`cargo clean` failed with status: {}", check_status);
+        }
+
+        Command::new(cargo)
+            .arg("check")
+            .args(cargo_args)
+            .env(
+                "RUSTFLAGS",
+                format!(
+                    "--cfg __sqlx_recompile_trigger=\"{}\"",
+                    SystemTime::UNIX_EPOCH.elapsed()?.as_millis()
+                ),
+            )
+            .envs(envs.iter().copied())
+            .status()?
+    } else {
+        Command::new(cargo)
+            .arg("rustc")
+            .args(cargo_args)
+            .arg("--")
+            .arg("--emit")
+            .arg("dep-info,metadata")
+            // set an always-changing cfg so we can consistently trigger recompile
+            .arg("--cfg")
+            .arg(format!(
+                "__sqlx_recompile_trigger=\"{}\"",
+                SystemTime::UNIX_EPOCH.elapsed()?.as_millis()
+            ))
+            .envs(envs.iter().copied())
+            .status()?
+    };
+
+    Ok(status)
+}
+
+pub(crate) fn get_db_kind(url: &str) -> anyhow::Result<&'static str> {
     let options = AnyConnectOptions::from_str(&url)?;
 
     // these should match the values of `DatabaseExt::NAME` in `sqlx-macros`
@@ -208,6 +728,54 @@ mod tests {
     use serde_json::json;
     use std::assert_eq;
 
+    #[test]
+    fn describe_changes_works() {
+        let describe = |type_info: &str, nullable: bool| {
+            json!({
+                "columns": [{ "name": "id", "ordinal": 0, "type_info": type_info }],
+                "parameters": { "Left": ["Int4"] },
+                "nullable": [nullable]
+            })
+        };
+
+        let mut saved = QueryData::new();
+        saved.insert(
+            "a".to_owned(),
+            json!({ "query": "SELECT id FROM users", "describe": describe("Int4", false) }),
+        );
+        saved.insert(
+            "b".to_owned(),
+            json!({ "query": "SELECT 1", "describe": describe("Int4", false) }),
+        );
+
+        let mut current = QueryData::new();
+        current.insert(
+            "a".to_owned(),
+            json!({ "query": "SELECT id FROM users", "describe": describe("Int8", true) }),
+        );
+        current.insert(
+            "c".to_owned(),
+            json!({ "query": "\nSELECT 2\n", "describe": describe("Int4", false) }),
+        );
+
+        assert_eq!(
+            describe_changes(&saved, &current),
+            "changed query a:
+    SELECT id FROM users
+
+    - column `id` type: Int4 -> Int8
+    - column `id` nullability: not null -> nullable
+
+added query c:
+    SELECT 2
+
+removed query b:
+    SELECT 1
+
+"
+        );
+    }
+
     #[test]
     fn data_file_serialization_works() {
         let data_file = DataFile {
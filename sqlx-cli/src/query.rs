@@ -0,0 +1,236 @@
+use crate::opt::ConnectOpts;
+use anyhow::bail;
+use console::style;
+use futures::TryStreamExt;
+use promptly::{prompt_opt, ReadlineError};
+use sqlx::any::AnyRow;
+use sqlx::{AnyConnection, Column, Executor, Row, TypeInfo, ValueRef};
+use std::cmp;
+
+const HELP: &str = r"Statements are run once a line ends with `;`.
+
+  \describe <sql>  show the columns, types and nullability `query!()` would see
+  \help            show this message
+  \quit            exit";
+
+pub async fn run(connect_opts: &ConnectOpts, command: Option<&str>) -> anyhow::Result<()> {
+    let mut conn = crate::connect(connect_opts).await?;
+
+    if let Some(command) = command {
+        return execute(&mut conn, command).await;
+    }
+
+    let mut buf = String::new();
+    let mut failed = false;
+
+    loop {
+        let line: Option<String> = match prompt_opt(if buf.is_empty() { "sqlx" } else { "  ->" }) {
+            Ok(line) => line,
+            Err(ReadlineError::Eof) | Err(ReadlineError::Interrupted) => break,
+            Err(e) => return Err(e.into()),
+        };
+
+        let line = match line {
+            Some(line) => line,
+            None => continue,
+        };
+
+        if buf.is_empty() && line.starts_with('\\') {
+            if matches!(&*line, "\\q" | "\\quit") {
+                break;
+            }
+        } else {
+            buf.push_str(&line);
+            buf.push('\n');
+
+            if !line.ends_with(';') {
+                continue;
+            }
+        }
+
+        let input = if buf.is_empty() {
+            line
+        } else {
+            std::mem::take(&mut buf)
+        };
+
+        // keep going so that a typo doesn't end the session, but remember it for the exit status
+        if let Err(e) = execute(&mut conn, &input).await {
+            eprintln!("{} {}", style("error:").bold().red(), e);
+            failed = true;
+        }
+    }
+
+    if !buf.trim().is_empty() {
+        execute(&mut conn, &buf).await?;
+    }
+
+    if failed {
+        bail!("one or more statements failed");
+    }
+
+    Ok(())
+}
+
+async fn execute(conn: &mut AnyConnection, input: &str) -> anyhow::Result<()> {
+    let input = input.trim();
+
+    if let Some(command) = input.strip_prefix('\\') {
+        let (name, arg) = match command.find(char::is_whitespace) {
+            Some(i) => (&command[..i], command[i..].trim()),
+            None => (command, ""),
+        };
+
+        return match name {
+            "d" | "describe" if !arg.is_empty() => describe(conn, arg).await,
+            "?" | "h" | "help" => {
+                println!("{}", HELP);
+                Ok(())
+            }
+            _ => bail!("unknown command `\\{}`, see `\\help`", name),
+        };
+    }
+
+    // without arguments, statements are sent as a simple query so any number of them can be
+    // run at once, and most databases send back every value as text
+    let mut stream = conn.fetch_many(input);
+    let mut rows = Vec::new();
+
+    while let Some(step) = stream.try_next().await? {
+        if step.is_right() {
+            rows.extend(step.right());
+            continue;
+        }
+
+        if rows.is_empty() {
+            let rows_affected = step.left().map_or(0, |result| result.rows_affected());
+            println!("{} rows affected\n", rows_affected);
+        } else {
+            print_rows(&rows);
+            rows.clear();
+        }
+    }
+
+    Ok(())
+}
+
+async fn describe(conn: &mut AnyConnection, sql: &str) -> anyhow::Result<()> {
+    let describe = conn.describe(sql).await?;
+
+    let table: Vec<Vec<String>> = describe
+        .columns()
+        .iter()
+        .enumerate()
+        .map(|(i, column)| {
+            let nullable = match describe.nullable(i) {
+                Some(true) => "yes",
+                Some(false) => "no",
+                None => "unknown",
+            };
+
+            vec![
+                column.name().to_owned(),
+                column.type_info().name().to_owned(),
+                nullable.to_owned(),
+            ]
+        })
+        .collect();
+
+    print_table(&["column", "type", "nullable"], &table);
+    println!();
+
+    // not every database can tell us the types of the parameters
+    let parameters = match describe.parameters() {
+        Some(parameters) => match (parameters.left(), parameters.right()) {
+            (Some(types), _) => types
+                .iter()
+                .map(|ty| ty.name())
+                .collect::<Vec<_>>()
+                .join(", "),
+            (_, count) => format!("{} (types unknown)", count.unwrap_or(0)),
+        },
+        None => "unknown".into(),
+    };
+
+    println!("parameters: {}\n", parameters);
+
+    Ok(())
+}
+
+fn print_rows(rows: &[AnyRow]) {
+    let columns = rows[0].columns();
+    let header: Vec<&str> = columns.iter().map(|column| column.name()).collect();
+
+    let table: Vec<Vec<String>> = rows
+        .iter()
+        .map(|row| (0..columns.len()).map(|i| format_value(row, i)).collect())
+        .collect();
+
+    print_table(&header, &table);
+
+    match rows.len() {
+        1 => println!("(1 row)\n"),
+        n => println!("({} rows)\n", n),
+    }
+}
+
+fn format_value(row: &AnyRow, index: usize) -> String {
+    match row.try_get_raw(index) {
+        Ok(value) if value.is_null() => return "NULL".into(),
+        Ok(_) => {}
+        Err(e) => return format!("<{}>", e),
+    }
+
+    if let Ok(text) = row.try_get_unchecked::<String, _>(index) {
+        return text;
+    }
+
+    // blobs that are not valid UTF-8
+    if let Ok(bytes) = row.try_get_unchecked::<Vec<u8>, _>(index) {
+        return bytes.iter().fold(String::from("\\x"), |mut hex, byte| {
+            hex.push_str(&format!("{:02x}", byte));
+            hex
+        });
+    }
+
+    format!("<{}>", row.columns()[index].type_info().name())
+}
+
+fn print_table<S: AsRef<str>>(header: &[S], rows: &[Vec<String>]) {
+    let mut widths: Vec<usize> = header.iter().map(|h| h.as_ref().chars().count()).collect();
+
+    for row in rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = cmp::max(*width, cell.chars().count());
+        }
+    }
+
+    let format_row = |cells: Vec<&str>| {
+        cells
+            .iter()
+            .zip(&widths)
+            .map(|(cell, width)| format!(" {:<width$} ", cell, width = width))
+            .collect::<Vec<_>>()
+            .join("|")
+            .trim_end()
+            .to_owned()
+    };
+
+    println!(
+        "{}",
+        style(format_row(header.iter().map(AsRef::as_ref).collect())).bold()
+    );
+
+    println!(
+        "{}",
+        widths
+            .iter()
+            .map(|width| "-".repeat(width + 2))
+            .collect::<Vec<_>>()
+            .join("+")
+    );
+
+    for row in rows {
+        println!("{}", format_row(row.iter().map(|cell| &**cell).collect()));
+    }
+}
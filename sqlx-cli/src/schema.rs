@@ -0,0 +1,704 @@
+use crate::migrate;
+use crate::opt::ConnectOpts;
+use anyhow::bail;
+use console::style;
+use sqlx::migrate::{
+    Composite, Constraint, ConstraintKind, Enum, Index, Migrate, Schema, SchemaDialect, Table,
+    TableColumn, View,
+};
+use std::fmt::{self, Display, Formatter};
+
+/// A single difference between two schemas, as a step from one towards the other.
+#[derive(Debug)]
+enum Change<'a> {
+    AddEnum(&'a Enum),
+    DropEnum(&'a Enum),
+    AlterEnum(&'a Enum, &'a Enum),
+    AddComposite(&'a Composite),
+    DropComposite(&'a Composite),
+    AlterComposite(&'a Composite, &'a Composite),
+    AddTable(&'a Table),
+    DropTable(&'a Table),
+    AddColumn(&'a Table, &'a TableColumn),
+    DropColumn(&'a Table, &'a TableColumn),
+    AlterColumn(&'a Table, &'a TableColumn, &'a TableColumn),
+    AddConstraint(&'a Table, &'a Constraint),
+    DropConstraint(&'a Table, &'a Constraint),
+    AlterConstraint(&'a Table, &'a Constraint, &'a Constraint),
+    AddIndex(&'a Table, &'a Index),
+    DropIndex(&'a Table, &'a Index),
+    AlterIndex(&'a Table, &'a Index, &'a Index),
+    AddView(&'a View),
+    DropView(&'a View),
+    AlterView(&'a View, &'a View),
+}
+
+pub async fn diff(
+    from_opts: &ConnectOpts,
+    to_opts: &ConnectOpts,
+    migration: Option<&str>,
+    migration_source: &str,
+    table_name: &str,
+) -> anyhow::Result<()> {
+    let from = crate::connect(from_opts).await?.schema(table_name).await?;
+    let to = crate::connect(to_opts).await?.schema(table_name).await?;
+
+    if from.dialect != to.dialect {
+        bail!(
+            "cannot compare a {:?} schema with a {:?} schema",
+            from.dialect,
+            to.dialect
+        );
+    }
+
+    let changes = changes(&from, &to);
+
+    if changes.is_empty() {
+        println!("The schemas are identical");
+        return Ok(());
+    }
+
+    for change in &changes {
+        let marker = match change.marker() {
+            '+' => style('+').green(),
+            '-' => style('-').red(),
+            c => style(c).yellow(),
+        };
+
+        println!("{} {}", marker, change);
+    }
+
+    match migration {
+        Some(description) => {
+            let up = migration_sql(&changes, to.dialect);
+            let down = migration_sql(&self::changes(&to, &from), to.dialect);
+
+            migrate::add_with_sql(migration_source, description, &up, &down).await
+        }
+
+        None => bail!("found {} difference(s)", changes.len()),
+    }
+}
+
+/// Compare two schemas by the names of their objects.
+fn changes<'a>(from: &'a Schema, to: &'a Schema) -> Vec<Change<'a>> {
+    let mut changes = Vec::new();
+
+    for diff in diff_by_name(&from.enums, &to.enums, |e| &e.name) {
+        changes.push(match diff {
+            Diff::Added(e) => Change::AddEnum(e),
+            Diff::Removed(e) => Change::DropEnum(e),
+            Diff::Changed(a, b) => Change::AlterEnum(a, b),
+        });
+    }
+
+    for diff in diff_by_name(&from.composites, &to.composites, |c| &c.name) {
+        changes.push(match diff {
+            Diff::Added(c) => Change::AddComposite(c),
+            Diff::Removed(c) => Change::DropComposite(c),
+            Diff::Changed(a, b) => Change::AlterComposite(a, b),
+        });
+    }
+
+    for diff in diff_by_name(&from.tables, &to.tables, |t| &t.name) {
+        match diff {
+            Diff::Added(t) => changes.push(Change::AddTable(t)),
+            Diff::Removed(t) => changes.push(Change::DropTable(t)),
+            Diff::Changed(a, b) => table_changes(a, b, &mut changes),
+        }
+    }
+
+    for diff in diff_by_name(&from.views, &to.views, |v| &v.name) {
+        changes.push(match diff {
+            Diff::Added(v) => Change::AddView(v),
+            Diff::Removed(v) => Change::DropView(v),
+            Diff::Changed(a, b) => Change::AlterView(a, b),
+        });
+    }
+
+    changes
+}
+
+fn table_changes<'a>(from: &'a Table, to: &'a Table, changes: &mut Vec<Change<'a>>) {
+    for diff in diff_by_name(&from.columns, &to.columns, |c| &c.name) {
+        changes.push(match diff {
+            Diff::Added(c) => Change::AddColumn(to, c),
+            Diff::Removed(c) => Change::DropColumn(from, c),
+            Diff::Changed(a, b) => Change::AlterColumn(to, a, b),
+        });
+    }
+
+    for diff in diff_by_name(&from.constraints, &to.constraints, |c| &c.name) {
+        changes.push(match diff {
+            Diff::Added(c) => Change::AddConstraint(to, c),
+            Diff::Removed(c) => Change::DropConstraint(from, c),
+            Diff::Changed(a, b) => Change::AlterConstraint(to, a, b),
+        });
+    }
+
+    for diff in diff_by_name(&from.indexes, &to.indexes, |i| &i.name) {
+        changes.push(match diff {
+            Diff::Added(i) => Change::AddIndex(to, i),
+            Diff::Removed(i) => Change::DropIndex(from, i),
+            Diff::Changed(a, b) => Change::AlterIndex(to, a, b),
+        });
+    }
+}
+
+enum Diff<'a, T> {
+    Added(&'a T),
+    Removed(&'a T),
+    Changed(&'a T, &'a T),
+}
+
+/// Pair up the elements of two lists by name, keeping those that differ.
+fn diff_by_name<'a, T: PartialEq>(
+    from: &'a [T],
+    to: &'a [T],
+    name: impl Fn(&T) -> &String,
+) -> Vec<Diff<'a, T>> {
+    let mut diffs = Vec::new();
+
+    for a in from {
+        match to.iter().find(|b| name(b) == name(a)) {
+            Some(b) if a != b => diffs.push(Diff::Changed(a, b)),
+            Some(_) => {}
+            None => diffs.push(Diff::Removed(a)),
+        }
+    }
+
+    for b in to {
+        if !from.iter().any(|a| name(a) == name(b)) {
+            diffs.push(Diff::Added(b));
+        }
+    }
+
+    diffs
+}
+
+impl Change<'_> {
+    fn marker(&self) -> char {
+        match self {
+            Change::AddEnum(_)
+            | Change::AddComposite(_)
+            | Change::AddTable(_)
+            | Change::AddColumn(..)
+            | Change::AddConstraint(..)
+            | Change::AddIndex(..)
+            | Change::AddView(_) => '+',
+
+            Change::DropEnum(_)
+            | Change::DropComposite(_)
+            | Change::DropTable(_)
+            | Change::DropColumn(..)
+            | Change::DropConstraint(..)
+            | Change::DropIndex(..)
+            | Change::DropView(_) => '-',
+
+            _ => '~',
+        }
+    }
+}
+
+impl Display for Change<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Change::AddEnum(e) | Change::DropEnum(e) => write!(f, "enum {}", e.name),
+            Change::AlterEnum(a, b) => write!(
+                f,
+                "enum {}: values ({}) -> ({})",
+                a.name,
+                a.variants.join(", "),
+                b.variants.join(", ")
+            ),
+
+            Change::AddComposite(c) | Change::DropComposite(c) => write!(f, "type {}", c.name),
+            Change::AlterComposite(a, b) => {
+                write!(f, "type {}: attributes changed", a.name)?;
+
+                for diff in diff_by_name(&a.attributes, &b.attributes, |c| &c.name) {
+                    match diff {
+                        Diff::Added(c) => write!(f, "\n    + {}: {}", c.name, describe_column(c))?,
+                        Diff::Removed(c) => write!(f, "\n    - {}", c.name)?,
+                        Diff::Changed(a, b) => {
+                            write!(f, "\n    ~ {}: {}", a.name, column_difference(a, b))?
+                        }
+                    }
+                }
+
+                Ok(())
+            }
+
+            Change::AddTable(t) | Change::DropTable(t) => write!(f, "table {}", t.name),
+
+            Change::AddColumn(t, c) | Change::DropColumn(t, c) => {
+                write!(f, "column {}.{}: {}", t.name, c.name, describe_column(c))
+            }
+            Change::AlterColumn(t, a, b) => write!(
+                f,
+                "column {}.{}: {}",
+                t.name,
+                a.name,
+                column_difference(a, b)
+            ),
+
+            Change::AddConstraint(t, c) | Change::DropConstraint(t, c) => {
+                write!(f, "constraint {}.{}: {}", t.name, c.name, c.definition)
+            }
+            Change::AlterConstraint(t, a, b) => write!(
+                f,
+                "constraint {}.{}: {} -> {}",
+                t.name, a.name, a.definition, b.definition
+            ),
+
+            Change::AddIndex(t, i) | Change::DropIndex(t, i) => {
+                write!(f, "index {}.{}: {}", t.name, i.name, i.definition)
+            }
+            Change::AlterIndex(t, a, b) => write!(
+                f,
+                "index {}.{}: {} -> {}",
+                t.name, a.name, a.definition, b.definition
+            ),
+
+            Change::AddView(v) | Change::DropView(v) => write!(f, "view {}", v.name),
+            Change::AlterView(v, _) => write!(f, "view {}: definition changed", v.name),
+        }
+    }
+}
+
+fn describe_column(column: &TableColumn) -> String {
+    let mut description = if column.data_type.is_empty() {
+        "(no type)".to_owned()
+    } else {
+        column.data_type.clone()
+    };
+
+    if let Some(extra) = &column.extra {
+        description.push(' ');
+        description.push_str(extra);
+    }
+
+    description.push_str(if column.nullable {
+        " NULL"
+    } else {
+        " NOT NULL"
+    });
+
+    if let Some(default) = &column.default {
+        description.push_str(" DEFAULT ");
+        description.push_str(default);
+    }
+
+    description
+}
+
+fn column_difference(from: &TableColumn, to: &TableColumn) -> String {
+    let mut differences = Vec::new();
+
+    if from.data_type != to.data_type || from.extra != to.extra {
+        let type_of = |c: &TableColumn| match &c.extra {
+            Some(extra) => format!("{} {}", c.data_type, extra),
+            None => c.data_type.clone(),
+        };
+
+        differences.push(format!("type {} -> {}", type_of(from), type_of(to)));
+    }
+
+    if from.nullable != to.nullable {
+        let nullability = |nullable| if nullable { "NULL" } else { "NOT NULL" };
+
+        differences.push(format!(
+            "{} -> {}",
+            nullability(from.nullable),
+            nullability(to.nullable)
+        ));
+    }
+
+    if from.default != to.default {
+        let default = |d: &Option<String>| d.clone().unwrap_or_else(|| "no default".into());
+
+        differences.push(format!(
+            "default {} -> {}",
+            default(&from.default),
+            default(&to.default)
+        ));
+    }
+
+    differences.join(", ")
+}
+
+/// Render the changes as a migration script.
+///
+/// Statements are ordered so that objects are dropped before they are replaced and created
+/// before anything that may depend on them. Changes that cannot be expressed in the dialect
+/// are left as comments to be resolved by hand.
+fn migration_sql(changes: &[Change<'_>], dialect: SchemaDialect) -> String {
+    let mut statements: Vec<(u8, String)> = Vec::new();
+
+    for change in changes {
+        change_sql(change, dialect, &mut statements);
+    }
+
+    // a stable sort keeps the order of statements within a step
+    statements.sort_by_key(|(step, _)| *step);
+
+    let mut sql = String::new();
+
+    for (_, statement) in statements {
+        sql.push_str(&statement);
+        sql.push('\n');
+    }
+
+    sql
+}
+
+fn change_sql(change: &Change<'_>, dialect: SchemaDialect, out: &mut Vec<(u8, String)>) {
+    let q = |ident: &str| dialect.quote_ident(ident);
+
+    // the order in which each kind of statement is run
+    const DROP_VIEW: u8 = 0;
+    const DROP_CONSTRAINT: u8 = 1;
+    const DROP_TABLE: u8 = 2;
+    const ADD_TYPE: u8 = 3;
+    const ADD_TABLE: u8 = 4;
+    const ALTER_COLUMN: u8 = 5;
+    const ADD_CONSTRAINT: u8 = 6;
+    const DROP_TYPE: u8 = 7;
+    const ADD_VIEW: u8 = 8;
+
+    match change {
+        Change::AddEnum(e) => out.push((ADD_TYPE, format!("{};", e.to_sql(dialect)))),
+        Change::DropEnum(e) => out.push((DROP_TYPE, format!("DROP TYPE {};", q(&e.name)))),
+        Change::AlterEnum(a, b) => {
+            for (i, variant) in b.variants.iter().enumerate() {
+                if a.variants.contains(variant) {
+                    continue;
+                }
+
+                let position = match (i.checked_sub(1), b.variants.get(i + 1)) {
+                    (Some(prev), _) => format!(" AFTER {}", quote_literal(&b.variants[prev])),
+                    (None, Some(next)) if a.variants.contains(next) => {
+                        format!(" BEFORE {}", quote_literal(next))
+                    }
+                    _ => String::new(),
+                };
+
+                out.push((
+                    ADD_TYPE,
+                    format!(
+                        "ALTER TYPE {} ADD VALUE {}{};",
+                        q(&a.name),
+                        quote_literal(variant),
+                        position
+                    ),
+                ));
+            }
+
+            for variant in &a.variants {
+                if !b.variants.contains(variant) {
+                    out.push((
+                        ADD_TYPE,
+                        format!(
+                            "-- TODO: values cannot be removed from an enum; remove {} from {} by hand",
+                            quote_literal(variant),
+                            q(&a.name)
+                        ),
+                    ));
+                }
+            }
+        }
+
+        Change::AddComposite(c) => out.push((ADD_TYPE, format!("{};", c.to_sql(dialect)))),
+        Change::DropComposite(c) => out.push((DROP_TYPE, format!("DROP TYPE {};", q(&c.name)))),
+        Change::AlterComposite(a, b) => {
+            for diff in diff_by_name(&a.attributes, &b.attributes, |c| &c.name) {
+                let statement = match diff {
+                    Diff::Added(to) => format!(
+                        "ALTER TYPE {} ADD ATTRIBUTE {};",
+                        q(&a.name),
+                        to.to_sql(dialect)
+                    ),
+                    Diff::Removed(from) => format!(
+                        "ALTER TYPE {} DROP ATTRIBUTE {};",
+                        q(&a.name),
+                        q(&from.name)
+                    ),
+                    Diff::Changed(_, to) => format!(
+                        "ALTER TYPE {} ALTER ATTRIBUTE {} TYPE {};",
+                        q(&a.name),
+                        q(&to.name),
+                        to.data_type
+                    ),
+                };
+
+                out.push((ADD_TYPE, statement));
+            }
+        }
+
+        Change::AddTable(t) => {
+            out.push((ADD_TABLE, format!("{};", t.to_sql(dialect))));
+
+            if dialect.defers_foreign_keys() {
+                for c in &t.constraints {
+                    if c.kind == ConstraintKind::ForeignKey {
+                        change_sql(&Change::AddConstraint(t, c), dialect, out);
+                    }
+                }
+            }
+
+            for index in &t.indexes {
+                out.push((ADD_CONSTRAINT, format!("{};", index.definition)));
+            }
+        }
+        Change::DropTable(t) => out.push((DROP_TABLE, format!("DROP TABLE {};", q(&t.name)))),
+
+        Change::AddColumn(t, c) => out.push((
+            ALTER_COLUMN,
+            format!(
+                "ALTER TABLE {} ADD COLUMN {};",
+                q(&t.name),
+                c.to_sql(dialect)
+            ),
+        )),
+        Change::DropColumn(t, c) => out.push((
+            ALTER_COLUMN,
+            format!("ALTER TABLE {} DROP COLUMN {};", q(&t.name), q(&c.name)),
+        )),
+        Change::AlterColumn(t, a, b) => match dialect {
+            SchemaDialect::Postgres => {
+                let alter = format!("ALTER TABLE {} ALTER COLUMN {}", q(&t.name), q(&b.name));
+
+                // serial and identity columns own a sequence which `ALTER COLUMN` cannot create
+                if a.extra != b.extra
+                    || a.data_type.ends_with("serial") != b.data_type.ends_with("serial")
+                {
+                    out.push((
+                        ALTER_COLUMN,
+                        format!(
+                            "-- TODO: change {}.{} from `{}` to `{}` by hand",
+                            q(&t.name),
+                            q(&b.name),
+                            a.to_sql(dialect),
+                            b.to_sql(dialect)
+                        ),
+                    ));
+                } else if a.data_type != b.data_type {
+                    out.push((ALTER_COLUMN, format!("{} TYPE {};", alter, b.data_type)));
+                }
+
+                if a.nullable != b.nullable {
+                    let action = if b.nullable { "DROP" } else { "SET" };
+                    out.push((ALTER_COLUMN, format!("{} {} NOT NULL;", alter, action)));
+                }
+
+                if a.default != b.default && a.extra == b.extra {
+                    let statement = match &b.default {
+                        Some(default) => format!("{} SET DEFAULT {};", alter, default),
+                        None => format!("{} DROP DEFAULT;", alter),
+                    };
+
+                    out.push((ALTER_COLUMN, statement));
+                }
+            }
+
+            SchemaDialect::MySql => out.push((
+                ALTER_COLUMN,
+                format!(
+                    "ALTER TABLE {} MODIFY COLUMN {};",
+                    q(&t.name),
+                    b.to_sql(dialect)
+                ),
+            )),
+
+            SchemaDialect::Sqlite => out.push((
+                ALTER_COLUMN,
+                format!(
+                    "-- TODO: SQLite cannot alter columns; rebuild {} to change {} to `{}`",
+                    q(&t.name),
+                    q(&b.name),
+                    b.to_sql(dialect)
+                ),
+            )),
+        },
+
+        Change::AddConstraint(t, c) => out.push((
+            ADD_CONSTRAINT,
+            match dialect {
+                SchemaDialect::Sqlite => format!(
+                    "-- TODO: SQLite cannot add constraints; rebuild {} to add `{}`",
+                    q(&t.name),
+                    c.definition
+                ),
+                _ => format!("ALTER TABLE {} ADD {};", q(&t.name), c.to_sql(dialect)),
+            },
+        )),
+        Change::DropConstraint(t, c) => {
+            out.push((DROP_CONSTRAINT, drop_constraint_sql(t, c, dialect)))
+        }
+        Change::AlterConstraint(t, a, b) => {
+            change_sql(&Change::DropConstraint(t, a), dialect, out);
+            change_sql(&Change::AddConstraint(t, b), dialect, out);
+        }
+
+        Change::AddIndex(_, i) => out.push((ADD_CONSTRAINT, format!("{};", i.definition))),
+        Change::DropIndex(t, i) => out.push((
+            DROP_CONSTRAINT,
+            match dialect {
+                SchemaDialect::MySql => format!("DROP INDEX {} ON {};", q(&i.name), q(&t.name)),
+                _ => format!("DROP INDEX {};", q(&i.name)),
+            },
+        )),
+        Change::AlterIndex(t, a, b) => {
+            change_sql(&Change::DropIndex(t, a), dialect, out);
+            change_sql(&Change::AddIndex(t, b), dialect, out);
+        }
+
+        Change::AddView(v) => out.push((ADD_VIEW, format!("{};", v.definition))),
+        Change::DropView(v) => {
+            let materialized = v.definition.starts_with("CREATE MATERIALIZED VIEW");
+
+            out.push((
+                DROP_VIEW,
+                format!(
+                    "DROP {}VIEW {};",
+                    if materialized { "MATERIALIZED " } else { "" },
+                    q(&v.name)
+                ),
+            ));
+        }
+        Change::AlterView(a, b) => {
+            change_sql(&Change::DropView(a), dialect, out);
+            change_sql(&Change::AddView(b), dialect, out);
+        }
+    }
+}
+
+fn drop_constraint_sql(table: &Table, constraint: &Constraint, dialect: SchemaDialect) -> String {
+    let table_name = dialect.quote_ident(&table.name);
+    let name = dialect.quote_ident(&constraint.name);
+
+    match (dialect, constraint.kind) {
+        (SchemaDialect::Sqlite, _) => format!(
+            "-- TODO: SQLite cannot drop constraints; rebuild {} to drop `{}`",
+            table_name, constraint.definition
+        ),
+
+        (SchemaDialect::MySql, ConstraintKind::PrimaryKey) => {
+            format!("ALTER TABLE {} DROP PRIMARY KEY;", table_name)
+        }
+        (SchemaDialect::MySql, ConstraintKind::ForeignKey) => {
+            format!("ALTER TABLE {} DROP FOREIGN KEY {};", table_name, name)
+        }
+        (SchemaDialect::MySql, ConstraintKind::Unique) => {
+            format!("ALTER TABLE {} DROP INDEX {};", table_name, name)
+        }
+        (SchemaDialect::MySql, _) => format!("ALTER TABLE {} DROP CHECK {};", table_name, name),
+
+        (SchemaDialect::Postgres, _) => {
+            format!("ALTER TABLE {} DROP CONSTRAINT {};", table_name, name)
+        }
+    }
+}
+
+fn quote_literal(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "''"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn column(name: &str, data_type: &str, nullable: bool) -> TableColumn {
+        TableColumn {
+            name: name.into(),
+            data_type: data_type.into(),
+            nullable,
+            default: None,
+            extra: None,
+        }
+    }
+
+    #[test]
+    fn migration_sql_works() {
+        let mut from = Schema::new(SchemaDialect::Postgres);
+
+        from.enums.push(Enum {
+            name: "mood".into(),
+            variants: vec!["sad".into(), "happy".into()],
+        });
+
+        from.tables.push(Table {
+            name: "users".into(),
+            columns: vec![
+                column("id", "bigint", false),
+                column("email", "character varying(255)", true),
+                column("legacy", "text", true),
+            ],
+            constraints: vec![Constraint {
+                name: "users_pkey".into(),
+                kind: ConstraintKind::PrimaryKey,
+                definition: "PRIMARY KEY (id)".into(),
+            }],
+            indexes: Vec::new(),
+        });
+
+        let mut to = from.clone();
+
+        to.enums[0].variants.insert(1, "ok".into());
+
+        let users = &mut to.tables[0];
+        users.columns[1] = column("email", "text", false);
+        users.columns.remove(2);
+        users.indexes.push(Index {
+            name: "users_email".into(),
+            definition: "CREATE INDEX users_email ON public.users USING btree (email)".into(),
+        });
+
+        to.tables.push(Table {
+            name: "posts".into(),
+            columns: vec![
+                column("id", "bigint", false),
+                column("user_id", "bigint", false),
+            ],
+            constraints: vec![Constraint {
+                name: "posts_user_id_fkey".into(),
+                kind: ConstraintKind::ForeignKey,
+                definition: "FOREIGN KEY (user_id) REFERENCES users(id)".into(),
+            }],
+            indexes: Vec::new(),
+        });
+
+        let changes = changes(&from, &to);
+
+        let report: Vec<String> = changes
+            .iter()
+            .map(|change| format!("{} {}", change.marker(), change))
+            .collect();
+
+        assert_eq!(
+            report,
+            [
+                "~ enum mood: values (sad, happy) -> (sad, ok, happy)",
+                "~ column users.email: type character varying(255) -> text, NULL -> NOT NULL",
+                "- column users.legacy: text NULL",
+                "+ index users.users_email: CREATE INDEX users_email ON public.users USING btree (email)",
+                "+ table posts",
+            ]
+        );
+
+        assert_eq!(
+            migration_sql(&changes, SchemaDialect::Postgres),
+            r#"ALTER TYPE "mood" ADD VALUE 'ok' AFTER 'sad';
+CREATE TABLE "posts" (
+    "id" bigint NOT NULL,
+    "user_id" bigint NOT NULL
+);
+ALTER TABLE "users" ALTER COLUMN "email" TYPE text;
+ALTER TABLE "users" ALTER COLUMN "email" SET NOT NULL;
+ALTER TABLE "users" DROP COLUMN "legacy";
+CREATE INDEX users_email ON public.users USING btree (email);
+ALTER TABLE "posts" ADD CONSTRAINT "posts_user_id_fkey" FOREIGN KEY (user_id) REFERENCES users(id);
+"#
+        );
+    }
+}
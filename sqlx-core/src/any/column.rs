@@ -74,6 +74,22 @@ impl Column for AnyColumn {
     fn type_info(&self) -> &AnyTypeInfo {
         &self.type_info
     }
+
+    fn origin_table(&self) -> Option<&str> {
+        match &self.kind {
+            #[cfg(feature = "postgres")]
+            AnyColumnKind::Postgres(row) => row.origin_table(),
+
+            #[cfg(feature = "mysql")]
+            AnyColumnKind::MySql(row) => row.origin_table(),
+
+            #[cfg(feature = "sqlite")]
+            AnyColumnKind::Sqlite(row) => row.origin_table(),
+
+            #[cfg(feature = "mssql")]
+            AnyColumnKind::Mssql(row) => row.origin_table(),
+        }
+    }
 }
 
 // FIXME: Find a nice way to auto-generate the below or petition Rust to add support for #[cfg]
@@ -2,7 +2,10 @@ use crate::any::connection::AnyConnectionKind;
 use crate::any::kind::AnyKind;
 use crate::any::{Any, AnyConnection};
 use crate::error::Error;
-use crate::migrate::{AppliedMigration, Migrate, MigrateDatabase, MigrateError, Migration};
+use crate::migrate::{
+    AppliedMigration, AppliedSeed, Migrate, MigrateDatabase, MigrateError, Migration, Schema, Seed,
+    DEFAULT_TABLE_NAME,
+};
 use futures_core::future::BoxFuture;
 use std::str::FromStr;
 use std::time::Duration;
@@ -65,72 +68,112 @@ impl MigrateDatabase for Any {
 
 impl Migrate for AnyConnection {
     fn ensure_migrations_table(&mut self) -> BoxFuture<'_, Result<(), MigrateError>> {
+        self.ensure_migrations_table_in(DEFAULT_TABLE_NAME)
+    }
+
+    fn ensure_migrations_table_in<'e>(
+        &'e mut self,
+        table_name: &'e str,
+    ) -> BoxFuture<'e, Result<(), MigrateError>> {
         match &mut self.0 {
             #[cfg(feature = "postgres")]
-            AnyConnectionKind::Postgres(conn) => conn.ensure_migrations_table(),
+            AnyConnectionKind::Postgres(conn) => conn.ensure_migrations_table_in(table_name),
 
             #[cfg(feature = "sqlite")]
-            AnyConnectionKind::Sqlite(conn) => conn.ensure_migrations_table(),
+            AnyConnectionKind::Sqlite(conn) => conn.ensure_migrations_table_in(table_name),
 
             #[cfg(feature = "mysql")]
-            AnyConnectionKind::MySql(conn) => conn.ensure_migrations_table(),
+            AnyConnectionKind::MySql(conn) => conn.ensure_migrations_table_in(table_name),
 
             #[cfg(feature = "mssql")]
-            AnyConnectionKind::Mssql(_conn) => unimplemented!(),
+            AnyConnectionKind::Mssql(_conn) => {
+                let _ = table_name;
+                unimplemented!()
+            }
         }
     }
 
-    #[allow(deprecated)]
     fn version(&mut self) -> BoxFuture<'_, Result<Option<(i64, bool)>, MigrateError>> {
+        #[allow(deprecated)]
+        self.version_in(DEFAULT_TABLE_NAME)
+    }
+
+    #[allow(deprecated)]
+    fn version_in<'e>(
+        &'e mut self,
+        table_name: &'e str,
+    ) -> BoxFuture<'e, Result<Option<(i64, bool)>, MigrateError>> {
         match &mut self.0 {
             #[cfg(feature = "postgres")]
-            AnyConnectionKind::Postgres(conn) => conn.version(),
+            AnyConnectionKind::Postgres(conn) => conn.version_in(table_name),
 
             #[cfg(feature = "sqlite")]
-            AnyConnectionKind::Sqlite(conn) => conn.version(),
+            AnyConnectionKind::Sqlite(conn) => conn.version_in(table_name),
 
             #[cfg(feature = "mysql")]
-            AnyConnectionKind::MySql(conn) => conn.version(),
+            AnyConnectionKind::MySql(conn) => conn.version_in(table_name),
 
             #[cfg(feature = "mssql")]
-            AnyConnectionKind::Mssql(_conn) => unimplemented!(),
+            AnyConnectionKind::Mssql(_conn) => {
+                let _ = table_name;
+                unimplemented!()
+            }
         }
     }
 
     fn dirty_version(&mut self) -> BoxFuture<'_, Result<Option<i64>, MigrateError>> {
+        self.dirty_version_in(DEFAULT_TABLE_NAME)
+    }
+
+    fn dirty_version_in<'e>(
+        &'e mut self,
+        table_name: &'e str,
+    ) -> BoxFuture<'e, Result<Option<i64>, MigrateError>> {
         match &mut self.0 {
             #[cfg(feature = "postgres")]
-            AnyConnectionKind::Postgres(conn) => conn.dirty_version(),
+            AnyConnectionKind::Postgres(conn) => conn.dirty_version_in(table_name),
 
             #[cfg(feature = "sqlite")]
-            AnyConnectionKind::Sqlite(conn) => conn.dirty_version(),
+            AnyConnectionKind::Sqlite(conn) => conn.dirty_version_in(table_name),
 
             #[cfg(feature = "mysql")]
-            AnyConnectionKind::MySql(conn) => conn.dirty_version(),
+            AnyConnectionKind::MySql(conn) => conn.dirty_version_in(table_name),
 
             #[cfg(feature = "mssql")]
-            AnyConnectionKind::Mssql(_conn) => unimplemented!(),
+            AnyConnectionKind::Mssql(_conn) => {
+                let _ = table_name;
+                unimplemented!()
+            }
         }
     }
 
-    #[allow(deprecated)]
     fn validate<'e: 'm, 'm>(
         &'e mut self,
         migration: &'m Migration,
+    ) -> BoxFuture<'m, Result<(), MigrateError>> {
+        #[allow(deprecated)]
+        self.validate_in(DEFAULT_TABLE_NAME, migration)
+    }
+
+    #[allow(deprecated)]
+    fn validate_in<'e: 'm, 'm>(
+        &'e mut self,
+        table_name: &'m str,
+        migration: &'m Migration,
     ) -> BoxFuture<'m, Result<(), MigrateError>> {
         match &mut self.0 {
             #[cfg(feature = "postgres")]
-            AnyConnectionKind::Postgres(conn) => conn.validate(migration),
+            AnyConnectionKind::Postgres(conn) => conn.validate_in(table_name, migration),
 
             #[cfg(feature = "sqlite")]
-            AnyConnectionKind::Sqlite(conn) => conn.validate(migration),
+            AnyConnectionKind::Sqlite(conn) => conn.validate_in(table_name, migration),
 
             #[cfg(feature = "mysql")]
-            AnyConnectionKind::MySql(conn) => conn.validate(migration),
+            AnyConnectionKind::MySql(conn) => conn.validate_in(table_name, migration),
 
             #[cfg(feature = "mssql")]
             AnyConnectionKind::Mssql(_conn) => {
-                let _ = migration;
+                let _ = (table_name, migration);
                 unimplemented!()
             }
         }
@@ -139,18 +182,28 @@ impl Migrate for AnyConnection {
     fn list_applied_migrations(
         &mut self,
     ) -> BoxFuture<'_, Result<Vec<AppliedMigration>, MigrateError>> {
+        self.list_applied_migrations_in(DEFAULT_TABLE_NAME)
+    }
+
+    fn list_applied_migrations_in<'e>(
+        &'e mut self,
+        table_name: &'e str,
+    ) -> BoxFuture<'e, Result<Vec<AppliedMigration>, MigrateError>> {
         match &mut self.0 {
             #[cfg(feature = "postgres")]
-            AnyConnectionKind::Postgres(conn) => conn.list_applied_migrations(),
+            AnyConnectionKind::Postgres(conn) => conn.list_applied_migrations_in(table_name),
 
             #[cfg(feature = "sqlite")]
-            AnyConnectionKind::Sqlite(conn) => conn.list_applied_migrations(),
+            AnyConnectionKind::Sqlite(conn) => conn.list_applied_migrations_in(table_name),
 
             #[cfg(feature = "mysql")]
-            AnyConnectionKind::MySql(conn) => conn.list_applied_migrations(),
+            AnyConnectionKind::MySql(conn) => conn.list_applied_migrations_in(table_name),
 
             #[cfg(feature = "mssql")]
-            AnyConnectionKind::Mssql(_conn) => unimplemented!(),
+            AnyConnectionKind::Mssql(_conn) => {
+                let _ = table_name;
+                unimplemented!()
+            }
         }
     }
 
@@ -189,20 +242,28 @@ impl Migrate for AnyConnection {
     fn apply<'e: 'm, 'm>(
         &'e mut self,
         migration: &'m Migration,
+    ) -> BoxFuture<'m, Result<Duration, MigrateError>> {
+        self.apply_in(DEFAULT_TABLE_NAME, migration)
+    }
+
+    fn apply_in<'e: 'm, 'm>(
+        &'e mut self,
+        table_name: &'m str,
+        migration: &'m Migration,
     ) -> BoxFuture<'m, Result<Duration, MigrateError>> {
         match &mut self.0 {
             #[cfg(feature = "postgres")]
-            AnyConnectionKind::Postgres(conn) => conn.apply(migration),
+            AnyConnectionKind::Postgres(conn) => conn.apply_in(table_name, migration),
 
             #[cfg(feature = "sqlite")]
-            AnyConnectionKind::Sqlite(conn) => conn.apply(migration),
+            AnyConnectionKind::Sqlite(conn) => conn.apply_in(table_name, migration),
 
             #[cfg(feature = "mysql")]
-            AnyConnectionKind::MySql(conn) => conn.apply(migration),
+            AnyConnectionKind::MySql(conn) => conn.apply_in(table_name, migration),
 
             #[cfg(feature = "mssql")]
             AnyConnectionKind::Mssql(_conn) => {
-                let _ = migration;
+                let _ = (table_name, migration);
                 unimplemented!()
             }
         }
@@ -212,19 +273,126 @@ impl Migrate for AnyConnection {
         &'e mut self,
         migration: &'m Migration,
     ) -> BoxFuture<'m, Result<Duration, MigrateError>> {
+        self.revert_in(DEFAULT_TABLE_NAME, migration)
+    }
+
+    fn revert_in<'e: 'm, 'm>(
+        &'e mut self,
+        table_name: &'m str,
+        migration: &'m Migration,
+    ) -> BoxFuture<'m, Result<Duration, MigrateError>> {
+        match &mut self.0 {
+            #[cfg(feature = "postgres")]
+            AnyConnectionKind::Postgres(conn) => conn.revert_in(table_name, migration),
+
+            #[cfg(feature = "sqlite")]
+            AnyConnectionKind::Sqlite(conn) => conn.revert_in(table_name, migration),
+
+            #[cfg(feature = "mysql")]
+            AnyConnectionKind::MySql(conn) => conn.revert_in(table_name, migration),
+
+            #[cfg(feature = "mssql")]
+            AnyConnectionKind::Mssql(_conn) => {
+                let _ = (table_name, migration);
+                unimplemented!()
+            }
+        }
+    }
+
+    fn apply_baseline<'e: 'm, 'm>(
+        &'e mut self,
+        table_name: &'m str,
+        migration: &'m Migration,
+    ) -> BoxFuture<'m, Result<(), MigrateError>> {
+        match &mut self.0 {
+            #[cfg(feature = "postgres")]
+            AnyConnectionKind::Postgres(conn) => conn.apply_baseline(table_name, migration),
+
+            #[cfg(feature = "sqlite")]
+            AnyConnectionKind::Sqlite(conn) => conn.apply_baseline(table_name, migration),
+
+            #[cfg(feature = "mysql")]
+            AnyConnectionKind::MySql(conn) => conn.apply_baseline(table_name, migration),
+
+            #[cfg(feature = "mssql")]
+            AnyConnectionKind::Mssql(_conn) => {
+                let _ = (table_name, migration);
+                unimplemented!()
+            }
+        }
+    }
+
+    fn ensure_seeds_table(&mut self) -> BoxFuture<'_, Result<(), MigrateError>> {
+        match &mut self.0 {
+            #[cfg(feature = "postgres")]
+            AnyConnectionKind::Postgres(conn) => conn.ensure_seeds_table(),
+
+            #[cfg(feature = "sqlite")]
+            AnyConnectionKind::Sqlite(conn) => conn.ensure_seeds_table(),
+
+            #[cfg(feature = "mysql")]
+            AnyConnectionKind::MySql(conn) => conn.ensure_seeds_table(),
+
+            #[cfg(feature = "mssql")]
+            AnyConnectionKind::Mssql(_conn) => unimplemented!(),
+        }
+    }
+
+    fn list_applied_seeds(&mut self) -> BoxFuture<'_, Result<Vec<AppliedSeed>, MigrateError>> {
+        match &mut self.0 {
+            #[cfg(feature = "postgres")]
+            AnyConnectionKind::Postgres(conn) => conn.list_applied_seeds(),
+
+            #[cfg(feature = "sqlite")]
+            AnyConnectionKind::Sqlite(conn) => conn.list_applied_seeds(),
+
+            #[cfg(feature = "mysql")]
+            AnyConnectionKind::MySql(conn) => conn.list_applied_seeds(),
+
+            #[cfg(feature = "mssql")]
+            AnyConnectionKind::Mssql(_conn) => unimplemented!(),
+        }
+    }
+
+    fn apply_seed<'e: 'm, 'm>(
+        &'e mut self,
+        seed: &'m Seed,
+    ) -> BoxFuture<'m, Result<Duration, MigrateError>> {
+        match &mut self.0 {
+            #[cfg(feature = "postgres")]
+            AnyConnectionKind::Postgres(conn) => conn.apply_seed(seed),
+
+            #[cfg(feature = "sqlite")]
+            AnyConnectionKind::Sqlite(conn) => conn.apply_seed(seed),
+
+            #[cfg(feature = "mysql")]
+            AnyConnectionKind::MySql(conn) => conn.apply_seed(seed),
+
+            #[cfg(feature = "mssql")]
+            AnyConnectionKind::Mssql(_conn) => {
+                let _ = seed;
+                unimplemented!()
+            }
+        }
+    }
+
+    fn schema<'e>(
+        &'e mut self,
+        migrations_table: &'e str,
+    ) -> BoxFuture<'e, Result<Schema, MigrateError>> {
         match &mut self.0 {
             #[cfg(feature = "postgres")]
-            AnyConnectionKind::Postgres(conn) => conn.revert(migration),
+            AnyConnectionKind::Postgres(conn) => conn.schema(migrations_table),
 
             #[cfg(feature = "sqlite")]
-            AnyConnectionKind::Sqlite(conn) => conn.revert(migration),
+            AnyConnectionKind::Sqlite(conn) => conn.schema(migrations_table),
 
             #[cfg(feature = "mysql")]
-            AnyConnectionKind::MySql(conn) => conn.revert(migration),
+            AnyConnectionKind::MySql(conn) => conn.schema(migrations_table),
 
             #[cfg(feature = "mssql")]
             AnyConnectionKind::Mssql(_conn) => {
-                let _ = migration;
+                let _ = migrations_table;
                 unimplemented!()
             }
         }
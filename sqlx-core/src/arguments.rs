@@ -18,6 +18,34 @@ pub trait Arguments<'q>: Send + Sized + Default {
         T: 'q + Send + Encode<'q, Self::Database> + Type<Self::Database>;
 }
 
+/// A value that can be bound as the arguments of a query, as with
+/// [`query_with`](crate::query::query_with).
+///
+/// ## Derivable
+///
+/// This trait can be derived by SQLx for any struct, binding each field in order of declaration:
+///
+/// ```rust,ignore
+/// #[derive(sqlx::IntoArguments)]
+/// struct NewUser {
+///     name: String,
+///     email: String,
+///     #[sqlx(skip)]
+///     password: String,
+///     #[sqlx(json)]
+///     preferences: Preferences,
+/// }
+///
+/// sqlx::query_with("INSERT INTO users (name, email, preferences) VALUES ($1, $2, $3)", user)
+///     .execute(&pool)
+///     .await?;
+/// ```
+///
+/// A field marked with `#[sqlx(skip)]` is not bound, and a field marked with `#[sqlx(json)]`
+/// is bound as [`Json`](crate::types::Json).
+///
+/// The query macros can bind the fields of a struct to the named parameters of a query by name
+/// instead, with `..value`; see the documentation of `query!()`.
 pub trait IntoArguments<'q, DB: HasArguments<'q>>: Sized + Send {
     fn into_arguments(self) -> <DB as HasArguments<'q>>::Arguments;
 }
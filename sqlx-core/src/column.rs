@@ -1,5 +1,6 @@
 use crate::database::Database;
 use crate::error::Error;
+use crate::row::Row;
 use std::fmt::Debug;
 
 pub trait Column: private_column::Sealed + 'static + Send + Sync + Debug {
@@ -19,6 +20,14 @@ pub trait Column: private_column::Sealed + 'static + Send + Sync + Debug {
 
     /// Gets the type information for the column.
     fn type_info(&self) -> &<Self::Database as Database>::TypeInfo;
+
+    /// Gets the name of the table the column was selected from, if it is a column of a table.
+    ///
+    /// This is only known for the columns of a statement returned by
+    /// [`describe`](crate::executor::Executor::describe), and not for MSSQL.
+    fn origin_table(&self) -> Option<&str> {
+        None
+    }
 }
 
 // Prevent users from implementing the `Row` trait.
@@ -87,11 +96,38 @@ macro_rules! impl_column_index_for_statement {
     };
 }
 
+/// The name of a column read by a `#[sqlx(flatten, prefix = "..")]` field of a derived
+/// [`FromRow`](crate::from_row::FromRow), which starts with the prefix.
+#[doc(hidden)]
+#[derive(Clone, Copy)]
+pub struct PrefixedColumn<'a> {
+    pub prefix: &'a str,
+    pub name: &'a str,
+}
+
+// formatted like the full name of the column, for errors
+impl Debug for PrefixedColumn<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        Debug::fmt(&format!("{}{}", self.prefix, self.name), f)
+    }
+}
+
+impl<R: Row> ColumnIndex<R> for PrefixedColumn<'_> {
+    fn index(&self, row: &R) -> Result<usize, Error> {
+        // like a lookup by name, the last of several columns with the same name is used
+        row.columns()
+            .iter()
+            .rposition(|column| column.name().strip_prefix(self.prefix) == Some(self.name))
+            .ok_or_else(|| Error::ColumnNotFound(format!("{}{}", self.prefix, self.name)))
+    }
+}
+
 // Prevent users from implementing the `ColumnIndex` trait.
 mod private_column_index {
     pub trait Sealed {}
 
     impl Sealed for usize {}
+    impl Sealed for super::PrefixedColumn<'_> {}
     impl Sealed for str {}
     impl<T> Sealed for &'_ T where T: Sealed + ?Sized {}
 }
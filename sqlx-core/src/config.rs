@@ -0,0 +1,249 @@
+//! Project configuration shared by `sqlx-cli` and the query macros.
+//!
+//! The configuration of a crate is read from a `sqlx.toml` file next to its `Cargo.toml` or,
+//! if there is none, from the `[package.metadata.sqlx]` table of its `Cargo.toml`. Every key is
+//! optional, so each crate in a workspace with several databases only needs to set what differs:
+//!
+//! ```toml
+//! [common]
+//! # the environment variable holding the database URL
+//! database-url-var = "ANALYTICS_DATABASE_URL"
+//!
+//! [migrate]
+//! migrations-dir = "db/migrations"
+//! table-name = "_analytics_migrations"
+//!
+//! [macros]
+//! # the directory to keep `sqlx-data.json` or `sqlx-data/` in
+//! offline-dir = "db"
+//! # more derives for the records of `query!()`, besides `Debug`
+//! record-derives = ["Clone", "Serialize"]
+//! # without a database URL, check queries against an in-memory SQLite database with the
+//! # migrations in `migrations-dir` applied
+//! sqlite-migrations = true
+//!
+//! # the Rust types of columns in query results, as if overridden with `as "name: Type"`;
+//! # a column is matched by `table.column` first, if its table is known, then by its name
+//! [macros.column-types]
+//! user_id = "crate::UserId"
+//! "posts.id" = "crate::PostId"
+//!
+//! # the Rust types of every column and parameter of an SQL type
+//! [macros.type-overrides]
+//! uuid = "crate::Id"
+//! citext = "String"
+//! timestamptz = "sqlx::types::time::OffsetDateTime"
+//! ```
+
+use std::collections::BTreeMap;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+
+/// The configuration file read by [`Config::read_from`].
+pub const CONFIG_FILE: &str = "sqlx.toml";
+
+#[derive(Debug, thiserror::Error)]
+pub enum ConfigError {
+    #[error("failed to read `{path}`: {source}")]
+    Read {
+        path: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+
+    #[error("failed to parse `{path}`: {source}")]
+    Parse {
+        path: PathBuf,
+        #[source]
+        source: toml::de::Error,
+    },
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Deserialize)]
+#[serde(default, rename_all = "kebab-case", deny_unknown_fields)]
+pub struct Config {
+    pub common: CommonConfig,
+    pub migrate: MigrateConfig,
+    pub macros: MacrosConfig,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Deserialize)]
+#[serde(default, rename_all = "kebab-case", deny_unknown_fields)]
+pub struct CommonConfig {
+    /// The environment variable to read the database URL from, instead of `DATABASE_URL`.
+    pub database_url_var: Option<String>,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Deserialize)]
+#[serde(default, rename_all = "kebab-case", deny_unknown_fields)]
+pub struct MigrateConfig {
+    /// The directory containing migrations, instead of `migrations`.
+    pub migrations_dir: Option<String>,
+
+    /// The table to track applied migrations in, instead of `_sqlx_migrations`.
+    pub table_name: Option<String>,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Deserialize)]
+#[serde(default, rename_all = "kebab-case", deny_unknown_fields)]
+pub struct MacrosConfig {
+    /// The directory to keep the query data for offline builds in, instead of the crate or
+    /// workspace root.
+    pub offline_dir: Option<String>,
+
+    /// The Rust types to use for columns with these names, or `table.column`, in the results of
+    /// every query.
+    pub column_types: BTreeMap<String, String>,
+
+    /// The Rust types to use for columns and parameters of these SQL types in every query,
+    /// instead of the types the database maps them to. SQL type names are matched
+    /// case-insensitively.
+    pub type_overrides: BTreeMap<String, String>,
+
+    /// The derives to add to the records generated by `query!()`, besides `Debug`. `Serialize`
+    /// is provided by SQLx with the `json` feature; other derives are resolved in the crate.
+    pub record_derives: Vec<String>,
+
+    /// Whether to check queries against an in-memory SQLite database with the migrations of
+    /// [`MigrateConfig::migrations_dir`] applied if the database URL is not set, like
+    /// `SQLX_SQLITE_MIGRATIONS` does.
+    pub sqlite_migrations: bool,
+}
+
+impl Config {
+    /// Read the configuration of the crate in `dir`, or the default if it has none.
+    pub fn read_from(dir: &Path) -> Result<Self, ConfigError> {
+        let path = dir.join(CONFIG_FILE);
+
+        if path.exists() {
+            let contents = read(&path)?;
+
+            return toml::from_str(&contents).map_err(|source| ConfigError::Parse { path, source });
+        }
+
+        let path = dir.join("Cargo.toml");
+
+        if !path.exists() {
+            return Ok(Config::default());
+        }
+
+        #[derive(Deserialize)]
+        struct Manifest {
+            package: Option<Package>,
+        }
+
+        #[derive(Deserialize)]
+        struct Package {
+            metadata: Option<Metadata>,
+        }
+
+        #[derive(Deserialize)]
+        struct Metadata {
+            sqlx: Option<Config>,
+        }
+
+        let manifest: Manifest =
+            toml::from_str(&read(&path)?).map_err(|source| ConfigError::Parse { path, source })?;
+
+        Ok(manifest
+            .package
+            .and_then(|package| package.metadata)
+            .and_then(|metadata| metadata.sqlx)
+            .unwrap_or_default())
+    }
+}
+
+impl CommonConfig {
+    pub fn database_url_var(&self) -> &str {
+        self.database_url_var.as_deref().unwrap_or("DATABASE_URL")
+    }
+}
+
+impl MacrosConfig {
+    /// The Rust type configured for a column of a query, by `table.column` if the table of the
+    /// column is known, then by its name.
+    pub fn column_type(&self, table: Option<&str>, column: &str) -> Option<&str> {
+        table
+            .and_then(|table| self.column_types.get(&format!("{}.{}", table, column)))
+            .or_else(|| self.column_types.get(column))
+            .map(String::as_str)
+    }
+
+    /// The Rust type configured for an SQL type.
+    pub fn type_override(&self, sql_type: &str) -> Option<&str> {
+        self.type_overrides
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case(sql_type))
+            .map(|(_, ty)| ty.as_str())
+    }
+}
+
+impl MigrateConfig {
+    pub fn migrations_dir(&self) -> &str {
+        self.migrations_dir.as_deref().unwrap_or("migrations")
+    }
+
+    pub fn table_name(&self) -> &str {
+        self.table_name.as_deref().unwrap_or("_sqlx_migrations")
+    }
+}
+
+fn read(path: &Path) -> Result<String, ConfigError> {
+    std::fs::read_to_string(path).map_err(|source| ConfigError::Read {
+        path: path.to_owned(),
+        source,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_parses_config() {
+        let config: Config = toml::from_str(
+            r#"
+[common]
+database-url-var = "OTHER_DATABASE_URL"
+
+[migrate]
+table-name = "_other_migrations"
+
+[macros]
+record-derives = ["Clone", "Serialize"]
+sqlite-migrations = true
+
+[macros.column-types]
+user_id = "crate::UserId"
+"posts.id" = "crate::PostId"
+
+[macros.type-overrides]
+citext = "String"
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(config.common.database_url_var(), "OTHER_DATABASE_URL");
+        assert_eq!(config.migrate.migrations_dir(), "migrations");
+        assert_eq!(config.migrate.table_name(), "_other_migrations");
+        assert_eq!(config.macros.offline_dir, None);
+        assert_eq!(config.macros.record_derives, ["Clone", "Serialize"]);
+        assert!(config.macros.sqlite_migrations);
+        assert_eq!(config.macros.column_types["user_id"], "crate::UserId");
+
+        let macros = &config.macros;
+        assert_eq!(macros.column_type(None, "user_id"), Some("crate::UserId"));
+        assert_eq!(
+            macros.column_type(Some("posts"), "id"),
+            Some("crate::PostId")
+        );
+        assert_eq!(macros.column_type(Some("users"), "id"), None);
+        assert_eq!(macros.column_type(None, "id"), None);
+        assert_eq!(macros.type_override("CITEXT"), Some("String"));
+        assert_eq!(macros.type_override("TEXT"), None);
+
+        assert!(toml::from_str::<Config>("[migrate]\ntable = \"x\"").is_err());
+    }
+}
@@ -43,6 +43,13 @@ pub trait Encode<'q, DB: Database> {
     fn size_hint(&self) -> usize {
         mem::size_of_val(self)
     }
+
+    /// If `self` is a list that is bound as one parameter per value, like
+    /// [`InList`](crate::types::InList), the values with their types.
+    #[doc(hidden)]
+    fn list_values(&self) -> Option<Vec<(&dyn Encode<'q, DB>, DB::TypeInfo)>> {
+        None
+    }
 }
 
 impl<'q, T, DB: Database> Encode<'q, DB> for &'_ T
@@ -68,6 +75,11 @@ where
     fn size_hint(&self) -> usize {
         (**self).size_hint()
     }
+
+    #[inline]
+    fn list_values(&self) -> Option<Vec<(&dyn Encode<'q, DB>, DB::TypeInfo)>> {
+        (**self).list_values()
+    }
 }
 
 #[allow(unused_macros)]
@@ -114,6 +126,18 @@ macro_rules! impl_encode_for_option {
             fn size_hint(&self) -> usize {
                 self.as_ref().map_or(0, crate::encode::Encode::size_hint)
             }
+
+            #[inline]
+            fn list_values(
+                &self,
+            ) -> Option<
+                Vec<(
+                    &dyn crate::encode::Encode<'q, $DB>,
+                    <$DB as crate::database::Database>::TypeInfo,
+                )>,
+            > {
+                self.as_ref().and_then(crate::encode::Encode::list_values)
+            }
         }
     };
 }
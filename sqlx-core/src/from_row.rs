@@ -92,8 +92,188 @@ use crate::row::Row;
 /// will set the value of the field `location` to the default value of `Option<String>`,
 /// which is `None`.
 ///
+/// #### `try_from`
+///
+/// When the type of a field cannot be decoded from its column directly, but can be converted from
+/// a type that can, such as a `u64` from a `BIGINT` or a newtype with a checked constructor, the
+/// `try_from` attribute decodes the column as the given type and converts it with
+/// [`TryFrom`](std::convert::TryFrom):
+///
+/// ```rust,ignore
+/// #[derive(sqlx::FromRow)]
+/// struct User {
+///     #[sqlx(try_from = "i64")]
+///     id: u64,
+///     name: String,
+/// }
+/// ```
+///
+/// A failed conversion is returned as an [`Error::ColumnDecode`] for the column.
+///
+/// #### `json`
+///
+/// A field of any type that implements `serde::Deserialize` can be decoded from a JSON column
+/// with the `json` attribute, which decodes it as `sqlx::types::Json<T>` without having to wrap
+/// the field in it. This requires the `json` feature:
+///
+/// ```rust,ignore
+/// #[derive(serde::Deserialize)]
+/// struct Settings {
+///     theme: String,
+/// }
+///
+/// #[derive(sqlx::FromRow)]
+/// struct User {
+///     id: i32,
+///     #[sqlx(json)]
+///     settings: Settings,
+/// }
+/// ```
+///
+/// The column must not be `NULL`, unless the field is of type `Option<T>`, which is `None` for a
+/// `NULL` column as well as for a JSON `null`.
+///
+/// #### `skip`
+///
+/// A field that is not read from the row at all, e.g. one that is filled in later, can be
+/// skipped with the `skip` attribute. It is set to the [`Default`] value of its type:
+///
+/// ```rust,ignore
+/// #[derive(sqlx::FromRow)]
+/// struct User {
+///     id: i32,
+///     name: String,
+///     #[sqlx(skip)]
+///     posts: Vec<Post>,
+/// }
+/// ```
+///
+/// #### `flatten`
+///
+/// A field whose type implements `FromRow` itself, e.g. another struct deriving it, can be read
+/// from the same row with the `flatten` attribute. The columns of both structs are then expected
+/// side by side in the row:
+///
+/// ```rust,ignore
+/// #[derive(sqlx::FromRow)]
+/// struct Address {
+///     country: String,
+///     city: String,
+/// }
+///
+/// #[derive(sqlx::FromRow)]
+/// struct User {
+///     id: i32,
+///     name: String,
+///     #[sqlx(flatten)]
+///     address: Address,
+/// }
+/// ```
+///
+/// Given a query such as:
+///
+/// ```sql
+/// SELECT id, name, country, city FROM users;
+/// ```
+///
+/// will read the columns `country` and `city` into the field `address`.
+///
+/// When the same struct is flattened more than once, or its column names clash with those of
+/// other fields, as is common when joining tables, `prefix` reads it from the columns named with
+/// the given prefix instead. Prefixes of nested flattened structs are added to it:
+///
+/// ```rust,ignore
+/// #[derive(sqlx::FromRow)]
+/// struct Order {
+///     id: i32,
+///     #[sqlx(flatten, prefix = "billing_")]
+///     billing: Address,
+///     #[sqlx(flatten, prefix = "shipping_")]
+///     shipping: Address,
+/// }
+/// ```
+///
+/// Given a query such as:
+///
+/// ```sql
+/// SELECT id, billing_country, billing_city, shipping_country, shipping_city FROM orders;
+/// ```
+///
+/// will read the columns `billing_country` and `billing_city` into the field `billing`. A prefix
+/// can only be applied to types that derive `FromRow`.
+///
+/// ### Enums
+///
+/// Rows that can be one of several kinds, told apart by the value of a column, can be read into
+/// an enum by naming that column with the `tag` attribute. The value of the column selects the
+/// variant, which is the name of the variant, or the name given with its `rename` attribute.
+/// The fields of the variant are then read like those of a struct, with the same attributes:
+///
+/// ```rust,ignore
+/// #[derive(sqlx::FromRow)]
+/// #[sqlx(tag = "kind", rename_all = "lowercase")]
+/// enum Shape {
+///     Circle { radius: f64 },
+///     #[sqlx(rename = "rect")]
+///     Rectangle { width: f64, height: f64 },
+///     Point,
+///     Polygon(Polygon),
+/// }
+/// ```
+///
+/// Given a query such as:
+///
+/// ```sql
+/// SELECT kind, radius, width, height, sides FROM shapes;
+/// ```
+///
+/// will read a row with `kind = 'rect'` as a `Shape::Rectangle` from the columns `width` and
+/// `height`, and ignore the other columns, which are typically `NULL` for it. A variant with a
+/// single unnamed field, like `Polygon`, reads the field from the row with its own `FromRow`
+/// implementation. A `rename_all` attribute on the enum applies to the values of the tag as well
+/// as to the columns of the fields. A row whose tag does not match any variant is returned as an
+/// [`Error::ColumnDecode`] for the tag column.
+///
+/// The tag is read as a `String`. A tag of another type, such as an integer, is given with the
+/// `tag_type` attribute, and the value of each variant with its own `tag` attribute:
+///
+/// ```rust,ignore
+/// #[derive(sqlx::FromRow)]
+/// #[sqlx(tag = "kind", tag_type = "i32")]
+/// enum Shape {
+///     #[sqlx(tag = 1)]
+///     Circle { radius: f64 },
+///     #[sqlx(tag = 2)]
+///     Point,
+/// }
+/// ```
+///
+/// A tag column of a Postgres enum type is not a `String`, and is cast to text in the query
+/// instead, as in `SELECT kind::text AS kind, ...`.
+///
 pub trait FromRow<'r, R: Row>: Sized {
     fn from_row(row: &'r R) -> Result<Self, Error>;
+
+    /// Builds the record from the columns of `row` whose names start with `prefix`, for
+    /// `#[sqlx(flatten, prefix = "..")]`.
+    ///
+    /// Only derived implementations can apply a prefix; others return an error if one is given.
+    #[doc(hidden)]
+    fn from_row_prefixed(row: &'r R, prefix: &str) -> Result<Self, Error> {
+        if !prefix.is_empty() {
+            return Err(Error::Decode(
+                format!(
+                    "cannot read `{}` from columns prefixed with `{}`, \
+                     as only a derived `FromRow` supports a column prefix",
+                    std::any::type_name::<Self>(),
+                    prefix
+                )
+                .into(),
+            ));
+        }
+
+        Self::from_row(row)
+    }
 }
 
 // implement FromRow for tuples of types that implement Decode
@@ -19,6 +19,11 @@
 #[cfg(feature = "bigdecimal")]
 extern crate bigdecimal_ as bigdecimal;
 
+// `Serialize` for the records of `query!()`
+#[cfg(feature = "json")]
+#[doc(hidden)]
+pub use serde;
+
 #[macro_use]
 mod ext;
 
@@ -74,6 +79,12 @@ pub mod value;
 #[cfg(feature = "migrate")]
 pub mod migrate;
 
+#[cfg(feature = "migrate")]
+pub mod testing;
+
+#[cfg(feature = "config")]
+pub mod config;
+
 #[cfg(all(
     any(
         feature = "postgres",
@@ -15,6 +15,11 @@ pub enum MigrateError {
     #[error("migration {0} was previously applied but has been modified")]
     VersionMismatch(i64),
 
+    #[error(
+        "seed {0} was previously applied but has been modified; re-run seeds to apply it again"
+    )]
+    SeedMismatch(String),
+
     #[error("cannot mix reversible migrations with simple migrations. All migrations should be reversible or simple migrations")]
     InvalidMixReversibleAndSimple,
 
@@ -23,4 +28,22 @@ pub enum MigrateError {
         "migration {0} is partially applied; fix and remove row from `_sqlx_migrations` table"
     )]
     Dirty(i64),
+
+    // the same as `Dirty`, for a migrations table other than `_sqlx_migrations`
+    #[error("migration {0} is partially applied; fix and remove row from `{1}` table")]
+    DirtyTable(i64, String),
+
+    #[error("{0} is not supported by this database driver")]
+    Unsupported(&'static str),
+}
+
+impl MigrateError {
+    /// The error for a migration that is partially applied, naming the table it is recorded in.
+    pub fn dirty(version: i64, table_name: &str) -> Self {
+        if table_name == super::migrate::DEFAULT_TABLE_NAME {
+            MigrateError::Dirty(version)
+        } else {
+            MigrateError::DirtyTable(version, table_name.to_owned())
+        }
+    }
 }
@@ -1,5 +1,5 @@
 use crate::error::Error;
-use crate::migrate::{AppliedMigration, MigrateError, Migration};
+use crate::migrate::{AppliedMigration, AppliedSeed, MigrateError, Migration, Schema, Seed};
 use futures_core::future::BoxFuture;
 use std::time::Duration;
 
@@ -17,21 +17,65 @@ pub trait MigrateDatabase {
     fn drop_database(uri: &str) -> BoxFuture<'_, Result<(), Error>>;
 }
 
+/// The table applied migrations are tracked in unless another is configured.
+pub(crate) const DEFAULT_TABLE_NAME: &str = "_sqlx_migrations";
+
 // 'e = Executor
+//
+// the methods taking the name of the migrations table have defaults that only support
+// `_sqlx_migrations`, through the methods without it, so that drivers outside of SQLx keep working
 pub trait Migrate {
     // ensure migrations table exists
     // will create or migrate it if needed
     fn ensure_migrations_table(&mut self) -> BoxFuture<'_, Result<(), MigrateError>>;
 
+    // ensure migrations table with the given name exists
+    fn ensure_migrations_table_in<'e>(
+        &'e mut self,
+        table_name: &'e str,
+    ) -> BoxFuture<'e, Result<(), MigrateError>> {
+        if table_name == DEFAULT_TABLE_NAME {
+            self.ensure_migrations_table()
+        } else {
+            unsupported(UNSUPPORTED_TABLE_NAME)
+        }
+    }
+
     // Return the version on which the database is dirty or None otherwise.
     // "dirty" means there is a partially applied migration that failed.
     fn dirty_version(&mut self) -> BoxFuture<'_, Result<Option<i64>, MigrateError>>;
 
+    // Return the version on which the database is dirty in the given migrations table
+    fn dirty_version_in<'e>(
+        &'e mut self,
+        table_name: &'e str,
+    ) -> BoxFuture<'e, Result<Option<i64>, MigrateError>> {
+        if table_name == DEFAULT_TABLE_NAME {
+            self.dirty_version()
+        } else {
+            unsupported(UNSUPPORTED_TABLE_NAME)
+        }
+    }
+
     // Return the current version and if the database is "dirty".
     // "dirty" means there is a partially applied migration that failed.
     #[deprecated]
     fn version(&mut self) -> BoxFuture<'_, Result<Option<(i64, bool)>, MigrateError>>;
 
+    // Return the current version and if the database is "dirty" in the given migrations table
+    #[deprecated]
+    fn version_in<'e>(
+        &'e mut self,
+        table_name: &'e str,
+    ) -> BoxFuture<'e, Result<Option<(i64, bool)>, MigrateError>> {
+        if table_name == DEFAULT_TABLE_NAME {
+            #[allow(deprecated)]
+            self.version()
+        } else {
+            unsupported(UNSUPPORTED_TABLE_NAME)
+        }
+    }
+
     // validate the migration
     // checks that it does exist on the database and that the checksum matches
     #[deprecated]
@@ -40,11 +84,38 @@ pub trait Migrate {
         migration: &'m Migration,
     ) -> BoxFuture<'m, Result<(), MigrateError>>;
 
+    // validate the migration against the given migrations table
+    #[deprecated]
+    fn validate_in<'e: 'm, 'm>(
+        &'e mut self,
+        table_name: &'m str,
+        migration: &'m Migration,
+    ) -> BoxFuture<'m, Result<(), MigrateError>> {
+        if table_name == DEFAULT_TABLE_NAME {
+            #[allow(deprecated)]
+            self.validate(migration)
+        } else {
+            unsupported(UNSUPPORTED_TABLE_NAME)
+        }
+    }
+
     // Return the ordered list of applied migrations
     fn list_applied_migrations(
         &mut self,
     ) -> BoxFuture<'_, Result<Vec<AppliedMigration>, MigrateError>>;
 
+    // Return the ordered list of migrations applied in the given migrations table
+    fn list_applied_migrations_in<'e>(
+        &'e mut self,
+        table_name: &'e str,
+    ) -> BoxFuture<'e, Result<Vec<AppliedMigration>, MigrateError>> {
+        if table_name == DEFAULT_TABLE_NAME {
+            self.list_applied_migrations()
+        } else {
+            unsupported(UNSUPPORTED_TABLE_NAME)
+        }
+    }
+
     // Should acquire a database lock so that only one migration process
     // can run at a time. [`Migrate`] will call this function before applying
     // any migrations.
@@ -62,6 +133,19 @@ pub trait Migrate {
         migration: &'m Migration,
     ) -> BoxFuture<'m, Result<Duration, MigrateError>>;
 
+    // run SQL from migration, recording it in the given migrations table
+    fn apply_in<'e: 'm, 'm>(
+        &'e mut self,
+        table_name: &'m str,
+        migration: &'m Migration,
+    ) -> BoxFuture<'m, Result<Duration, MigrateError>> {
+        if table_name == DEFAULT_TABLE_NAME {
+            self.apply(migration)
+        } else {
+            unsupported(UNSUPPORTED_TABLE_NAME)
+        }
+    }
+
     // run a revert SQL from migration in a DDL transaction
     // deletes the row in [_migrations] table with specified migration version on completion (success or failure)
     // returns the time taking to run the migration SQL
@@ -69,4 +153,68 @@ pub trait Migrate {
         &'e mut self,
         migration: &'m Migration,
     ) -> BoxFuture<'m, Result<Duration, MigrateError>>;
+
+    // run a revert SQL from migration, deleting its row from the given migrations table
+    fn revert_in<'e: 'm, 'm>(
+        &'e mut self,
+        table_name: &'m str,
+        migration: &'m Migration,
+    ) -> BoxFuture<'m, Result<Duration, MigrateError>> {
+        if table_name == DEFAULT_TABLE_NAME {
+            self.revert(migration)
+        } else {
+            unsupported(UNSUPPORTED_TABLE_NAME)
+        }
+    }
+
+    // replace the rows of all applied migrations up to and including the version of the
+    // migration with a single row for it, without running its SQL
+    // used to bring databases in line after those migrations were squashed into it
+    fn apply_baseline<'e: 'm, 'm>(
+        &'e mut self,
+        table_name: &'m str,
+        migration: &'m Migration,
+    ) -> BoxFuture<'m, Result<(), MigrateError>> {
+        let _ = (table_name, migration);
+        unsupported("applying a baseline")
+    }
+
+    // ensure seeds table exists
+    // seeds are tracked apart from migrations so they can be re-run or skipped per environment
+    fn ensure_seeds_table(&mut self) -> BoxFuture<'_, Result<(), MigrateError>> {
+        unsupported("seeding")
+    }
+
+    // Return the list of applied seeds, ordered by name
+    fn list_applied_seeds(&mut self) -> BoxFuture<'_, Result<Vec<AppliedSeed>, MigrateError>> {
+        unsupported("seeding")
+    }
+
+    // run SQL from seed in a transaction
+    // replaces the row in [_sqlx_seeds] table for the seed in the same transaction
+    // returns the time taking to run the seed SQL
+    fn apply_seed<'e: 'm, 'm>(
+        &'e mut self,
+        seed: &'m Seed,
+    ) -> BoxFuture<'m, Result<Duration, MigrateError>> {
+        let _ = seed;
+        unsupported("seeding")
+    }
+
+    // introspect the tables, columns, indexes, constraints, enums and views of the database
+    // through its own catalog, excluding the migrations table and the [_sqlx_seeds] table
+    // the result renders to deterministic DDL through its `Display` impl
+    fn schema<'e>(
+        &'e mut self,
+        migrations_table: &'e str,
+    ) -> BoxFuture<'e, Result<Schema, MigrateError>> {
+        let _ = migrations_table;
+        unsupported("schema introspection")
+    }
+}
+
+const UNSUPPORTED_TABLE_NAME: &str = "a migrations table other than `_sqlx_migrations`";
+
+fn unsupported<'e, T>(feature: &'static str) -> BoxFuture<'e, Result<T, MigrateError>> {
+    Box::pin(async move { Err(MigrateError::Unsupported(feature)) })
 }
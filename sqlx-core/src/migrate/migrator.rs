@@ -1,5 +1,7 @@
 use crate::acquire::Acquire;
-use crate::migrate::{AppliedMigration, Migrate, MigrateError, Migration, MigrationSource};
+use crate::migrate::{
+    AppliedMigration, Migrate, MigrateError, Migration, MigrationSource, DEFAULT_TABLE_NAME,
+};
 use std::borrow::Cow;
 use std::collections::{HashMap, HashSet};
 use std::ops::Deref;
@@ -9,6 +11,7 @@ use std::slice;
 pub struct Migrator {
     pub migrations: Cow<'static, [Migration]>,
     pub ignore_missing: bool,
+    table_name: Cow<'static, str>,
 }
 
 fn validate_applied_migrations(
@@ -56,15 +59,41 @@ impl Migrator {
         Ok(Self {
             migrations: Cow::Owned(source.resolve().await.map_err(MigrateError::Source)?),
             ignore_missing: false,
+            table_name: Cow::Borrowed(DEFAULT_TABLE_NAME),
         })
     }
 
+    /// Creates a new instance tracking migrations in the given table, for `migrate!()`.
+    #[doc(hidden)]
+    pub const fn with_table_name(
+        migrations: Cow<'static, [Migration]>,
+        ignore_missing: bool,
+        table_name: &'static str,
+    ) -> Self {
+        Self {
+            migrations,
+            ignore_missing,
+            table_name: Cow::Borrowed(table_name),
+        }
+    }
+
     /// Specify should ignore applied migrations that missing in the resolved migrations.
     pub fn set_ignore_missing(&mut self, ignore_missing: bool) -> &Self {
         self.ignore_missing = ignore_missing;
         self
     }
 
+    /// Specify the table to track applied migrations in, `_sqlx_migrations` by default.
+    pub fn set_table_name(&mut self, table_name: impl Into<Cow<'static, str>>) -> &Self {
+        self.table_name = table_name.into();
+        self
+    }
+
+    /// The table applied migrations are tracked in.
+    pub fn table_name(&self) -> &str {
+        &self.table_name
+    }
+
     /// Get an iterator over all known migrations.
     pub fn iter(&self) -> slice::Iter<'_, Migration> {
         self.migrations.iter()
@@ -99,14 +128,14 @@ impl Migrator {
 
         // creates [_migrations] table only if needed
         // eventually this will likely migrate previous versions of the table
-        conn.ensure_migrations_table().await?;
+        conn.ensure_migrations_table_in(&self.table_name).await?;
 
-        let version = conn.dirty_version().await?;
+        let version = conn.dirty_version_in(&self.table_name).await?;
         if let Some(version) = version {
-            return Err(MigrateError::Dirty(version));
+            return Err(MigrateError::dirty(version, &self.table_name));
         }
 
-        let applied_migrations = conn.list_applied_migrations().await?;
+        let applied_migrations = conn.list_applied_migrations_in(&self.table_name).await?;
         validate_applied_migrations(&applied_migrations, self)?;
 
         let applied_migrations: HashMap<_, _> = applied_migrations
@@ -126,7 +155,7 @@ impl Migrator {
                     }
                 }
                 None => {
-                    conn.apply(migration).await?;
+                    conn.apply_in(&self.table_name, migration).await?;
                 }
             }
         }
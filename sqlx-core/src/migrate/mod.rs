@@ -4,11 +4,21 @@ mod migrate;
 mod migration;
 mod migration_type;
 mod migrator;
+mod schema;
+mod seed;
+mod seeder;
 mod source;
 
 pub use error::MigrateError;
+pub(crate) use migrate::DEFAULT_TABLE_NAME;
 pub use migrate::{Migrate, MigrateDatabase};
 pub use migration::{AppliedMigration, Migration};
 pub use migration_type::MigrationType;
 pub use migrator::Migrator;
+pub use schema::{
+    Composite, Constraint, ConstraintKind, Enum, Index, Schema, SchemaDialect, Table, TableColumn,
+    View,
+};
+pub use seed::{AppliedSeed, Seed, SeedData, SeedValue};
+pub use seeder::Seeder;
 pub use source::MigrationSource;
@@ -0,0 +1,303 @@
+use std::fmt::{self, Display, Formatter, Write};
+
+/// The SQL dialect a [`Schema`] was introspected from.
+///
+/// This determines how identifiers are quoted and where constraints are placed when the schema
+/// is rendered back to DDL.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum SchemaDialect {
+    Postgres,
+    MySql,
+    Sqlite,
+}
+
+impl SchemaDialect {
+    /// Quote an identifier for use in DDL of this dialect.
+    pub fn quote_ident(&self, ident: &str) -> String {
+        match self {
+            SchemaDialect::MySql => format!("`{}`", ident.replace('`', "``")),
+            SchemaDialect::Postgres | SchemaDialect::Sqlite => {
+                format!("\"{}\"", ident.replace('"', "\"\""))
+            }
+        }
+    }
+
+    /// Whether foreign keys are added after all tables are created so that tables may be
+    /// created in any order. SQLite cannot add constraints to an existing table.
+    pub fn defers_foreign_keys(&self) -> bool {
+        *self != SchemaDialect::Sqlite
+    }
+}
+
+/// The shape of a database as seen through its own catalog, excluding the `_sqlx_migrations`
+/// table.
+///
+/// Tables, enums and the columns, constraints and indexes of each table are sorted by name;
+/// columns keep their ordinal position and composite types and views keep their creation order
+/// so that those depending on each other can be replayed.
+///
+/// The [`Display`] implementation renders the schema as deterministic DDL suitable for checking
+/// into version control.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Schema {
+    pub dialect: SchemaDialect,
+    pub enums: Vec<Enum>,
+    pub composites: Vec<Composite>,
+    pub tables: Vec<Table>,
+    pub views: Vec<View>,
+}
+
+/// A user-defined enumeration type (Postgres only; MySQL enums are part of the column type).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Enum {
+    pub name: String,
+    pub variants: Vec<String>,
+}
+
+/// A user-defined composite type (Postgres only).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Composite {
+    pub name: String,
+    pub attributes: Vec<TableColumn>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Table {
+    pub name: String,
+    pub columns: Vec<TableColumn>,
+    pub constraints: Vec<Constraint>,
+    pub indexes: Vec<Index>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TableColumn {
+    pub name: String,
+    /// The declared type of the column, as the database reports it.
+    pub data_type: String,
+    pub nullable: bool,
+    /// The default value expression, if any.
+    pub default: Option<String>,
+    /// Any other column attribute the dialect needs to recreate the column,
+    /// e.g. `AUTO_INCREMENT` or `GENERATED BY DEFAULT AS IDENTITY`.
+    pub extra: Option<String>,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ConstraintKind {
+    PrimaryKey,
+    Unique,
+    ForeignKey,
+    Check,
+    Exclusion,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Constraint {
+    pub name: String,
+    pub kind: ConstraintKind,
+    /// The constraint definition without its name, e.g. `PRIMARY KEY (id)`.
+    pub definition: String,
+}
+
+/// An index that is not implied by a primary key or unique constraint.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Index {
+    pub name: String,
+    /// The complete `CREATE INDEX` statement, without a trailing semicolon.
+    pub definition: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct View {
+    pub name: String,
+    /// The complete `CREATE VIEW` statement, without a trailing semicolon.
+    pub definition: String,
+}
+
+impl Schema {
+    pub fn new(dialect: SchemaDialect) -> Self {
+        Schema {
+            dialect,
+            enums: Vec::new(),
+            composites: Vec::new(),
+            tables: Vec::new(),
+            views: Vec::new(),
+        }
+    }
+
+    /// Find a table by name.
+    pub fn table(&self, name: &str) -> Option<&Table> {
+        self.tables.iter().find(|table| table.name == name)
+    }
+}
+
+impl Enum {
+    /// Render the `CREATE TYPE` statement for the enum, without a trailing semicolon.
+    pub fn to_sql(&self, dialect: SchemaDialect) -> String {
+        let variants = self
+            .variants
+            .iter()
+            .map(|variant| format!("'{}'", variant.replace('\'', "''")))
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        format!(
+            "CREATE TYPE {} AS ENUM ({})",
+            dialect.quote_ident(&self.name),
+            variants
+        )
+    }
+}
+
+impl Composite {
+    /// Render the `CREATE TYPE` statement for the composite type, without a trailing semicolon.
+    pub fn to_sql(&self, dialect: SchemaDialect) -> String {
+        let attributes = self
+            .attributes
+            .iter()
+            .map(|attribute| attribute.to_sql(dialect))
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        format!(
+            "CREATE TYPE {} AS ({})",
+            dialect.quote_ident(&self.name),
+            attributes
+        )
+    }
+}
+
+impl Table {
+    /// Find a column by name.
+    pub fn column(&self, name: &str) -> Option<&TableColumn> {
+        self.columns.iter().find(|column| column.name == name)
+    }
+
+    /// Render the `CREATE TABLE` statement for the table, without a trailing semicolon or its
+    /// indexes.
+    ///
+    /// Foreign keys are left out where [`SchemaDialect::defers_foreign_keys()`] is true and must
+    /// be added with `ALTER TABLE` once the referenced tables exist.
+    pub fn to_sql(&self, dialect: SchemaDialect) -> String {
+        let mut lines: Vec<String> = self
+            .columns
+            .iter()
+            .map(|column| column.to_sql(dialect))
+            .collect();
+
+        lines.extend(
+            self.constraints
+                .iter()
+                .filter(|c| {
+                    !(dialect.defers_foreign_keys() && c.kind == ConstraintKind::ForeignKey)
+                })
+                .map(|c| c.to_sql(dialect)),
+        );
+
+        let mut sql = format!("CREATE TABLE {} (\n", dialect.quote_ident(&self.name));
+
+        for (i, line) in lines.iter().enumerate() {
+            let sep = if i + 1 < lines.len() { "," } else { "" };
+            let _ = writeln!(sql, "    {}{}", line, sep);
+        }
+
+        sql.push(')');
+        sql
+    }
+}
+
+impl TableColumn {
+    /// Render the column as it appears in a `CREATE TABLE` or `ADD COLUMN` clause.
+    pub fn to_sql(&self, dialect: SchemaDialect) -> String {
+        let mut sql = dialect.quote_ident(&self.name);
+
+        // SQLite allows columns without a declared type
+        if !self.data_type.is_empty() {
+            sql.push(' ');
+            sql.push_str(&self.data_type);
+        }
+
+        if let Some(extra) = &self.extra {
+            sql.push(' ');
+            sql.push_str(extra);
+        }
+
+        if !self.nullable {
+            sql.push_str(" NOT NULL");
+        }
+
+        if let Some(default) = &self.default {
+            sql.push_str(" DEFAULT ");
+            sql.push_str(default);
+        }
+
+        sql
+    }
+}
+
+impl Constraint {
+    /// Render the constraint as it appears in a `CREATE TABLE` or `ADD CONSTRAINT` clause.
+    pub fn to_sql(&self, dialect: SchemaDialect) -> String {
+        // SQLite does not retain constraint names so we don't invent any
+        if dialect == SchemaDialect::Sqlite {
+            self.definition.clone()
+        } else {
+            format!(
+                "CONSTRAINT {} {}",
+                dialect.quote_ident(&self.name),
+                self.definition
+            )
+        }
+    }
+}
+
+impl Display for Schema {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let dialect = self.dialect;
+        let mut statements = Vec::new();
+
+        for enum_ in &self.enums {
+            statements.push(format!("{};", enum_.to_sql(dialect)));
+        }
+
+        for composite in &self.composites {
+            statements.push(format!("{};", composite.to_sql(dialect)));
+        }
+
+        for table in &self.tables {
+            statements.push(format!("{};", table.to_sql(dialect)));
+
+            for index in &table.indexes {
+                statements.push(format!("{};", index.definition));
+            }
+        }
+
+        if dialect.defers_foreign_keys() {
+            for table in &self.tables {
+                for constraint in &table.constraints {
+                    if constraint.kind == ConstraintKind::ForeignKey {
+                        statements.push(format!(
+                            "ALTER TABLE {} ADD {};",
+                            dialect.quote_ident(&table.name),
+                            constraint.to_sql(dialect)
+                        ));
+                    }
+                }
+            }
+        }
+
+        for view in &self.views {
+            statements.push(format!("{};", view.definition));
+        }
+
+        for (i, statement) in statements.iter().enumerate() {
+            if i > 0 {
+                f.write_str("\n")?;
+            }
+
+            writeln!(f, "{}", statement)?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,317 @@
+use std::borrow::Cow;
+
+use sha2::{Digest, Sha384};
+
+use crate::error::BoxDynError;
+use crate::migrate::SchemaDialect;
+
+/// A file of data to insert into the database, applied by a [`Seeder`](super::Seeder).
+#[derive(Debug, Clone)]
+pub struct Seed {
+    /// The file name of the seed, which identifies it in the `_sqlx_seeds` table.
+    pub name: Cow<'static, str>,
+    pub data: SeedData,
+    pub checksum: Cow<'static, [u8]>,
+}
+
+#[derive(Debug, Clone)]
+pub enum SeedData {
+    /// A script of SQL statements.
+    Sql(Cow<'static, str>),
+
+    /// Rows to insert into a table, read from a CSV or JSON file.
+    Rows {
+        table: String,
+        columns: Vec<String>,
+        rows: Vec<Vec<SeedValue>>,
+    },
+}
+
+/// A value to insert, rendered as a literal so the database can coerce it to the column type.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SeedValue {
+    Null,
+    Bool(bool),
+    Number(String),
+    Text(String),
+}
+
+#[derive(Debug, Clone)]
+pub struct AppliedSeed {
+    pub name: String,
+    pub checksum: Cow<'static, [u8]>,
+}
+
+impl Seed {
+    pub fn new(name: Cow<'static, str>, data: SeedData, checksum: Cow<'static, [u8]>) -> Self {
+        Seed {
+            name,
+            data,
+            checksum,
+        }
+    }
+
+    /// Parse the contents of a seed file by its extension: `.sql`, `.csv` or `.json`.
+    ///
+    /// CSV and JSON seeds insert into the table named by the file name, without any leading
+    /// `<NUMBER>_` used for ordering, e.g. `02_users.csv` inserts into `users`.
+    ///
+    /// A CSV file starts with a header row of column names and an empty unquoted field is
+    /// `NULL`. A JSON file holds an array of objects that map column names to values.
+    pub fn parse(name: &str, contents: String) -> Result<Self, BoxDynError> {
+        let checksum = Cow::Owned(Vec::from(Sha384::digest(contents.as_bytes()).as_slice()));
+
+        let (stem, extension) = match name.rsplit_once('.') {
+            Some(parts) => parts,
+            None => return Err(format!("seed `{}` has no file extension", name).into()),
+        };
+
+        let table = match stem.split_once('_') {
+            Some((order, table)) if order.bytes().all(|b| b.is_ascii_digit()) => table,
+            _ => stem,
+        };
+
+        let data = match extension {
+            "sql" => SeedData::Sql(Cow::Owned(contents)),
+            "csv" => parse_csv(table, &contents)?,
+            "json" => parse_json(table, &contents)?,
+            _ => return Err(format!("seed `{}` is not a .sql, .csv or .json file", name).into()),
+        };
+
+        Ok(Seed::new(Cow::Owned(name.to_owned()), data, checksum))
+    }
+
+    /// The SQL that applies this seed in the given dialect.
+    pub fn sql(&self, dialect: SchemaDialect) -> Cow<'_, str> {
+        let (table, columns, rows) = match &self.data {
+            SeedData::Sql(sql) => return Cow::Borrowed(sql),
+            SeedData::Rows {
+                table,
+                columns,
+                rows,
+            } => (table, columns, rows),
+        };
+
+        let columns = columns
+            .iter()
+            .map(|column| dialect.quote_ident(column))
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        let mut sql = String::new();
+
+        for row in rows {
+            let values = row
+                .iter()
+                .map(|value| match value {
+                    SeedValue::Null => "NULL".to_owned(),
+                    SeedValue::Bool(true) => "TRUE".to_owned(),
+                    SeedValue::Bool(false) => "FALSE".to_owned(),
+                    SeedValue::Number(n) => n.clone(),
+                    SeedValue::Text(s) => format!("'{}'", s.replace('\'', "''")),
+                })
+                .collect::<Vec<_>>()
+                .join(", ");
+
+            sql.push_str(&format!(
+                "INSERT INTO {} ({}) VALUES ({});\n",
+                dialect.quote_ident(table),
+                columns,
+                values
+            ));
+        }
+
+        Cow::Owned(sql)
+    }
+}
+
+fn parse_csv(table: &str, contents: &str) -> Result<SeedData, BoxDynError> {
+    let mut records = Vec::new();
+    let mut record = Vec::new();
+    let mut field = String::new();
+    // an empty field is `NULL` unless it was quoted
+    let mut quoted = false;
+    let mut in_quotes = false;
+    let mut chars = contents.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if in_quotes {
+            match c {
+                '"' if chars.peek() == Some(&'"') => {
+                    chars.next();
+                    field.push('"');
+                }
+                '"' => in_quotes = false,
+                c => field.push(c),
+            }
+
+            continue;
+        }
+
+        match c {
+            '"' if field.is_empty() => {
+                in_quotes = true;
+                quoted = true;
+            }
+            ',' => record.push(csv_value(&mut field, &mut quoted)),
+            '\r' if chars.peek() == Some(&'\n') => {}
+            '\n' => {
+                // skip blank lines
+                if !record.is_empty() || !field.is_empty() || quoted {
+                    record.push(csv_value(&mut field, &mut quoted));
+                    records.push(std::mem::take(&mut record));
+                }
+            }
+            c => field.push(c),
+        }
+    }
+
+    if in_quotes {
+        return Err(format!("unterminated quoted field in seed for `{}`", table).into());
+    }
+
+    if !record.is_empty() || !field.is_empty() || quoted {
+        record.push(csv_value(&mut field, &mut quoted));
+        records.push(record);
+    }
+
+    let mut records = records.into_iter();
+
+    let columns = match records.next() {
+        Some(header) => header
+            .into_iter()
+            .map(|column| match column {
+                SeedValue::Text(name) => Ok(name),
+                _ => Err(format!("empty column name in seed for `{}`", table)),
+            })
+            .collect::<Result<Vec<_>, _>>()?,
+        None => return Err(format!("seed for `{}` has no header row", table).into()),
+    };
+
+    let rows: Vec<_> = records.collect();
+
+    for (i, row) in rows.iter().enumerate() {
+        if row.len() != columns.len() {
+            return Err(format!(
+                "row {} of seed for `{}` has {} fields but there are {} columns",
+                i + 1,
+                table,
+                row.len(),
+                columns.len()
+            )
+            .into());
+        }
+    }
+
+    Ok(SeedData::Rows {
+        table: table.to_owned(),
+        columns,
+        rows,
+    })
+}
+
+fn csv_value(field: &mut String, quoted: &mut bool) -> SeedValue {
+    let value = if field.is_empty() && !*quoted {
+        SeedValue::Null
+    } else {
+        SeedValue::Text(std::mem::take(field))
+    };
+
+    *quoted = false;
+    value
+}
+
+#[cfg(feature = "json")]
+fn parse_json(table: &str, contents: &str) -> Result<SeedData, BoxDynError> {
+    use serde_json::Value;
+
+    let objects: Vec<serde_json::Map<String, Value>> = serde_json::from_str(contents)?;
+
+    // the columns of the first object fix the order; every object must have the same keys
+    let columns: Vec<String> = match objects.first() {
+        Some(object) => object.keys().cloned().collect(),
+        None => Vec::new(),
+    };
+
+    let mut rows = Vec::new();
+
+    for (i, object) in objects.into_iter().enumerate() {
+        if object.len() != columns.len() || columns.iter().any(|c| !object.contains_key(c)) {
+            return Err(format!(
+                "object {} of seed for `{}` does not have the same keys as the first",
+                i + 1,
+                table
+            )
+            .into());
+        }
+
+        let row = columns
+            .iter()
+            .map(|column| match &object[column] {
+                Value::Null => SeedValue::Null,
+                Value::Bool(b) => SeedValue::Bool(*b),
+                Value::Number(n) => SeedValue::Number(n.to_string()),
+                Value::String(s) => SeedValue::Text(s.clone()),
+                // nested values are inserted as JSON text
+                value => SeedValue::Text(value.to_string()),
+            })
+            .collect();
+
+        rows.push(row);
+    }
+
+    Ok(SeedData::Rows {
+        table: table.to_owned(),
+        columns,
+        rows,
+    })
+}
+
+#[cfg(not(feature = "json"))]
+fn parse_json(table: &str, _contents: &str) -> Result<SeedData, BoxDynError> {
+    Err(format!(
+        "seed for `{}` is a JSON file, which requires the `json` feature",
+        table
+    )
+    .into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_parses_csv_seeds() -> Result<(), BoxDynError> {
+        let seed = Seed::parse(
+            "02_users.csv",
+            "id,name,bio\r\n1,\"O'Brien, Pat\",\n2,\"say \"\"hi\"\"\",\"\"\n".into(),
+        )?;
+
+        assert_eq!(
+            seed.sql(SchemaDialect::Postgres),
+            r#"INSERT INTO "users" ("id", "name", "bio") VALUES ('1', 'O''Brien, Pat', NULL);
+INSERT INTO "users" ("id", "name", "bio") VALUES ('2', 'say "hi"', '');
+"#
+        );
+
+        assert!(Seed::parse("users.csv", "id,name\n1\n".into()).is_err());
+
+        Ok(())
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn it_parses_json_seeds() -> Result<(), BoxDynError> {
+        let seed = Seed::parse(
+            "users.json",
+            r#"[{"admin": true, "bio": null, "id": 1, "tags": ["a"]}]"#.into(),
+        )?;
+
+        assert_eq!(
+            seed.sql(SchemaDialect::MySql),
+            "INSERT INTO `users` (`admin`, `bio`, `id`, `tags`) VALUES (TRUE, NULL, 1, '[\"a\"]');\n"
+        );
+
+        Ok(())
+    }
+}
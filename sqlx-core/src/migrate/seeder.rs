@@ -0,0 +1,131 @@
+use crate::acquire::Acquire;
+use crate::error::BoxDynError;
+use crate::migrate::{Migrate, MigrateError, Seed};
+use futures_util::TryStreamExt;
+use sqlx_rt::fs;
+use std::collections::HashMap;
+use std::ops::Deref;
+use std::path::Path;
+use std::slice;
+
+/// Applies the seed files of a directory, tracking them in the `_sqlx_seeds` table.
+///
+/// Seeds are applied in order of their file names, and each seed is applied once unless
+/// [`set_rerun`](Self::set_rerun) is used. Unlike migrations, seeds are not required to exist in
+/// every environment, so a directory per environment (e.g. `seeds/dev`) can be used.
+#[derive(Debug)]
+pub struct Seeder {
+    pub seeds: Vec<Seed>,
+    pub rerun: bool,
+}
+
+impl Seeder {
+    /// Creates a new instance with the seeds of the given directory.
+    ///
+    /// Every file with a `.sql`, `.csv` or `.json` extension is a seed; see [`Seed::parse`] for
+    /// their format. Other files are ignored.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use sqlx_core::migrate::MigrateError;
+    /// # fn main() -> Result<(), MigrateError> {
+    /// # sqlx_rt::block_on(async move {
+    /// # use sqlx_core::migrate::Seeder;
+    /// use std::path::Path;
+    ///
+    /// // Read seeds from a local folder: ./seeds
+    /// let s = Seeder::new(Path::new("./seeds")).await?;
+    /// # Ok(())
+    /// # })
+    /// # }
+    /// ```
+    pub async fn new(path: &Path) -> Result<Self, MigrateError> {
+        Ok(Self {
+            seeds: resolve(path).await.map_err(MigrateError::Source)?,
+            rerun: false,
+        })
+    }
+
+    /// Specify whether seeds that were already applied should be applied again.
+    pub fn set_rerun(&mut self, rerun: bool) -> &Self {
+        self.rerun = rerun;
+        self
+    }
+
+    /// Get an iterator over all known seeds.
+    pub fn iter(&self) -> slice::Iter<'_, Seed> {
+        self.seeds.iter()
+    }
+
+    /// Apply any seeds that were not applied yet, or all of them if `rerun` is set.
+    ///
+    /// Fails if a seed was modified since it was applied, unless `rerun` is set.
+    pub async fn run<'a, A>(&self, seeder: A) -> Result<(), MigrateError>
+    where
+        A: Acquire<'a>,
+        <A::Connection as Deref>::Target: Migrate,
+    {
+        let mut conn = seeder.acquire().await?;
+
+        conn.ensure_seeds_table().await?;
+
+        let applied_seeds: HashMap<_, _> = conn
+            .list_applied_seeds()
+            .await?
+            .into_iter()
+            .map(|s| (s.name.clone(), s))
+            .collect();
+
+        for seed in self.iter() {
+            if let Some(applied_seed) = applied_seeds.get(&*seed.name) {
+                if !self.rerun {
+                    if seed.checksum != applied_seed.checksum {
+                        return Err(MigrateError::SeedMismatch(seed.name.to_string()));
+                    }
+
+                    continue;
+                }
+            }
+
+            conn.apply_seed(seed).await?;
+        }
+
+        Ok(())
+    }
+}
+
+async fn resolve(path: &Path) -> Result<Vec<Seed>, BoxDynError> {
+    #[allow(unused_mut)]
+    let mut s = fs::read_dir(path.canonicalize()?).await?;
+    let mut seeds = Vec::new();
+
+    #[cfg(any(feature = "_rt-actix", feature = "_rt-tokio"))]
+    let mut s = tokio_stream::wrappers::ReadDirStream::new(s);
+
+    while let Some(entry) = s.try_next().await? {
+        if !entry.metadata().await?.is_file() {
+            // not a file; ignore
+            continue;
+        }
+
+        let file_name = entry.file_name();
+        let file_name = file_name.to_string_lossy();
+
+        if ![".sql", ".csv", ".json"]
+            .iter()
+            .any(|extension| file_name.ends_with(extension))
+        {
+            continue;
+        }
+
+        let contents = fs::read_to_string(&entry.path()).await?;
+
+        seeds.push(Seed::parse(&file_name, contents)?);
+    }
+
+    // ensure that we are sorted by name so the order does not depend on the file system
+    seeds.sort_by(|a, b| a.name.cmp(&b.name));
+
+    Ok(seeds)
+}
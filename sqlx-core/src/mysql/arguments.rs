@@ -1,7 +1,8 @@
 use crate::arguments::Arguments;
 use crate::encode::{Encode, IsNull};
 use crate::mysql::{MySql, MySqlTypeInfo};
-use crate::types::Type;
+use crate::types::{expand_in_lists, Placeholders, Type};
+use std::borrow::Cow;
 
 /// Implementation of [`Arguments`] for MySQL.
 #[derive(Debug, Default)]
@@ -9,6 +10,8 @@ pub struct MySqlArguments {
     pub(crate) values: Vec<u8>,
     pub(crate) types: Vec<MySqlTypeInfo>,
     pub(crate) null_bitmap: Vec<u8>,
+    // the index of the first value of each `InList`, with its number of values
+    pub(crate) lists: Vec<(usize, usize)>,
 }
 
 impl MySqlArguments {
@@ -16,16 +19,37 @@ impl MySqlArguments {
     where
         T: Encode<'q, MySql> + Type<MySql>,
     {
+        if let Some(values) = value.list_values() {
+            self.lists.push((self.types.len(), values.len()));
+
+            for (value, ty) in values {
+                self.add_value(value, ty);
+            }
+
+            return;
+        }
+
         let ty = value.produces().unwrap_or_else(T::type_info);
+
+        self.add_value(&value, ty);
+    }
+
+    fn add_value<'q>(&mut self, value: &dyn Encode<'q, MySql>, ty: MySqlTypeInfo) {
         let index = self.types.len();
 
         self.types.push(ty);
         self.null_bitmap.resize((index / 8) + 1, 0);
 
-        if let IsNull::Yes = value.encode(&mut self.values) {
+        if let IsNull::Yes = value.encode_by_ref(&mut self.values) {
             self.null_bitmap[index / 8] |= (1 << (index % 8)) as u8;
         }
     }
+
+    /// Replace the placeholder of each [`InList`](crate::types::InList) in `sql` with one
+    /// placeholder per value.
+    pub(crate) fn expand_in_lists<'s>(&self, sql: &'s str) -> Cow<'s, str> {
+        expand_in_lists(sql, &self.lists, Placeholders::Question)
+    }
 }
 
 impl<'q> Arguments<'q> for MySqlArguments {
@@ -12,6 +12,9 @@ pub struct MySqlColumn {
 
     #[cfg_attr(feature = "offline", serde(skip))]
     pub(crate) flags: Option<ColumnFlags>,
+
+    #[cfg_attr(feature = "offline", serde(default))]
+    pub(crate) origin_table: Option<UStr>,
 }
 
 impl crate::column::private_column::Sealed for MySqlColumn {}
@@ -30,6 +33,10 @@ impl Column for MySqlColumn {
     fn type_info(&self) -> &MySqlTypeInfo {
         &self.type_info
     }
+
+    fn origin_table(&self) -> Option<&str> {
+        self.origin_table.as_deref()
+    }
 }
 
 #[cfg(feature = "any")]
@@ -223,8 +223,13 @@ impl<'c> Executor<'c> for &'c mut MySqlConnection {
         let arguments = query.take_arguments();
         let persistent = query.persistent();
 
+        let sql = match &arguments {
+            Some(arguments) => arguments.expand_in_lists(sql),
+            None => Cow::Borrowed(sql),
+        };
+
         Box::pin(try_stream! {
-            let s = self.run(sql, arguments, persistent).await?;
+            let s = self.run(&sql, arguments, persistent).await?;
             pin_mut!(s);
 
             while let Some(v) = s.try_next().await? {
@@ -335,11 +340,17 @@ fn recv_next_result_column(def: &ColumnDefinition, ordinal: usize) -> Result<MyS
 
     let type_info = MySqlTypeInfo::from_column(&def);
 
+    // the table of an expression is empty
+    let origin_table = Some(def.table()?)
+        .filter(|table| !table.is_empty())
+        .map(UStr::new);
+
     Ok(MySqlColumn {
         name,
         type_info,
         ordinal,
         flags: Some(def.flags),
+        origin_table,
     })
 }
 
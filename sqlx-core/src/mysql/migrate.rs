@@ -1,14 +1,19 @@
-use crate::connection::ConnectOptions;
+use crate::connection::{ConnectOptions, Connection};
 use crate::error::Error;
 use crate::executor::Executor;
-use crate::migrate::MigrateError;
-use crate::migrate::{AppliedMigration, Migration};
+use crate::migrate::{AppliedMigration, AppliedSeed, Migration, Seed};
+use crate::migrate::{
+    Constraint, ConstraintKind, Index, Schema, SchemaDialect, Table, TableColumn, View,
+};
 use crate::migrate::{Migrate, MigrateDatabase};
+use crate::migrate::{MigrateError, DEFAULT_TABLE_NAME};
 use crate::mysql::{MySql, MySqlConnectOptions, MySqlConnection};
 use crate::query::query;
 use crate::query_as::query_as;
 use crate::query_scalar::query_scalar;
+use crate::testing::{database_url, replace_database, TestArgs, TestSupport};
 use futures_core::future::BoxFuture;
+use std::collections::BTreeMap;
 use std::str::FromStr;
 use std::time::Duration;
 use std::time::Instant;
@@ -74,13 +79,26 @@ impl MigrateDatabase for MySql {
     }
 }
 
+impl TestSupport for MySql {
+    fn test_database_url(args: &TestArgs) -> Result<String, Error> {
+        replace_database(&database_url(args)?, &args.database_name())
+    }
+}
+
 impl Migrate for MySqlConnection {
     fn ensure_migrations_table(&mut self) -> BoxFuture<'_, Result<(), MigrateError>> {
+        self.ensure_migrations_table_in(DEFAULT_TABLE_NAME)
+    }
+
+    fn ensure_migrations_table_in<'e>(
+        &'e mut self,
+        table_name: &'e str,
+    ) -> BoxFuture<'e, Result<(), MigrateError>> {
         Box::pin(async move {
             // language=MySQL
-            self.execute(
+            self.execute(&*format!(
                 r#"
-CREATE TABLE IF NOT EXISTS _sqlx_migrations (
+CREATE TABLE IF NOT EXISTS {} (
     version BIGINT PRIMARY KEY,
     description TEXT NOT NULL,
     installed_on TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
@@ -89,7 +107,8 @@ CREATE TABLE IF NOT EXISTS _sqlx_migrations (
     execution_time BIGINT NOT NULL
 );
                 "#,
-            )
+                SchemaDialect::MySql.quote_ident(table_name)
+            ))
             .await?;
 
             Ok(())
@@ -97,11 +116,20 @@ CREATE TABLE IF NOT EXISTS _sqlx_migrations (
     }
 
     fn version(&mut self) -> BoxFuture<'_, Result<Option<(i64, bool)>, MigrateError>> {
+        #[allow(deprecated)]
+        self.version_in(DEFAULT_TABLE_NAME)
+    }
+
+    fn version_in<'e>(
+        &'e mut self,
+        table_name: &'e str,
+    ) -> BoxFuture<'e, Result<Option<(i64, bool)>, MigrateError>> {
         Box::pin(async move {
             // language=SQL
-            let row = query_as(
-                "SELECT version, NOT success FROM _sqlx_migrations ORDER BY version DESC LIMIT 1",
-            )
+            let row = query_as(&format!(
+                "SELECT version, NOT success FROM {} ORDER BY version DESC LIMIT 1",
+                SchemaDialect::MySql.quote_ident(table_name)
+            ))
             .fetch_optional(self)
             .await?;
 
@@ -110,11 +138,19 @@ CREATE TABLE IF NOT EXISTS _sqlx_migrations (
     }
 
     fn dirty_version(&mut self) -> BoxFuture<'_, Result<Option<i64>, MigrateError>> {
+        self.dirty_version_in(DEFAULT_TABLE_NAME)
+    }
+
+    fn dirty_version_in<'e>(
+        &'e mut self,
+        table_name: &'e str,
+    ) -> BoxFuture<'e, Result<Option<i64>, MigrateError>> {
         Box::pin(async move {
             // language=SQL
-            let row: Option<(i64,)> = query_as(
-                "SELECT version FROM _sqlx_migrations WHERE success = false ORDER BY version LIMIT 1",
-            )
+            let row: Option<(i64,)> = query_as(&format!(
+                "SELECT version FROM {} WHERE success = false ORDER BY version LIMIT 1",
+                SchemaDialect::MySql.quote_ident(table_name)
+            ))
             .fetch_optional(self)
             .await?;
 
@@ -125,12 +161,21 @@ CREATE TABLE IF NOT EXISTS _sqlx_migrations (
     fn list_applied_migrations(
         &mut self,
     ) -> BoxFuture<'_, Result<Vec<AppliedMigration>, MigrateError>> {
+        self.list_applied_migrations_in(DEFAULT_TABLE_NAME)
+    }
+
+    fn list_applied_migrations_in<'e>(
+        &'e mut self,
+        table_name: &'e str,
+    ) -> BoxFuture<'e, Result<Vec<AppliedMigration>, MigrateError>> {
         Box::pin(async move {
             // language=SQL
-            let rows: Vec<(i64, Vec<u8>)> =
-                query_as("SELECT version, checksum FROM _sqlx_migrations ORDER BY version")
-                    .fetch_all(self)
-                    .await?;
+            let rows: Vec<(i64, Vec<u8>)> = query_as(&format!(
+                "SELECT version, checksum FROM {} ORDER BY version",
+                SchemaDialect::MySql.quote_ident(table_name)
+            ))
+            .fetch_all(self)
+            .await?;
 
             let migrations = rows
                 .into_iter()
@@ -183,14 +228,25 @@ CREATE TABLE IF NOT EXISTS _sqlx_migrations (
     fn validate<'e: 'm, 'm>(
         &'e mut self,
         migration: &'m Migration,
+    ) -> BoxFuture<'m, Result<(), MigrateError>> {
+        #[allow(deprecated)]
+        self.validate_in(DEFAULT_TABLE_NAME, migration)
+    }
+
+    fn validate_in<'e: 'm, 'm>(
+        &'e mut self,
+        table_name: &'m str,
+        migration: &'m Migration,
     ) -> BoxFuture<'m, Result<(), MigrateError>> {
         Box::pin(async move {
             // language=SQL
-            let checksum: Option<Vec<u8>> =
-                query_scalar("SELECT checksum FROM _sqlx_migrations WHERE version = ?")
-                    .bind(migration.version)
-                    .fetch_optional(self)
-                    .await?;
+            let checksum: Option<Vec<u8>> = query_scalar(&format!(
+                "SELECT checksum FROM {} WHERE version = ?",
+                SchemaDialect::MySql.quote_ident(table_name)
+            ))
+            .bind(migration.version)
+            .fetch_optional(self)
+            .await?;
 
             if let Some(checksum) = checksum {
                 return if checksum == &*migration.checksum {
@@ -207,6 +263,14 @@ CREATE TABLE IF NOT EXISTS _sqlx_migrations (
     fn apply<'e: 'm, 'm>(
         &'e mut self,
         migration: &'m Migration,
+    ) -> BoxFuture<'m, Result<Duration, MigrateError>> {
+        self.apply_in(DEFAULT_TABLE_NAME, migration)
+    }
+
+    fn apply_in<'e: 'm, 'm>(
+        &'e mut self,
+        table_name: &'m str,
+        migration: &'m Migration,
     ) -> BoxFuture<'m, Result<Duration, MigrateError>> {
         Box::pin(async move {
             let start = Instant::now();
@@ -216,12 +280,13 @@ CREATE TABLE IF NOT EXISTS _sqlx_migrations (
             let elapsed = start.elapsed();
 
             // language=MySQL
-            let _ = query(
+            let _ = query(&format!(
                 r#"
-    INSERT INTO _sqlx_migrations ( version, description, success, checksum, execution_time )
+    INSERT INTO {} ( version, description, success, checksum, execution_time )
     VALUES ( ?, ?, ?, ?, ? )
                 "#,
-            )
+                SchemaDialect::MySql.quote_ident(table_name)
+            ))
             .bind(migration.version)
             .bind(&*migration.description)
             .bind(res.is_ok())
@@ -239,6 +304,14 @@ CREATE TABLE IF NOT EXISTS _sqlx_migrations (
     fn revert<'e: 'm, 'm>(
         &'e mut self,
         migration: &'m Migration,
+    ) -> BoxFuture<'m, Result<Duration, MigrateError>> {
+        self.revert_in(DEFAULT_TABLE_NAME, migration)
+    }
+
+    fn revert_in<'e: 'm, 'm>(
+        &'e mut self,
+        table_name: &'m str,
+        migration: &'m Migration,
     ) -> BoxFuture<'m, Result<Duration, MigrateError>> {
         Box::pin(async move {
             let start = Instant::now();
@@ -248,14 +321,465 @@ CREATE TABLE IF NOT EXISTS _sqlx_migrations (
             let elapsed = start.elapsed();
 
             // language=SQL
-            let _ = query(r#"DELETE FROM _sqlx_migrations WHERE version = ?"#)
-                .bind(migration.version)
-                .execute(self)
-                .await?;
+            let _ = query(&format!(
+                r#"DELETE FROM {} WHERE version = ?"#,
+                SchemaDialect::MySql.quote_ident(table_name)
+            ))
+            .bind(migration.version)
+            .execute(self)
+            .await?;
 
             Ok(elapsed)
         })
     }
+
+    fn apply_baseline<'e: 'm, 'm>(
+        &'e mut self,
+        table_name: &'m str,
+        migration: &'m Migration,
+    ) -> BoxFuture<'m, Result<(), MigrateError>> {
+        Box::pin(async move {
+            let mut tx = self.begin().await?;
+
+            // language=SQL
+            let _ = query(&format!(
+                r#"DELETE FROM {} WHERE version <= ?"#,
+                SchemaDialect::MySql.quote_ident(table_name)
+            ))
+            .bind(migration.version)
+            .execute(&mut tx)
+            .await?;
+
+            // language=SQL
+            let _ = query(&format!(
+                r#"
+    INSERT INTO {} ( version, description, success, checksum, execution_time )
+    VALUES ( ?, ?, TRUE, ?, 0 )
+                "#,
+                SchemaDialect::MySql.quote_ident(table_name)
+            ))
+            .bind(migration.version)
+            .bind(&*migration.description)
+            .bind(&*migration.checksum)
+            .execute(&mut tx)
+            .await?;
+
+            tx.commit().await?;
+
+            Ok(())
+        })
+    }
+
+    fn ensure_seeds_table(&mut self) -> BoxFuture<'_, Result<(), MigrateError>> {
+        Box::pin(async move {
+            // language=MySQL
+            self.execute(
+                r#"
+CREATE TABLE IF NOT EXISTS _sqlx_seeds (
+    name VARCHAR(255) PRIMARY KEY,
+    applied_on TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    checksum BLOB NOT NULL
+);
+                "#,
+            )
+            .await?;
+
+            Ok(())
+        })
+    }
+
+    fn list_applied_seeds(&mut self) -> BoxFuture<'_, Result<Vec<AppliedSeed>, MigrateError>> {
+        Box::pin(async move {
+            // language=SQL
+            let rows: Vec<(String, Vec<u8>)> =
+                query_as("SELECT name, checksum FROM _sqlx_seeds ORDER BY name")
+                    .fetch_all(self)
+                    .await?;
+
+            let seeds = rows
+                .into_iter()
+                .map(|(name, checksum)| AppliedSeed {
+                    name,
+                    checksum: checksum.into(),
+                })
+                .collect();
+
+            Ok(seeds)
+        })
+    }
+
+    fn apply_seed<'e: 'm, 'm>(
+        &'e mut self,
+        seed: &'m Seed,
+    ) -> BoxFuture<'m, Result<Duration, MigrateError>> {
+        Box::pin(async move {
+            let mut tx = self.begin().await?;
+            let start = Instant::now();
+
+            let _ = tx.execute(&*seed.sql(SchemaDialect::MySql)).await?;
+
+            // language=SQL
+            let _ = query(r#"DELETE FROM _sqlx_seeds WHERE name = ?"#)
+                .bind(&*seed.name)
+                .execute(&mut tx)
+                .await?;
+
+            // language=SQL
+            let _ = query(r#"INSERT INTO _sqlx_seeds ( name, checksum ) VALUES ( ?, ? )"#)
+                .bind(&*seed.name)
+                .bind(&*seed.checksum)
+                .execute(&mut tx)
+                .await?;
+
+            tx.commit().await?;
+
+            Ok(start.elapsed())
+        })
+    }
+
+    fn schema<'e>(
+        &'e mut self,
+        migrations_table: &'e str,
+    ) -> BoxFuture<'e, Result<Schema, MigrateError>> {
+        Box::pin(async move {
+            let dialect = SchemaDialect::MySql;
+            let mut schema = Schema::new(dialect);
+
+            // language=MySQL
+            let table_names: Vec<String> = query_scalar(
+                r#"
+SELECT table_name
+FROM information_schema.tables
+WHERE table_schema = DATABASE()
+  AND table_type = 'BASE TABLE'
+  AND table_name NOT IN (?, '_sqlx_seeds')
+ORDER BY table_name
+                "#,
+            )
+            .bind(migrations_table)
+            .fetch_all(&mut *self)
+            .await?;
+
+            let mut tables: BTreeMap<String, Table> = table_names
+                .into_iter()
+                .map(|name| {
+                    let table = Table {
+                        name: name.clone(),
+                        columns: Vec::new(),
+                        constraints: Vec::new(),
+                        indexes: Vec::new(),
+                    };
+
+                    (name, table)
+                })
+                .collect();
+
+            // language=MySQL
+            let columns: Vec<(
+                String,
+                String,
+                String,
+                String,
+                Option<String>,
+                String,
+                Option<String>,
+            )> = query_as(
+                r#"
+SELECT table_name, column_name, column_type, is_nullable, column_default, extra, generation_expression
+FROM information_schema.columns
+WHERE table_schema = DATABASE()
+ORDER BY table_name, ordinal_position
+                "#,
+            )
+            .fetch_all(&mut *self)
+            .await?;
+
+            for (table, name, data_type, nullable, default, extra, generated) in columns {
+                let table = match tables.get_mut(&table) {
+                    Some(table) => table,
+                    // a view or the migrations table
+                    None => continue,
+                };
+
+                let generated = generated.filter(|expr| !expr.is_empty());
+
+                let mut attributes = Vec::new();
+
+                if let Some(expr) = &generated {
+                    let storage = if extra.contains("STORED") {
+                        "STORED"
+                    } else {
+                        "VIRTUAL"
+                    };
+
+                    attributes.push(format!("GENERATED ALWAYS AS ({}) {}", expr, storage));
+                }
+
+                let lower_extra = extra.to_ascii_lowercase();
+
+                if lower_extra.contains("auto_increment") {
+                    attributes.push("AUTO_INCREMENT".into());
+                }
+
+                if let Some(i) = lower_extra.find("on update ") {
+                    attributes.push(format!("ON UPDATE {}", &extra[i + "on update ".len()..]));
+                }
+
+                let default = if generated.is_some() {
+                    None
+                } else {
+                    default.and_then(|default| quote_default(default, &data_type, &extra))
+                };
+
+                table.columns.push(TableColumn {
+                    name,
+                    data_type,
+                    nullable: nullable == "YES",
+                    default,
+                    extra: if attributes.is_empty() {
+                        None
+                    } else {
+                        Some(attributes.join(" "))
+                    },
+                });
+            }
+
+            // language=MySQL
+            let key_columns: Vec<(
+                String,
+                String,
+                String,
+                String,
+                Option<String>,
+                Option<String>,
+                Option<String>,
+                Option<String>,
+            )> = query_as(
+                r#"
+SELECT tc.table_name,
+       tc.constraint_name,
+       tc.constraint_type,
+       kcu.column_name,
+       kcu.referenced_table_name,
+       kcu.referenced_column_name,
+       rc.update_rule,
+       rc.delete_rule
+FROM information_schema.table_constraints tc
+JOIN information_schema.key_column_usage kcu
+  ON kcu.constraint_schema = tc.constraint_schema
+  AND kcu.table_name = tc.table_name
+  AND kcu.constraint_name = tc.constraint_name
+LEFT JOIN information_schema.referential_constraints rc
+  ON rc.constraint_schema = tc.constraint_schema
+  AND rc.table_name = tc.table_name
+  AND rc.constraint_name = tc.constraint_name
+WHERE tc.table_schema = DATABASE()
+  AND tc.constraint_type IN ('PRIMARY KEY', 'UNIQUE', 'FOREIGN KEY')
+ORDER BY tc.table_name, tc.constraint_name, kcu.ordinal_position
+                "#,
+            )
+            .fetch_all(&mut *self)
+            .await?;
+
+            // rows are grouped by constraint; collect the columns of each one
+            struct KeyConstraint {
+                table: String,
+                name: String,
+                kind: String,
+                columns: Vec<String>,
+                ref_table: Option<String>,
+                ref_columns: Vec<String>,
+                actions: String,
+            }
+
+            let mut grouped: Vec<KeyConstraint> = Vec::new();
+
+            for (table, name, kind, column, ref_table, ref_column, on_update, on_delete) in
+                key_columns
+            {
+                match grouped.last_mut() {
+                    Some(last) if last.table == table && last.name == name => {
+                        last.columns.push(column);
+                        last.ref_columns.extend(ref_column);
+                    }
+
+                    _ => {
+                        let mut actions = String::new();
+
+                        for (event, rule) in [("UPDATE", on_update), ("DELETE", on_delete)] {
+                            if let Some(rule) = rule.filter(|r| r != "NO ACTION" && r != "RESTRICT")
+                            {
+                                actions.push_str(&format!(" ON {} {}", event, rule));
+                            }
+                        }
+
+                        grouped.push(KeyConstraint {
+                            table,
+                            name,
+                            kind,
+                            columns: vec![column],
+                            ref_table,
+                            ref_columns: ref_column.into_iter().collect(),
+                            actions,
+                        });
+                    }
+                }
+            }
+
+            for constraint in grouped {
+                let table = match tables.get_mut(&constraint.table) {
+                    Some(table) => table,
+                    None => continue,
+                };
+
+                let columns = quote_idents(dialect, &constraint.columns);
+
+                let (kind, definition) = match (&*constraint.kind, &constraint.ref_table) {
+                    ("PRIMARY KEY", _) => (
+                        ConstraintKind::PrimaryKey,
+                        format!("PRIMARY KEY ({})", columns),
+                    ),
+
+                    ("UNIQUE", _) => (ConstraintKind::Unique, format!("UNIQUE ({})", columns)),
+
+                    (_, Some(ref_table)) => (
+                        ConstraintKind::ForeignKey,
+                        format!(
+                            "FOREIGN KEY ({}) REFERENCES {} ({}){}",
+                            columns,
+                            dialect.quote_ident(ref_table),
+                            quote_idents(dialect, &constraint.ref_columns),
+                            constraint.actions
+                        ),
+                    ),
+
+                    _ => continue,
+                };
+
+                table.constraints.push(Constraint {
+                    name: constraint.name,
+                    kind,
+                    definition,
+                });
+            }
+
+            // unique indexes are covered by the `UNIQUE` constraints above
+            // language=MySQL
+            let index_columns: Vec<(String, String, String, Option<i64>)> = query_as(
+                r#"
+SELECT table_name, index_name, column_name, CAST(sub_part AS SIGNED)
+FROM information_schema.statistics
+WHERE table_schema = DATABASE()
+  AND non_unique = 1
+  AND column_name IS NOT NULL
+ORDER BY table_name, index_name, seq_in_index
+                "#,
+            )
+            .fetch_all(&mut *self)
+            .await?;
+
+            let mut grouped: Vec<(String, String, Vec<String>)> = Vec::new();
+
+            for (table, name, column, sub_part) in index_columns {
+                let column = match sub_part {
+                    Some(len) => format!("{}({})", dialect.quote_ident(&column), len),
+                    None => dialect.quote_ident(&column),
+                };
+
+                match grouped.last_mut() {
+                    Some(last) if last.0 == table && last.1 == name => last.2.push(column),
+                    _ => grouped.push((table, name, vec![column])),
+                }
+            }
+
+            for (table_name, name, columns) in grouped {
+                if let Some(table) = tables.get_mut(&table_name) {
+                    table.indexes.push(Index {
+                        definition: format!(
+                            "CREATE INDEX {} ON {} ({})",
+                            dialect.quote_ident(&name),
+                            dialect.quote_ident(&table_name),
+                            columns.join(", ")
+                        ),
+                        name,
+                    });
+                }
+            }
+
+            schema.tables = tables.into_values().collect();
+
+            // view definitions qualify every table with the database name, which we strip
+            // so the output does not depend on what the database is called
+            let database = current_database(&mut *self).await?;
+            let qualifier = format!("{}.", dialect.quote_ident(&database));
+
+            // language=MySQL
+            let views: Vec<(String, String)> = query_as(
+                r#"
+SELECT table_name, view_definition
+FROM information_schema.views
+WHERE table_schema = DATABASE()
+ORDER BY table_name
+                "#,
+            )
+            .fetch_all(&mut *self)
+            .await?;
+
+            schema.views = views
+                .into_iter()
+                .map(|(name, definition)| View {
+                    definition: format!(
+                        "CREATE VIEW {} AS {}",
+                        dialect.quote_ident(&name),
+                        definition.replace(&qualifier, "")
+                    ),
+                    name,
+                })
+                .collect();
+
+            Ok(schema)
+        })
+    }
+}
+
+fn quote_idents(dialect: SchemaDialect, idents: &[String]) -> String {
+    idents
+        .iter()
+        .map(|ident| dialect.quote_ident(ident))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+// `information_schema.columns.column_default` holds literals unquoted on MySQL 8
+// and quoted on MariaDB; expressions are flagged by `DEFAULT_GENERATED` in `extra` on MySQL 8
+fn quote_default(default: String, data_type: &str, extra: &str) -> Option<String> {
+    let upper = default.to_ascii_uppercase();
+
+    const NUMERIC_TYPES: &[&str] = &[
+        "tinyint",
+        "smallint",
+        "mediumint",
+        "int",
+        "bigint",
+        "decimal",
+        "float",
+        "double",
+        "bit",
+        "year",
+    ];
+
+    if upper == "NULL" {
+        None
+    } else if default.starts_with('\'')
+        || upper.starts_with("CURRENT_TIMESTAMP")
+        || NUMERIC_TYPES.iter().any(|ty| data_type.starts_with(ty))
+    {
+        Some(default)
+    } else if extra.contains("DEFAULT_GENERATED") {
+        Some(format!("({})", default))
+    } else {
+        Some(format!("'{}'", default.replace('\'', "''")))
+    }
 }
 
 async fn current_database(conn: &mut MySqlConnection) -> Result<String, MigrateError> {
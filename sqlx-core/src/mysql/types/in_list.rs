@@ -0,0 +1,41 @@
+use crate::encode::{Encode, IsNull};
+use crate::mysql::{MySql, MySqlTypeInfo};
+use crate::types::{InList, Type};
+
+impl<T: Type<MySql>> Type<MySql> for InList<T> {
+    fn type_info() -> MySqlTypeInfo {
+        T::type_info()
+    }
+
+    fn compatible(ty: &MySqlTypeInfo) -> bool {
+        T::compatible(ty)
+    }
+}
+
+impl<'q, T> Encode<'q, MySql> for InList<T>
+where
+    T: Encode<'q, MySql> + Type<MySql>,
+{
+    fn encode_by_ref(&self, _buf: &mut Vec<u8>) -> IsNull {
+        // `MySqlArguments` binds the values of a list one by one instead; this is only reached
+        // through a wrapper that does not forward `list_values`, so its placeholder can't be
+        // expanded and the list is bound as `NULL`
+        IsNull::Yes
+    }
+
+    fn list_values(&self) -> Option<Vec<(&dyn Encode<'q, MySql>, MySqlTypeInfo)>> {
+        Some(
+            self.0
+                .iter()
+                .map(|value| {
+                    let ty = value.produces().unwrap_or_else(T::type_info);
+                    (value as &dyn Encode<'q, MySql>, ty)
+                })
+                .collect(),
+        )
+    }
+
+    fn size_hint(&self) -> usize {
+        self.0.iter().map(Encode::size_hint).sum()
+    }
+}
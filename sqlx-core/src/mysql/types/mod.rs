@@ -72,6 +72,11 @@
 //! |---------------------------------------|------------------------------------------------------|
 //! | `json::JsonValue`             | JSON
 //!
+//! # Lists
+//!
+//! [`InList<T>`](crate::types::InList) binds a list of `T` as one parameter per value, for
+//! `IN (?)`.
+//!
 //! # Nullable
 //!
 //! In addition, `Option<T>` is supported where `T` implements `Type`. An `Option<T>` represents
@@ -81,6 +86,7 @@
 mod bool;
 mod bytes;
 mod float;
+mod in_list;
 mod int;
 mod str;
 mod uint;
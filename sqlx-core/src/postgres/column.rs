@@ -12,6 +12,8 @@ pub struct PgColumn {
     pub(crate) relation_id: Option<i32>,
     #[cfg_attr(feature = "offline", serde(skip))]
     pub(crate) relation_attribute_no: Option<i16>,
+    #[cfg_attr(feature = "offline", serde(default))]
+    pub(crate) origin_table: Option<UStr>,
 }
 
 impl crate::column::private_column::Sealed for PgColumn {}
@@ -30,6 +32,10 @@ impl Column for PgColumn {
     fn type_info(&self) -> &PgTypeInfo {
         &self.type_info
     }
+
+    fn origin_table(&self) -> Option<&str> {
+        self.origin_table.as_deref()
+    }
 }
 
 #[cfg(feature = "any")]
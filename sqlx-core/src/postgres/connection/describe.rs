@@ -121,6 +121,7 @@ impl PgConnection {
                 type_info,
                 relation_id: field.relation_id,
                 relation_attribute_no: field.relation_attribute_no,
+                origin_table: None,
             };
 
             columns.push(column);
@@ -414,6 +415,46 @@ SELECT oid FROM pg_catalog.pg_type WHERE typname ILIKE $1
         Ok(nullables)
     }
 
+    /// Find the names of the tables the columns of this statement are selected from.
+    pub(crate) async fn get_origin_tables_for_columns(
+        &mut self,
+        meta: &PgStatementMetadata,
+    ) -> Result<Vec<Option<String>>, Error> {
+        if meta
+            .columns
+            .iter()
+            .all(|column| column.relation_id.is_none())
+        {
+            return Ok(vec![None; meta.columns.len()]);
+        }
+
+        let mut tables_query = String::from("SELECT pg_class.relname::text FROM (VALUES ");
+        let mut args = PgArguments::default();
+
+        for (i, (column, bind)) in meta.columns.iter().zip((1..).step_by(2)).enumerate() {
+            if !args.buffer.is_empty() {
+                tables_query += ", ";
+            }
+
+            let _ = write!(tables_query, "(${}::int4, ${}::int4)", bind, bind + 1);
+
+            args.add(i as i32);
+            args.add(column.relation_id);
+        }
+
+        tables_query.push_str(
+            ") as col(idx, table_id) \
+            LEFT JOIN pg_catalog.pg_class \
+                ON table_id IS NOT NULL \
+               AND pg_class.oid = table_id \
+            ORDER BY col.idx",
+        );
+
+        query_scalar_with::<_, Option<String>, _>(&tables_query, args)
+            .fetch_all(&mut *self)
+            .await
+    }
+
     /// Infer nullability for columns of this statement using EXPLAIN VERBOSE.
     ///
     /// This currently only marks columns that are on the inner half of an outer join
@@ -1,6 +1,7 @@
 use crate::describe::Describe;
 use crate::error::Error;
 use crate::executor::{Execute, Executor};
+use crate::ext::ustr::UStr;
 use crate::logger::QueryLogger;
 use crate::postgres::message::{
     self, Bind, Close, CommandComplete, DataRow, MessageFormat, ParameterDescription, Parse, Query,
@@ -422,9 +423,16 @@ impl<'c> Executor<'c> for &'c mut PgConnection {
             let (stmt_id, metadata) = self.get_or_prepare(sql, &[], true, None).await?;
 
             let nullable = self.get_nullable_for_columns(stmt_id, &metadata).await?;
+            let origin_tables = self.get_origin_tables_for_columns(&metadata).await?;
+
+            let mut columns = metadata.columns.clone();
+
+            for (column, table) in columns.iter_mut().zip(origin_tables) {
+                column.origin_table = table.map(UStr::from);
+            }
 
             Ok(Describe {
-                columns: metadata.columns.clone(),
+                columns,
                 nullable,
                 parameters: Some(Either::Left(metadata.parameters.clone())),
             })
@@ -1,14 +1,20 @@
 use crate::connection::{ConnectOptions, Connection};
 use crate::error::Error;
 use crate::executor::Executor;
-use crate::migrate::MigrateError;
-use crate::migrate::{AppliedMigration, Migration};
+use crate::migrate::{AppliedMigration, AppliedSeed, Migration, Seed};
+use crate::migrate::{
+    Composite, Constraint, ConstraintKind, Enum, Index, Schema, SchemaDialect, Table, TableColumn,
+    View,
+};
 use crate::migrate::{Migrate, MigrateDatabase};
+use crate::migrate::{MigrateError, DEFAULT_TABLE_NAME};
 use crate::postgres::{PgConnectOptions, PgConnection, Postgres};
 use crate::query::query;
 use crate::query_as::query_as;
 use crate::query_scalar::query_scalar;
+use crate::testing::{database_url, replace_database, TestArgs, TestSupport};
 use futures_core::future::BoxFuture;
+use std::collections::BTreeMap;
 use std::str::FromStr;
 use std::time::Duration;
 use std::time::Instant;
@@ -84,13 +90,26 @@ impl MigrateDatabase for Postgres {
     }
 }
 
+impl TestSupport for Postgres {
+    fn test_database_url(args: &TestArgs) -> Result<String, Error> {
+        replace_database(&database_url(args)?, &args.database_name())
+    }
+}
+
 impl Migrate for PgConnection {
     fn ensure_migrations_table(&mut self) -> BoxFuture<'_, Result<(), MigrateError>> {
+        self.ensure_migrations_table_in(DEFAULT_TABLE_NAME)
+    }
+
+    fn ensure_migrations_table_in<'e>(
+        &'e mut self,
+        table_name: &'e str,
+    ) -> BoxFuture<'e, Result<(), MigrateError>> {
         Box::pin(async move {
             // language=SQL
-            self.execute(
+            self.execute(&*format!(
                 r#"
-CREATE TABLE IF NOT EXISTS _sqlx_migrations (
+CREATE TABLE IF NOT EXISTS {} (
     version BIGINT PRIMARY KEY,
     description TEXT NOT NULL,
     installed_on TIMESTAMPTZ NOT NULL DEFAULT now(),
@@ -99,7 +118,8 @@ CREATE TABLE IF NOT EXISTS _sqlx_migrations (
     execution_time BIGINT NOT NULL
 );
                 "#,
-            )
+                SchemaDialect::Postgres.quote_ident(table_name)
+            ))
             .await?;
 
             Ok(())
@@ -107,11 +127,20 @@ CREATE TABLE IF NOT EXISTS _sqlx_migrations (
     }
 
     fn version(&mut self) -> BoxFuture<'_, Result<Option<(i64, bool)>, MigrateError>> {
+        #[allow(deprecated)]
+        self.version_in(DEFAULT_TABLE_NAME)
+    }
+
+    fn version_in<'e>(
+        &'e mut self,
+        table_name: &'e str,
+    ) -> BoxFuture<'e, Result<Option<(i64, bool)>, MigrateError>> {
         Box::pin(async move {
             // language=SQL
-            let row = query_as(
-                "SELECT version, NOT success FROM _sqlx_migrations ORDER BY version DESC LIMIT 1",
-            )
+            let row = query_as(&format!(
+                "SELECT version, NOT success FROM {} ORDER BY version DESC LIMIT 1",
+                SchemaDialect::Postgres.quote_ident(table_name)
+            ))
             .fetch_optional(self)
             .await?;
 
@@ -120,11 +149,19 @@ CREATE TABLE IF NOT EXISTS _sqlx_migrations (
     }
 
     fn dirty_version(&mut self) -> BoxFuture<'_, Result<Option<i64>, MigrateError>> {
+        self.dirty_version_in(DEFAULT_TABLE_NAME)
+    }
+
+    fn dirty_version_in<'e>(
+        &'e mut self,
+        table_name: &'e str,
+    ) -> BoxFuture<'e, Result<Option<i64>, MigrateError>> {
         Box::pin(async move {
             // language=SQL
-            let row: Option<(i64,)> = query_as(
-                "SELECT version FROM _sqlx_migrations WHERE success = false ORDER BY version LIMIT 1",
-            )
+            let row: Option<(i64,)> = query_as(&format!(
+                "SELECT version FROM {} WHERE success = false ORDER BY version LIMIT 1",
+                SchemaDialect::Postgres.quote_ident(table_name)
+            ))
             .fetch_optional(self)
             .await?;
 
@@ -135,12 +172,21 @@ CREATE TABLE IF NOT EXISTS _sqlx_migrations (
     fn list_applied_migrations(
         &mut self,
     ) -> BoxFuture<'_, Result<Vec<AppliedMigration>, MigrateError>> {
+        self.list_applied_migrations_in(DEFAULT_TABLE_NAME)
+    }
+
+    fn list_applied_migrations_in<'e>(
+        &'e mut self,
+        table_name: &'e str,
+    ) -> BoxFuture<'e, Result<Vec<AppliedMigration>, MigrateError>> {
         Box::pin(async move {
             // language=SQL
-            let rows: Vec<(i64, Vec<u8>)> =
-                query_as("SELECT version, checksum FROM _sqlx_migrations ORDER BY version")
-                    .fetch_all(self)
-                    .await?;
+            let rows: Vec<(i64, Vec<u8>)> = query_as(&format!(
+                "SELECT version, checksum FROM {} ORDER BY version",
+                SchemaDialect::Postgres.quote_ident(table_name)
+            ))
+            .fetch_all(self)
+            .await?;
 
             let migrations = rows
                 .into_iter()
@@ -193,14 +239,25 @@ CREATE TABLE IF NOT EXISTS _sqlx_migrations (
     fn validate<'e: 'm, 'm>(
         &'e mut self,
         migration: &'m Migration,
+    ) -> BoxFuture<'m, Result<(), MigrateError>> {
+        #[allow(deprecated)]
+        self.validate_in(DEFAULT_TABLE_NAME, migration)
+    }
+
+    fn validate_in<'e: 'm, 'm>(
+        &'e mut self,
+        table_name: &'m str,
+        migration: &'m Migration,
     ) -> BoxFuture<'m, Result<(), MigrateError>> {
         Box::pin(async move {
             // language=SQL
-            let checksum: Option<Vec<u8>> =
-                query_scalar("SELECT checksum FROM _sqlx_migrations WHERE version = $1")
-                    .bind(migration.version)
-                    .fetch_optional(self)
-                    .await?;
+            let checksum: Option<Vec<u8>> = query_scalar(&format!(
+                "SELECT checksum FROM {} WHERE version = $1",
+                SchemaDialect::Postgres.quote_ident(table_name)
+            ))
+            .bind(migration.version)
+            .fetch_optional(self)
+            .await?;
 
             if let Some(checksum) = checksum {
                 return if checksum == &*migration.checksum {
@@ -217,6 +274,14 @@ CREATE TABLE IF NOT EXISTS _sqlx_migrations (
     fn apply<'e: 'm, 'm>(
         &'e mut self,
         migration: &'m Migration,
+    ) -> BoxFuture<'m, Result<Duration, MigrateError>> {
+        self.apply_in(DEFAULT_TABLE_NAME, migration)
+    }
+
+    fn apply_in<'e: 'm, 'm>(
+        &'e mut self,
+        table_name: &'m str,
+        migration: &'m Migration,
     ) -> BoxFuture<'m, Result<Duration, MigrateError>> {
         Box::pin(async move {
             let mut tx = self.begin().await?;
@@ -229,12 +294,13 @@ CREATE TABLE IF NOT EXISTS _sqlx_migrations (
             let elapsed = start.elapsed();
 
             // language=SQL
-            let _ = query(
+            let _ = query(&format!(
                 r#"
-    INSERT INTO _sqlx_migrations ( version, description, success, checksum, execution_time )
+    INSERT INTO {} ( version, description, success, checksum, execution_time )
     VALUES ( $1, $2, TRUE, $3, $4 )
                 "#,
-            )
+                SchemaDialect::Postgres.quote_ident(table_name)
+            ))
             .bind(migration.version)
             .bind(&*migration.description)
             .bind(&*migration.checksum)
@@ -249,6 +315,14 @@ CREATE TABLE IF NOT EXISTS _sqlx_migrations (
     fn revert<'e: 'm, 'm>(
         &'e mut self,
         migration: &'m Migration,
+    ) -> BoxFuture<'m, Result<Duration, MigrateError>> {
+        self.revert_in(DEFAULT_TABLE_NAME, migration)
+    }
+
+    fn revert_in<'e: 'm, 'm>(
+        &'e mut self,
+        table_name: &'m str,
+        migration: &'m Migration,
     ) -> BoxFuture<'m, Result<Duration, MigrateError>> {
         Box::pin(async move {
             let mut tx = self.begin().await?;
@@ -261,14 +335,403 @@ CREATE TABLE IF NOT EXISTS _sqlx_migrations (
             let elapsed = start.elapsed();
 
             // language=SQL
-            let _ = query(r#"DELETE FROM _sqlx_migrations WHERE version = $1"#)
-                .bind(migration.version)
-                .execute(self)
-                .await?;
+            let _ = query(&format!(
+                r#"DELETE FROM {} WHERE version = $1"#,
+                SchemaDialect::Postgres.quote_ident(table_name)
+            ))
+            .bind(migration.version)
+            .execute(self)
+            .await?;
 
             Ok(elapsed)
         })
     }
+
+    fn apply_baseline<'e: 'm, 'm>(
+        &'e mut self,
+        table_name: &'m str,
+        migration: &'m Migration,
+    ) -> BoxFuture<'m, Result<(), MigrateError>> {
+        Box::pin(async move {
+            let mut tx = self.begin().await?;
+
+            // language=SQL
+            let _ = query(&format!(
+                r#"DELETE FROM {} WHERE version <= $1"#,
+                SchemaDialect::Postgres.quote_ident(table_name)
+            ))
+            .bind(migration.version)
+            .execute(&mut tx)
+            .await?;
+
+            // language=SQL
+            let _ = query(&format!(
+                r#"
+    INSERT INTO {} ( version, description, success, checksum, execution_time )
+    VALUES ( $1, $2, TRUE, $3, 0 )
+                "#,
+                SchemaDialect::Postgres.quote_ident(table_name)
+            ))
+            .bind(migration.version)
+            .bind(&*migration.description)
+            .bind(&*migration.checksum)
+            .execute(&mut tx)
+            .await?;
+
+            tx.commit().await?;
+
+            Ok(())
+        })
+    }
+
+    fn ensure_seeds_table(&mut self) -> BoxFuture<'_, Result<(), MigrateError>> {
+        Box::pin(async move {
+            // language=PostgreSQL
+            self.execute(
+                r#"
+CREATE TABLE IF NOT EXISTS _sqlx_seeds (
+    name TEXT PRIMARY KEY,
+    applied_on TIMESTAMPTZ NOT NULL DEFAULT now(),
+    checksum BYTEA NOT NULL
+);
+                "#,
+            )
+            .await?;
+
+            Ok(())
+        })
+    }
+
+    fn list_applied_seeds(&mut self) -> BoxFuture<'_, Result<Vec<AppliedSeed>, MigrateError>> {
+        Box::pin(async move {
+            // language=SQL
+            let rows: Vec<(String, Vec<u8>)> =
+                query_as("SELECT name, checksum FROM _sqlx_seeds ORDER BY name")
+                    .fetch_all(self)
+                    .await?;
+
+            let seeds = rows
+                .into_iter()
+                .map(|(name, checksum)| AppliedSeed {
+                    name,
+                    checksum: checksum.into(),
+                })
+                .collect();
+
+            Ok(seeds)
+        })
+    }
+
+    fn apply_seed<'e: 'm, 'm>(
+        &'e mut self,
+        seed: &'m Seed,
+    ) -> BoxFuture<'m, Result<Duration, MigrateError>> {
+        Box::pin(async move {
+            let mut tx = self.begin().await?;
+            let start = Instant::now();
+
+            let _ = tx.execute(&*seed.sql(SchemaDialect::Postgres)).await?;
+
+            // language=SQL
+            let _ = query(r#"DELETE FROM _sqlx_seeds WHERE name = $1"#)
+                .bind(&*seed.name)
+                .execute(&mut tx)
+                .await?;
+
+            // language=SQL
+            let _ = query(r#"INSERT INTO _sqlx_seeds ( name, checksum ) VALUES ( $1, $2 )"#)
+                .bind(&*seed.name)
+                .bind(&*seed.checksum)
+                .execute(&mut tx)
+                .await?;
+
+            tx.commit().await?;
+
+            Ok(start.elapsed())
+        })
+    }
+
+    fn schema<'e>(
+        &'e mut self,
+        migrations_table: &'e str,
+    ) -> BoxFuture<'e, Result<Schema, MigrateError>> {
+        Box::pin(async move {
+            let mut schema = Schema::new(SchemaDialect::Postgres);
+
+            // language=PostgreSQL
+            let enums: Vec<(String, Vec<String>)> = query_as(
+                r#"
+SELECT t.typname::text, array_agg(e.enumlabel::text ORDER BY e.enumsortorder)
+FROM pg_type t
+JOIN pg_namespace n ON n.oid = t.typnamespace
+JOIN pg_enum e ON e.enumtypid = t.oid
+WHERE n.nspname = current_schema()
+GROUP BY t.typname
+ORDER BY t.typname
+                "#,
+            )
+            .fetch_all(&mut *self)
+            .await?;
+
+            schema.enums = enums
+                .into_iter()
+                .map(|(name, variants)| Enum { name, variants })
+                .collect();
+
+            // composite types are kept in creation order as they may contain each other
+            // language=PostgreSQL
+            let attributes: Vec<(String, String, String)> = query_as(
+                r#"
+SELECT t.typname::text, a.attname::text, format_type(a.atttypid, a.atttypmod)
+FROM pg_type t
+JOIN pg_namespace n ON n.oid = t.typnamespace
+JOIN pg_class c ON c.oid = t.typrelid
+JOIN pg_attribute a ON a.attrelid = c.oid
+WHERE n.nspname = current_schema()
+  AND t.typtype = 'c'
+  AND c.relkind = 'c'
+  AND a.attnum > 0
+  AND NOT a.attisdropped
+ORDER BY t.oid, a.attnum
+                "#,
+            )
+            .fetch_all(&mut *self)
+            .await?;
+
+            for (type_name, name, data_type) in attributes {
+                if schema.composites.last().map(|c| &c.name) != Some(&type_name) {
+                    schema.composites.push(Composite {
+                        name: type_name,
+                        attributes: Vec::new(),
+                    });
+                }
+
+                if let Some(composite) = schema.composites.last_mut() {
+                    composite.attributes.push(TableColumn {
+                        name,
+                        data_type,
+                        nullable: true,
+                        default: None,
+                        extra: None,
+                    });
+                }
+            }
+
+            // language=PostgreSQL
+            let table_names: Vec<String> = query_scalar(
+                r#"
+SELECT c.relname::text
+FROM pg_class c
+JOIN pg_namespace n ON n.oid = c.relnamespace
+WHERE n.nspname = current_schema()
+  AND c.relkind IN ('r', 'p')
+  AND c.relname NOT IN ($1, '_sqlx_seeds')
+ORDER BY c.relname
+                "#,
+            )
+            .bind(migrations_table)
+            .fetch_all(&mut *self)
+            .await?;
+
+            let mut tables: BTreeMap<String, Table> = table_names
+                .into_iter()
+                .map(|name| {
+                    let table = Table {
+                        name: name.clone(),
+                        columns: Vec::new(),
+                        constraints: Vec::new(),
+                        indexes: Vec::new(),
+                    };
+
+                    (name, table)
+                })
+                .collect();
+
+            // `information_schema` tells us about identity and generated columns in a way
+            // that's portable across server versions; `format_type()` gives us the declared type
+            // language=PostgreSQL
+            let columns: Vec<(
+                String,
+                String,
+                String,
+                bool,
+                Option<String>,
+                bool,
+                Option<String>,
+                Option<String>,
+            )> = query_as(
+                r#"
+SELECT c.table_name::text,
+       c.column_name::text,
+       format_type(a.atttypid, a.atttypmod),
+       c.is_nullable = 'YES',
+       c.column_default::text,
+       pg_get_serial_sequence(format('%I.%I', c.table_schema, c.table_name), c.column_name) IS NOT NULL,
+       c.identity_generation::text,
+       CASE WHEN c.is_generated = 'ALWAYS' THEN c.generation_expression::text END
+FROM information_schema.columns c
+JOIN pg_namespace n ON n.nspname = c.table_schema
+JOIN pg_class t ON t.relnamespace = n.oid AND t.relname = c.table_name
+JOIN pg_attribute a ON a.attrelid = t.oid AND a.attname = c.column_name
+WHERE c.table_schema = current_schema()
+ORDER BY c.table_name, c.ordinal_position
+                "#,
+            )
+            .fetch_all(&mut *self)
+            .await?;
+
+            for (table, name, data_type, nullable, default, owns_sequence, identity, generated) in
+                columns
+            {
+                let table = match tables.get_mut(&table) {
+                    Some(table) => table,
+                    // a view or the migrations table
+                    None => continue,
+                };
+
+                let serial_type = match &*data_type {
+                    "smallint" => Some("smallserial"),
+                    "integer" => Some("serial"),
+                    "bigint" => Some("bigserial"),
+                    _ => None,
+                };
+
+                let is_serial = owns_sequence
+                    && identity.is_none()
+                    && matches!(&default, Some(d) if d.starts_with("nextval("));
+
+                let column = match (serial_type, identity, generated) {
+                    (Some(serial_type), None, None) if is_serial => TableColumn {
+                        name,
+                        data_type: serial_type.into(),
+                        nullable,
+                        default: None,
+                        extra: None,
+                    },
+
+                    (_, Some(identity), _) => TableColumn {
+                        name,
+                        data_type,
+                        nullable,
+                        default: None,
+                        extra: Some(format!("GENERATED {} AS IDENTITY", identity)),
+                    },
+
+                    (_, None, Some(expr)) => TableColumn {
+                        name,
+                        data_type,
+                        nullable,
+                        default: None,
+                        extra: Some(format!("GENERATED ALWAYS AS ({}) STORED", expr)),
+                    },
+
+                    _ => TableColumn {
+                        name,
+                        data_type,
+                        nullable,
+                        default,
+                        extra: None,
+                    },
+                };
+
+                table.columns.push(column);
+            }
+
+            // language=PostgreSQL
+            let constraints: Vec<(String, String, String, String)> = query_as(
+                r#"
+SELECT t.relname::text, con.conname::text, con.contype::text, pg_get_constraintdef(con.oid)
+FROM pg_constraint con
+JOIN pg_class t ON t.oid = con.conrelid
+JOIN pg_namespace n ON n.oid = t.relnamespace
+WHERE n.nspname = current_schema()
+  AND con.contype IN ('p', 'u', 'f', 'c', 'x')
+ORDER BY t.relname, con.conname
+                "#,
+            )
+            .fetch_all(&mut *self)
+            .await?;
+
+            for (table, name, kind, definition) in constraints {
+                if let Some(table) = tables.get_mut(&table) {
+                    let kind = match &*kind {
+                        "p" => ConstraintKind::PrimaryKey,
+                        "u" => ConstraintKind::Unique,
+                        "f" => ConstraintKind::ForeignKey,
+                        "c" => ConstraintKind::Check,
+                        _ => ConstraintKind::Exclusion,
+                    };
+
+                    table.constraints.push(Constraint {
+                        name,
+                        kind,
+                        definition,
+                    });
+                }
+            }
+
+            // indexes backing a constraint are recreated by the constraint itself
+            // language=PostgreSQL
+            let indexes: Vec<(String, String, String)> = query_as(
+                r#"
+SELECT t.relname::text, i.relname::text, pg_get_indexdef(i.oid)
+FROM pg_index x
+JOIN pg_class i ON i.oid = x.indexrelid
+JOIN pg_class t ON t.oid = x.indrelid
+JOIN pg_namespace n ON n.oid = t.relnamespace
+WHERE n.nspname = current_schema()
+  AND NOT EXISTS (
+    SELECT 1
+    FROM pg_constraint con
+    WHERE con.conindid = x.indexrelid
+      AND con.conrelid = x.indrelid
+      AND con.contype IN ('p', 'u', 'x')
+  )
+ORDER BY t.relname, i.relname
+                "#,
+            )
+            .fetch_all(&mut *self)
+            .await?;
+
+            for (table, name, definition) in indexes {
+                if let Some(table) = tables.get_mut(&table) {
+                    table.indexes.push(Index { name, definition });
+                }
+            }
+
+            schema.tables = tables.into_values().collect();
+
+            // views are kept in creation order as they may depend on each other
+            // language=PostgreSQL
+            let views: Vec<(String, bool, String)> = query_as(
+                r#"
+SELECT c.relname::text, c.relkind = 'm', pg_get_viewdef(c.oid, true)
+FROM pg_class c
+JOIN pg_namespace n ON n.oid = c.relnamespace
+WHERE n.nspname = current_schema()
+  AND c.relkind IN ('v', 'm')
+ORDER BY c.oid
+                "#,
+            )
+            .fetch_all(&mut *self)
+            .await?;
+
+            schema.views = views
+                .into_iter()
+                .map(|(name, materialized, definition)| View {
+                    definition: format!(
+                        "CREATE {}VIEW {} AS\n{}",
+                        if materialized { "MATERIALIZED " } else { "" },
+                        SchemaDialect::Postgres.quote_ident(&name),
+                        definition.trim().trim_end_matches(';')
+                    ),
+                    name,
+                })
+                .collect();
+
+            Ok(schema)
+        })
+    }
 }
 
 async fn current_database(conn: &mut PgConnection) -> Result<String, MigrateError> {
@@ -3,33 +3,69 @@ use bytes::Buf;
 use crate::decode::Decode;
 use crate::encode::{Encode, IsNull};
 use crate::error::BoxDynError;
-use crate::postgres::type_info::PgType;
+use crate::postgres::type_info::{PgType, PgTypeKind};
 use crate::postgres::{PgArgumentBuffer, PgTypeInfo, PgValueFormat, PgValueRef, Postgres};
 use crate::types::Type;
 
-impl<T> Type<Postgres> for [Option<T>]
+/// A type that has an array type in Postgres, making `Vec<T>` and `&[T]` of it a [`Type`].
+///
+/// Implemented for the builtin types that have an array type, and by `#[derive(sqlx::Type)]`
+/// for enums, composite types and domains, whose array type is named after the type with a leading
+/// underscore, e.g. `_mood` for `mood`.
+///
+/// Implement it by hand for a type whose array type is named differently:
+///
+/// ```rust,ignore
+/// impl sqlx::postgres::PgHasArrayType for Mood {
+///     fn array_type_info() -> sqlx::postgres::PgTypeInfo {
+///         sqlx::postgres::PgTypeInfo::with_name("_moods")
+///     }
+/// }
+/// ```
+pub trait PgHasArrayType {
+    fn array_type_info() -> PgTypeInfo;
+
+    fn array_compatible(ty: &PgTypeInfo) -> bool {
+        *ty == Self::array_type_info()
+    }
+}
+
+impl<T> PgHasArrayType for Option<T>
 where
-    [T]: Type<Postgres>,
+    T: PgHasArrayType,
+{
+    fn array_type_info() -> PgTypeInfo {
+        T::array_type_info()
+    }
+
+    fn array_compatible(ty: &PgTypeInfo) -> bool {
+        T::array_compatible(ty)
+    }
+}
+
+impl<T> Type<Postgres> for [T]
+where
+    T: PgHasArrayType,
 {
     fn type_info() -> PgTypeInfo {
-        <[T] as Type<Postgres>>::type_info()
+        T::array_type_info()
     }
 
     fn compatible(ty: &PgTypeInfo) -> bool {
-        <[T] as Type<Postgres>>::compatible(ty)
+        T::array_compatible(ty)
     }
 }
 
-impl<T> Type<Postgres> for Vec<Option<T>>
+impl<T> Type<Postgres> for Vec<T>
 where
-    Vec<T>: Type<Postgres>,
+    T: PgHasArrayType,
 {
     fn type_info() -> PgTypeInfo {
-        <Vec<T> as Type<Postgres>>::type_info()
+        T::array_type_info()
     }
 
     fn compatible(ty: &PgTypeInfo) -> bool {
-        <Vec<T> as Type<Postgres>>::compatible(ty)
+        T::array_compatible(ty)
     }
 }
 
@@ -105,8 +141,23 @@ where
 
                 // the OID of the element
                 let element_type_oid = buf.get_u32();
-                element_type_info = PgTypeInfo::try_from_oid(element_type_oid)
-                    .unwrap_or_else(|| PgTypeInfo(PgType::DeclareWithOid(element_type_oid)));
+                // a user-defined element type is resolved along with the array type, which
+                // composite types need to decode their fields
+                let resolved_element_type = match &value.type_info.0 {
+                    PgType::Custom(ty) => match &ty.kind {
+                        PgTypeKind::Array(element)
+                            if element.0.try_oid() == Some(element_type_oid) =>
+                        {
+                            Some(element.clone())
+                        }
+                        _ => None,
+                    },
+                    _ => None,
+                };
+
+                element_type_info = resolved_element_type
+                    .or_else(|| PgTypeInfo::try_from_oid(element_type_oid))
+                    .unwrap_or(PgTypeInfo(PgType::DeclareWithOid(element_type_oid)));
 
                 // length of the array axis
                 let len = buf.get_i32();
@@ -1,7 +1,9 @@
 use crate::decode::Decode;
 use crate::encode::{Encode, IsNull};
 use crate::error::BoxDynError;
-use crate::postgres::{PgArgumentBuffer, PgTypeInfo, PgValueFormat, PgValueRef, Postgres};
+use crate::postgres::{
+    PgArgumentBuffer, PgHasArrayType, PgTypeInfo, PgValueFormat, PgValueRef, Postgres,
+};
 use crate::types::Type;
 
 impl Type<Postgres> for [u8] {
@@ -16,27 +18,15 @@ impl Type<Postgres> for Vec<u8> {
     }
 }
 
-impl Type<Postgres> for [&'_ [u8]] {
-    fn type_info() -> PgTypeInfo {
+impl PgHasArrayType for &'_ [u8] {
+    fn array_type_info() -> PgTypeInfo {
         PgTypeInfo::BYTEA_ARRAY
     }
 }
 
-impl Type<Postgres> for [Vec<u8>] {
-    fn type_info() -> PgTypeInfo {
-        <[&[u8]] as Type<Postgres>>::type_info()
-    }
-}
-
-impl Type<Postgres> for Vec<&'_ [u8]> {
-    fn type_info() -> PgTypeInfo {
-        <[&[u8]] as Type<Postgres>>::type_info()
-    }
-}
-
-impl Type<Postgres> for Vec<Vec<u8>> {
-    fn type_info() -> PgTypeInfo {
-        <[&[u8]] as Type<Postgres>>::type_info()
+impl PgHasArrayType for Vec<u8> {
+    fn array_type_info() -> PgTypeInfo {
+        <&[u8] as PgHasArrayType>::array_type_info()
     }
 }
 
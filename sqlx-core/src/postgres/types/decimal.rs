@@ -9,7 +9,9 @@ use crate::decode::Decode;
 use crate::encode::{Encode, IsNull};
 use crate::error::BoxDynError;
 use crate::postgres::types::numeric::{PgNumeric, PgNumericSign};
-use crate::postgres::{PgArgumentBuffer, PgTypeInfo, PgValueFormat, PgValueRef, Postgres};
+use crate::postgres::{
+    PgArgumentBuffer, PgHasArrayType, PgTypeInfo, PgValueFormat, PgValueRef, Postgres,
+};
 use crate::types::Type;
 
 impl Type<Postgres> for Decimal {
@@ -18,18 +20,12 @@ impl Type<Postgres> for Decimal {
     }
 }
 
-impl Type<Postgres> for [Decimal] {
-    fn type_info() -> PgTypeInfo {
+impl PgHasArrayType for Decimal {
+    fn array_type_info() -> PgTypeInfo {
         PgTypeInfo::NUMERIC_ARRAY
     }
 }
 
-impl Type<Postgres> for Vec<Decimal> {
-    fn type_info() -> PgTypeInfo {
-        <[Decimal] as Type<Postgres>>::type_info()
-    }
-}
-
 impl TryFrom<PgNumeric> for Decimal {
     type Error = BoxDynError;
 
@@ -3,7 +3,9 @@ use byteorder::{BigEndian, ByteOrder};
 use crate::decode::Decode;
 use crate::encode::{Encode, IsNull};
 use crate::error::BoxDynError;
-use crate::postgres::{PgArgumentBuffer, PgTypeInfo, PgValueFormat, PgValueRef, Postgres};
+use crate::postgres::{
+    PgArgumentBuffer, PgHasArrayType, PgTypeInfo, PgValueFormat, PgValueRef, Postgres,
+};
 use crate::types::Type;
 
 impl Type<Postgres> for i8 {
@@ -12,18 +14,12 @@ impl Type<Postgres> for i8 {
     }
 }
 
-impl Type<Postgres> for [i8] {
-    fn type_info() -> PgTypeInfo {
+impl PgHasArrayType for i8 {
+    fn array_type_info() -> PgTypeInfo {
         PgTypeInfo::CHAR_ARRAY
     }
 }
 
-impl Type<Postgres> for Vec<i8> {
-    fn type_info() -> PgTypeInfo {
-        <[i8] as Type<Postgres>>::type_info()
-    }
-}
-
 impl Encode<'_, Postgres> for i8 {
     fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> IsNull {
         buf.extend(&self.to_be_bytes());
@@ -45,18 +41,12 @@ impl Type<Postgres> for i16 {
     }
 }
 
-impl Type<Postgres> for [i16] {
-    fn type_info() -> PgTypeInfo {
+impl PgHasArrayType for i16 {
+    fn array_type_info() -> PgTypeInfo {
         PgTypeInfo::INT2_ARRAY
     }
 }
 
-impl Type<Postgres> for Vec<i16> {
-    fn type_info() -> PgTypeInfo {
-        <[i16] as Type<Postgres>>::type_info()
-    }
-}
-
 impl Encode<'_, Postgres> for i16 {
     fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> IsNull {
         buf.extend(&self.to_be_bytes());
@@ -80,18 +70,12 @@ impl Type<Postgres> for u32 {
     }
 }
 
-impl Type<Postgres> for [u32] {
-    fn type_info() -> PgTypeInfo {
+impl PgHasArrayType for u32 {
+    fn array_type_info() -> PgTypeInfo {
         PgTypeInfo::OID_ARRAY
     }
 }
 
-impl Type<Postgres> for Vec<u32> {
-    fn type_info() -> PgTypeInfo {
-        <[u32] as Type<Postgres>>::type_info()
-    }
-}
-
 impl Encode<'_, Postgres> for u32 {
     fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> IsNull {
         buf.extend(&self.to_be_bytes());
@@ -115,18 +99,12 @@ impl Type<Postgres> for i32 {
     }
 }
 
-impl Type<Postgres> for [i32] {
-    fn type_info() -> PgTypeInfo {
+impl PgHasArrayType for i32 {
+    fn array_type_info() -> PgTypeInfo {
         PgTypeInfo::INT4_ARRAY
     }
 }
 
-impl Type<Postgres> for Vec<i32> {
-    fn type_info() -> PgTypeInfo {
-        <[i32] as Type<Postgres>>::type_info()
-    }
-}
-
 impl Encode<'_, Postgres> for i32 {
     fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> IsNull {
         buf.extend(&self.to_be_bytes());
@@ -150,18 +128,12 @@ impl Type<Postgres> for i64 {
     }
 }
 
-impl Type<Postgres> for [i64] {
-    fn type_info() -> PgTypeInfo {
+impl PgHasArrayType for i64 {
+    fn array_type_info() -> PgTypeInfo {
         PgTypeInfo::INT8_ARRAY
     }
 }
 
-impl Type<Postgres> for Vec<i64> {
-    fn type_info() -> PgTypeInfo {
-        <[i64] as Type<Postgres>>::type_info()
-    }
-}
-
 impl Encode<'_, Postgres> for i64 {
     fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> IsNull {
         buf.extend(&self.to_be_bytes());
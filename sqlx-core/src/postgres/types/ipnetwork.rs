@@ -5,7 +5,9 @@ use ipnetwork::{IpNetwork, Ipv4Network, Ipv6Network};
 use crate::decode::Decode;
 use crate::encode::{Encode, IsNull};
 use crate::error::BoxDynError;
-use crate::postgres::{PgArgumentBuffer, PgTypeInfo, PgValueFormat, PgValueRef, Postgres};
+use crate::postgres::{
+    PgArgumentBuffer, PgHasArrayType, PgTypeInfo, PgValueFormat, PgValueRef, Postgres,
+};
 use crate::types::Type;
 
 // https://github.com/rust-lang/rust/search?q=AF_INET&unscoped_q=AF_INET
@@ -34,26 +36,16 @@ impl Type<Postgres> for IpNetwork {
     }
 }
 
-impl Type<Postgres> for [IpNetwork] {
-    fn type_info() -> PgTypeInfo {
+impl PgHasArrayType for IpNetwork {
+    fn array_type_info() -> PgTypeInfo {
         PgTypeInfo::INET_ARRAY
     }
 
-    fn compatible(ty: &PgTypeInfo) -> bool {
+    fn array_compatible(ty: &PgTypeInfo) -> bool {
         *ty == PgTypeInfo::CIDR_ARRAY || *ty == PgTypeInfo::INET_ARRAY
     }
 }
 
-impl Type<Postgres> for Vec<IpNetwork> {
-    fn type_info() -> PgTypeInfo {
-        <[IpNetwork] as Type<Postgres>>::type_info()
-    }
-
-    fn compatible(ty: &PgTypeInfo) -> bool {
-        <[IpNetwork] as Type<Postgres>>::compatible(ty)
-    }
-}
-
 impl Encode<'_, Postgres> for IpNetwork {
     fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> IsNull {
         // https://github.com/postgres/postgres/blob/574925bfd0a8175f6e161936ea11d9695677ba09/src/backend/utils/adt/network.c#L293
@@ -131,7 +131,8 @@
 //!
 //! # Arrays
 //!
-//! One-dimensional arrays are supported as `Vec<T>` or `&[T]` where `T` implements `Type`.
+//! One-dimensional arrays are supported as `Vec<T>` or `&[T]` where `T` implements
+//! [`PgHasArrayType`], which the types above and derived enums and composite types do.
 //!
 //! # [Enumerations](https://www.postgresql.org/docs/current/datatype-enum.html)
 //!
@@ -208,6 +209,7 @@ mod mac_address;
 #[cfg(feature = "bit-vec")]
 mod bit_vec;
 
+pub use array::PgHasArrayType;
 pub use interval::PgInterval;
 pub use money::PgMoney;
 pub use range::PgRange;
@@ -1,7 +1,9 @@
 use crate::decode::Decode;
 use crate::encode::{Encode, IsNull};
 use crate::error::BoxDynError;
-use crate::postgres::{PgArgumentBuffer, PgTypeInfo, PgValueFormat, PgValueRef, Postgres};
+use crate::postgres::{
+    PgArgumentBuffer, PgHasArrayType, PgTypeInfo, PgValueFormat, PgValueRef, Postgres,
+};
 use crate::types::Type;
 use std::borrow::Cow;
 use std::mem;
@@ -13,18 +15,12 @@ impl Type<Postgres> for Time {
     }
 }
 
-impl Type<Postgres> for [Time] {
-    fn type_info() -> PgTypeInfo {
+impl PgHasArrayType for Time {
+    fn array_type_info() -> PgTypeInfo {
         PgTypeInfo::TIME_ARRAY
     }
 }
 
-impl Type<Postgres> for Vec<Time> {
-    fn type_info() -> PgTypeInfo {
-        <[Time] as Type<Postgres>>::type_info()
-    }
-}
-
 impl Encode<'_, Postgres> for Time {
     fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> IsNull {
         // TIME is encoded as the microseconds since midnight
@@ -1,7 +1,9 @@
 use crate::decode::Decode;
 use crate::encode::{Encode, IsNull};
 use crate::error::BoxDynError;
-use crate::postgres::{PgArgumentBuffer, PgTypeInfo, PgValueFormat, PgValueRef, Postgres};
+use crate::postgres::{
+    PgArgumentBuffer, PgHasArrayType, PgTypeInfo, PgValueFormat, PgValueRef, Postgres,
+};
 use crate::types::Type;
 use byteorder::{BigEndian, ReadBytesExt};
 use std::io::Cursor;
@@ -33,20 +35,11 @@ pub struct PgTimeTz<Time = DefaultTime, Offset = DefaultOffset> {
     pub offset: Offset,
 }
 
-impl<Time, Offset> Type<Postgres> for [PgTimeTz<Time, Offset>]
+impl<Time, Offset> PgHasArrayType for PgTimeTz<Time, Offset>
 where
     PgTimeTz<Time, Offset>: Type<Postgres>,
 {
-    fn type_info() -> PgTypeInfo {
-        PgTypeInfo::TIMETZ_ARRAY
-    }
-}
-
-impl<Time, Offset> Type<Postgres> for Vec<PgTimeTz<Time, Offset>>
-where
-    PgTimeTz<Time, Offset>: Type<Postgres>,
-{
-    fn type_info() -> PgTypeInfo {
+    fn array_type_info() -> PgTypeInfo {
         PgTypeInfo::TIMETZ_ARRAY
     }
 }
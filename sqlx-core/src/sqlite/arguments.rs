@@ -3,6 +3,7 @@ use crate::encode::{Encode, IsNull};
 use crate::error::Error;
 use crate::sqlite::statement::StatementHandle;
 use crate::sqlite::Sqlite;
+use crate::types::{expand_in_lists, Placeholders};
 use atoi::atoi;
 use libsqlite3_sys::SQLITE_OK;
 use std::borrow::Cow;
@@ -20,6 +21,8 @@ pub enum SqliteArgumentValue<'q> {
 #[derive(Default, Debug, Clone)]
 pub struct SqliteArguments<'q> {
     pub(crate) values: Vec<SqliteArgumentValue<'q>>,
+    // the index of the first value of each `InList`, with its number of values
+    pub(crate) lists: Vec<(usize, usize)>,
 }
 
 impl<'q> SqliteArguments<'q> {
@@ -27,10 +30,28 @@ impl<'q> SqliteArguments<'q> {
     where
         T: Encode<'q, Sqlite>,
     {
+        if let Some(values) = value.list_values() {
+            self.lists.push((self.values.len(), values.len()));
+
+            for (value, _) in values {
+                if let IsNull::Yes = value.encode_by_ref(&mut self.values) {
+                    self.values.push(SqliteArgumentValue::Null);
+                }
+            }
+
+            return;
+        }
+
         if let IsNull::Yes = value.encode(&mut self.values) {
             self.values.push(SqliteArgumentValue::Null);
         }
     }
+
+    /// Replace the placeholder of each [`InList`](crate::types::InList) in `sql` with one
+    /// placeholder per value.
+    pub(crate) fn expand_in_lists<'s>(&self, sql: &'s str) -> Cow<'s, str> {
+        expand_in_lists(sql, &self.lists, Placeholders::Numbered)
+    }
 }
 
 impl<'q> Arguments<'q> for SqliteArguments<'q> {
@@ -8,6 +8,8 @@ pub struct SqliteColumn {
     pub(crate) name: UStr,
     pub(crate) ordinal: usize,
     pub(crate) type_info: SqliteTypeInfo,
+    #[cfg_attr(feature = "offline", serde(default))]
+    pub(crate) origin_table: Option<UStr>,
 }
 
 impl crate::column::private_column::Sealed for SqliteColumn {}
@@ -26,6 +28,10 @@ impl Column for SqliteColumn {
     fn type_info(&self) -> &SqliteTypeInfo {
         &self.type_info
     }
+
+    fn origin_table(&self) -> Option<&str> {
+        self.origin_table.as_deref()
+    }
 }
 
 #[cfg(feature = "any")]
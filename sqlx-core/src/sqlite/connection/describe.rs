@@ -88,6 +88,9 @@ pub(super) fn describe<'c: 'e, 'q: 'e, 'e>(
                     name: name.into(),
                     type_info,
                     ordinal: col,
+                    origin_table: stmt
+                        .column_table_name(col)
+                        .map(|table| table.to_owned().into()),
                 });
             }
         }
@@ -95,11 +95,15 @@ impl<'c> Executor<'c> for &'c mut SqliteConnection {
         'c: 'e,
         E: Execute<'q, Self::Database>,
     {
-        let sql = query.sql();
-        let mut logger = QueryLogger::new(sql, self.log_settings.clone());
         let arguments = query.take_arguments();
         let persistent = query.persistent() && arguments.is_some();
 
+        let sql = match &arguments {
+            Some(arguments) => arguments.expand_in_lists(query.sql()),
+            None => Cow::Borrowed(query.sql()),
+        };
+        let log_settings = self.log_settings.clone();
+
         Box::pin(try_stream! {
             let SqliteConnection {
                 handle: ref mut conn,
@@ -109,8 +113,10 @@ impl<'c> Executor<'c> for &'c mut SqliteConnection {
                 ..
             } = self;
 
+            let mut logger = QueryLogger::new(&sql, log_settings);
+
             // prepare statement object (or checkout from cache)
-            let stmt = prepare(worker, statements, statement, sql, persistent).await?;
+            let stmt = prepare(worker, statements, statement, &sql, persistent).await?;
 
             // keep track of how many arguments we have bound
             let mut num_arguments = 0;
@@ -180,11 +186,15 @@ impl<'c> Executor<'c> for &'c mut SqliteConnection {
         'c: 'e,
         E: Execute<'q, Self::Database>,
     {
-        let sql = query.sql();
-        let mut logger = QueryLogger::new(sql, self.log_settings.clone());
         let arguments = query.take_arguments();
         let persistent = query.persistent() && arguments.is_some();
 
+        let sql = match &arguments {
+            Some(arguments) => arguments.expand_in_lists(query.sql()),
+            None => Cow::Borrowed(query.sql()),
+        };
+        let log_settings = self.log_settings.clone();
+
         Box::pin(async move {
             let SqliteConnection {
                 handle: ref mut conn,
@@ -194,8 +204,10 @@ impl<'c> Executor<'c> for &'c mut SqliteConnection {
                 ..
             } = self;
 
+            let mut logger = QueryLogger::new(&sql, log_settings);
+
             // prepare statement object (or checkout from cache)
-            let virtual_stmt = prepare(worker, statements, statement, sql, persistent).await?;
+            let virtual_stmt = prepare(worker, statements, statement, &sql, persistent).await?;
 
             // keep track of how many arguments we have bound
             let mut num_arguments = 0;
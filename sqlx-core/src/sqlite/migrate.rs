@@ -1,13 +1,17 @@
 use crate::connection::{ConnectOptions, Connection};
 use crate::error::Error;
 use crate::executor::Executor;
-use crate::migrate::MigrateError;
-use crate::migrate::{AppliedMigration, Migration};
+use crate::migrate::{AppliedMigration, AppliedSeed, Migration, Seed};
+use crate::migrate::{
+    Constraint, ConstraintKind, Index, Schema, SchemaDialect, Table, TableColumn, View,
+};
 use crate::migrate::{Migrate, MigrateDatabase};
+use crate::migrate::{MigrateError, DEFAULT_TABLE_NAME};
 use crate::query::query;
 use crate::query_as::query_as;
 use crate::query_scalar::query_scalar;
 use crate::sqlite::{Sqlite, SqliteConnectOptions, SqliteConnection};
+use crate::testing::{TestArgs, TestSupport};
 use futures_core::future::BoxFuture;
 use sqlx_rt::fs;
 use std::str::FromStr;
@@ -52,13 +56,40 @@ impl MigrateDatabase for Sqlite {
     }
 }
 
+impl TestSupport for Sqlite {
+    fn test_database_url(args: &TestArgs) -> Result<String, Error> {
+        // the test runs from `<target dir>/<profile>/deps`, and in the package directory
+        // rather than the workspace one
+        let exe = std::env::current_exe()?;
+        let target_dir = exe.ancestors().nth(3).ok_or_else(|| {
+            Error::Configuration(format!("no target directory above {}", exe.display()).into())
+        })?;
+
+        let dir = target_dir.join("sqlx/test-dbs");
+        std::fs::create_dir_all(&dir)?;
+
+        Ok(format!(
+            "sqlite://{}/{}.sqlite",
+            dir.display(),
+            args.database_name()
+        ))
+    }
+}
+
 impl Migrate for SqliteConnection {
     fn ensure_migrations_table(&mut self) -> BoxFuture<'_, Result<(), MigrateError>> {
+        self.ensure_migrations_table_in(DEFAULT_TABLE_NAME)
+    }
+
+    fn ensure_migrations_table_in<'e>(
+        &'e mut self,
+        table_name: &'e str,
+    ) -> BoxFuture<'e, Result<(), MigrateError>> {
         Box::pin(async move {
             // language=SQLite
-            self.execute(
+            self.execute(&*format!(
                 r#"
-CREATE TABLE IF NOT EXISTS _sqlx_migrations (
+CREATE TABLE IF NOT EXISTS {} (
     version BIGINT PRIMARY KEY,
     description TEXT NOT NULL,
     installed_on TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
@@ -67,7 +98,8 @@ CREATE TABLE IF NOT EXISTS _sqlx_migrations (
     execution_time BIGINT NOT NULL
 );
                 "#,
-            )
+                SchemaDialect::Sqlite.quote_ident(table_name)
+            ))
             .await?;
 
             Ok(())
@@ -75,11 +107,20 @@ CREATE TABLE IF NOT EXISTS _sqlx_migrations (
     }
 
     fn version(&mut self) -> BoxFuture<'_, Result<Option<(i64, bool)>, MigrateError>> {
+        #[allow(deprecated)]
+        self.version_in(DEFAULT_TABLE_NAME)
+    }
+
+    fn version_in<'e>(
+        &'e mut self,
+        table_name: &'e str,
+    ) -> BoxFuture<'e, Result<Option<(i64, bool)>, MigrateError>> {
         Box::pin(async move {
             // language=SQLite
-            let row = query_as(
-                "SELECT version, NOT success FROM _sqlx_migrations ORDER BY version DESC LIMIT 1",
-            )
+            let row = query_as(&format!(
+                "SELECT version, NOT success FROM {} ORDER BY version DESC LIMIT 1",
+                SchemaDialect::Sqlite.quote_ident(table_name)
+            ))
             .fetch_optional(self)
             .await?;
 
@@ -88,11 +129,19 @@ CREATE TABLE IF NOT EXISTS _sqlx_migrations (
     }
 
     fn dirty_version(&mut self) -> BoxFuture<'_, Result<Option<i64>, MigrateError>> {
+        self.dirty_version_in(DEFAULT_TABLE_NAME)
+    }
+
+    fn dirty_version_in<'e>(
+        &'e mut self,
+        table_name: &'e str,
+    ) -> BoxFuture<'e, Result<Option<i64>, MigrateError>> {
         Box::pin(async move {
             // language=SQLite
-            let row: Option<(i64,)> = query_as(
-                "SELECT version FROM _sqlx_migrations WHERE success = false ORDER BY version LIMIT 1",
-            )
+            let row: Option<(i64,)> = query_as(&format!(
+                "SELECT version FROM {} WHERE success = false ORDER BY version LIMIT 1",
+                SchemaDialect::Sqlite.quote_ident(table_name)
+            ))
             .fetch_optional(self)
             .await?;
 
@@ -103,12 +152,21 @@ CREATE TABLE IF NOT EXISTS _sqlx_migrations (
     fn list_applied_migrations(
         &mut self,
     ) -> BoxFuture<'_, Result<Vec<AppliedMigration>, MigrateError>> {
+        self.list_applied_migrations_in(DEFAULT_TABLE_NAME)
+    }
+
+    fn list_applied_migrations_in<'e>(
+        &'e mut self,
+        table_name: &'e str,
+    ) -> BoxFuture<'e, Result<Vec<AppliedMigration>, MigrateError>> {
         Box::pin(async move {
             // language=SQLite
-            let rows: Vec<(i64, Vec<u8>)> =
-                query_as("SELECT version, checksum FROM _sqlx_migrations ORDER BY version")
-                    .fetch_all(self)
-                    .await?;
+            let rows: Vec<(i64, Vec<u8>)> = query_as(&format!(
+                "SELECT version, checksum FROM {} ORDER BY version",
+                SchemaDialect::Sqlite.quote_ident(table_name)
+            ))
+            .fetch_all(self)
+            .await?;
 
             let migrations = rows
                 .into_iter()
@@ -133,14 +191,25 @@ CREATE TABLE IF NOT EXISTS _sqlx_migrations (
     fn validate<'e: 'm, 'm>(
         &'e mut self,
         migration: &'m Migration,
+    ) -> BoxFuture<'m, Result<(), MigrateError>> {
+        #[allow(deprecated)]
+        self.validate_in(DEFAULT_TABLE_NAME, migration)
+    }
+
+    fn validate_in<'e: 'm, 'm>(
+        &'e mut self,
+        table_name: &'m str,
+        migration: &'m Migration,
     ) -> BoxFuture<'m, Result<(), MigrateError>> {
         Box::pin(async move {
             // language=SQL
-            let checksum: Option<Vec<u8>> =
-                query_scalar("SELECT checksum FROM _sqlx_migrations WHERE version = ?1")
-                    .bind(migration.version)
-                    .fetch_optional(self)
-                    .await?;
+            let checksum: Option<Vec<u8>> = query_scalar(&format!(
+                "SELECT checksum FROM {} WHERE version = ?1",
+                SchemaDialect::Sqlite.quote_ident(table_name)
+            ))
+            .bind(migration.version)
+            .fetch_optional(self)
+            .await?;
 
             if let Some(checksum) = checksum {
                 if checksum == &*migration.checksum {
@@ -157,6 +226,14 @@ CREATE TABLE IF NOT EXISTS _sqlx_migrations (
     fn apply<'e: 'm, 'm>(
         &'e mut self,
         migration: &'m Migration,
+    ) -> BoxFuture<'m, Result<Duration, MigrateError>> {
+        self.apply_in(DEFAULT_TABLE_NAME, migration)
+    }
+
+    fn apply_in<'e: 'm, 'm>(
+        &'e mut self,
+        table_name: &'m str,
+        migration: &'m Migration,
     ) -> BoxFuture<'m, Result<Duration, MigrateError>> {
         Box::pin(async move {
             let mut tx = self.begin().await?;
@@ -169,12 +246,13 @@ CREATE TABLE IF NOT EXISTS _sqlx_migrations (
             let elapsed = start.elapsed();
 
             // language=SQL
-            let _ = query(
+            let _ = query(&format!(
                 r#"
-    INSERT INTO _sqlx_migrations ( version, description, success, checksum, execution_time )
+    INSERT INTO {} ( version, description, success, checksum, execution_time )
     VALUES ( ?1, ?2, TRUE, ?3, ?4 )
                 "#,
-            )
+                SchemaDialect::Sqlite.quote_ident(table_name)
+            ))
             .bind(migration.version)
             .bind(&*migration.description)
             .bind(&*migration.checksum)
@@ -189,6 +267,14 @@ CREATE TABLE IF NOT EXISTS _sqlx_migrations (
     fn revert<'e: 'm, 'm>(
         &'e mut self,
         migration: &'m Migration,
+    ) -> BoxFuture<'m, Result<Duration, MigrateError>> {
+        self.revert_in(DEFAULT_TABLE_NAME, migration)
+    }
+
+    fn revert_in<'e: 'm, 'm>(
+        &'e mut self,
+        table_name: &'m str,
+        migration: &'m Migration,
     ) -> BoxFuture<'m, Result<Duration, MigrateError>> {
         Box::pin(async move {
             let mut tx = self.begin().await?;
@@ -201,12 +287,539 @@ CREATE TABLE IF NOT EXISTS _sqlx_migrations (
             let elapsed = start.elapsed();
 
             // language=SQL
-            let _ = query(r#"DELETE FROM _sqlx_migrations WHERE version = ?1"#)
-                .bind(migration.version)
-                .execute(self)
-                .await?;
+            let _ = query(&format!(
+                r#"DELETE FROM {} WHERE version = ?1"#,
+                SchemaDialect::Sqlite.quote_ident(table_name)
+            ))
+            .bind(migration.version)
+            .execute(self)
+            .await?;
 
             Ok(elapsed)
         })
     }
+
+    fn apply_baseline<'e: 'm, 'm>(
+        &'e mut self,
+        table_name: &'m str,
+        migration: &'m Migration,
+    ) -> BoxFuture<'m, Result<(), MigrateError>> {
+        Box::pin(async move {
+            let mut tx = self.begin().await?;
+
+            // language=SQL
+            let _ = query(&format!(
+                r#"DELETE FROM {} WHERE version <= ?1"#,
+                SchemaDialect::Sqlite.quote_ident(table_name)
+            ))
+            .bind(migration.version)
+            .execute(&mut tx)
+            .await?;
+
+            // language=SQL
+            let _ = query(&format!(
+                r#"
+    INSERT INTO {} ( version, description, success, checksum, execution_time )
+    VALUES ( ?1, ?2, TRUE, ?3, 0 )
+                "#,
+                SchemaDialect::Sqlite.quote_ident(table_name)
+            ))
+            .bind(migration.version)
+            .bind(&*migration.description)
+            .bind(&*migration.checksum)
+            .execute(&mut tx)
+            .await?;
+
+            tx.commit().await?;
+
+            Ok(())
+        })
+    }
+
+    fn ensure_seeds_table(&mut self) -> BoxFuture<'_, Result<(), MigrateError>> {
+        Box::pin(async move {
+            // language=SQLite
+            self.execute(
+                r#"
+CREATE TABLE IF NOT EXISTS _sqlx_seeds (
+    name TEXT PRIMARY KEY,
+    applied_on TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    checksum BLOB NOT NULL
+);
+                "#,
+            )
+            .await?;
+
+            Ok(())
+        })
+    }
+
+    fn list_applied_seeds(&mut self) -> BoxFuture<'_, Result<Vec<AppliedSeed>, MigrateError>> {
+        Box::pin(async move {
+            // language=SQL
+            let rows: Vec<(String, Vec<u8>)> =
+                query_as("SELECT name, checksum FROM _sqlx_seeds ORDER BY name")
+                    .fetch_all(self)
+                    .await?;
+
+            let seeds = rows
+                .into_iter()
+                .map(|(name, checksum)| AppliedSeed {
+                    name,
+                    checksum: checksum.into(),
+                })
+                .collect();
+
+            Ok(seeds)
+        })
+    }
+
+    fn apply_seed<'e: 'm, 'm>(
+        &'e mut self,
+        seed: &'m Seed,
+    ) -> BoxFuture<'m, Result<Duration, MigrateError>> {
+        Box::pin(async move {
+            let mut tx = self.begin().await?;
+            let start = Instant::now();
+
+            let _ = tx.execute(&*seed.sql(SchemaDialect::Sqlite)).await?;
+
+            // language=SQL
+            let _ = query(r#"DELETE FROM _sqlx_seeds WHERE name = ?1"#)
+                .bind(&*seed.name)
+                .execute(&mut tx)
+                .await?;
+
+            // language=SQL
+            let _ = query(r#"INSERT INTO _sqlx_seeds ( name, checksum ) VALUES ( ?1, ?2 )"#)
+                .bind(&*seed.name)
+                .bind(&*seed.checksum)
+                .execute(&mut tx)
+                .await?;
+
+            tx.commit().await?;
+
+            Ok(start.elapsed())
+        })
+    }
+
+    fn schema<'e>(
+        &'e mut self,
+        migrations_table: &'e str,
+    ) -> BoxFuture<'e, Result<Schema, MigrateError>> {
+        Box::pin(async move {
+            let dialect = SchemaDialect::Sqlite;
+            let mut schema = Schema::new(dialect);
+
+            // language=SQLite
+            let table_names: Vec<(String, String)> = query_as(
+                r#"
+SELECT name, sql
+FROM sqlite_master
+WHERE type = 'table'
+  AND name NOT LIKE 'sqlite_%'
+  AND name NOT IN (?1, '_sqlx_seeds')
+ORDER BY name
+                "#,
+            )
+            .bind(migrations_table)
+            .fetch_all(&mut *self)
+            .await?;
+
+            for (table_name, table_sql) in table_names {
+                let mut table = Table {
+                    name: table_name,
+                    columns: Vec::new(),
+                    constraints: Vec::new(),
+                    indexes: Vec::new(),
+                };
+
+                let definitions = table_definitions(&table_sql);
+
+                // `table_xinfo` lists generated columns as well, which are hidden from
+                // `table_info`; `hidden` is 2 for a virtual and 3 for a stored generated column
+                // language=SQLite
+                let columns: Vec<(String, String, bool, Option<String>, i64, i64)> = query_as(
+                    r#"SELECT name, type, "notnull", dflt_value, pk, hidden FROM pragma_table_xinfo(?1) WHERE hidden != 1 ORDER BY cid"#,
+                )
+                .bind(&table.name)
+                .fetch_all(&mut *self)
+                .await?;
+
+                let mut primary_key: Vec<(i64, String)> = columns
+                    .iter()
+                    .filter(|column| column.4 > 0)
+                    .map(|column| (column.4, column.0.clone()))
+                    .collect();
+
+                primary_key.sort();
+
+                // `AUTOINCREMENT` is only allowed on an `INTEGER PRIMARY KEY` column declaration
+                // and is not reported by any pragma
+                let autoincrement = match &*primary_key {
+                    [(_, column)] => {
+                        matches!(column_definition(&definitions, column), Some(definition) if is_autoincrement(definition))
+                    }
+                    _ => false,
+                };
+
+                for (name, data_type, not_null, default, pk, hidden) in columns {
+                    // the expression of a generated column is not reported by any pragma
+                    let generated = match hidden {
+                        2 | 3 => column_definition(&definitions, &name).and_then(generated_expr),
+                        _ => None,
+                    };
+
+                    let extra = if let Some(expr) = generated {
+                        let storage = if hidden == 3 { "STORED" } else { "VIRTUAL" };
+                        Some(format!("GENERATED ALWAYS AS {} {}", expr, storage))
+                    } else if autoincrement && pk > 0 {
+                        Some("PRIMARY KEY AUTOINCREMENT".into())
+                    } else {
+                        None
+                    };
+
+                    table.columns.push(TableColumn {
+                        extra,
+                        name,
+                        data_type,
+                        nullable: !not_null,
+                        default,
+                    });
+                }
+
+                if !primary_key.is_empty() && !autoincrement {
+                    let columns: Vec<String> =
+                        primary_key.into_iter().map(|(_, name)| name).collect();
+
+                    table.constraints.push(Constraint {
+                        name: format!("{}_pkey", table.name),
+                        kind: ConstraintKind::PrimaryKey,
+                        definition: format!("PRIMARY KEY ({})", quote_idents(dialect, &columns)),
+                    });
+                }
+
+                // language=SQLite
+                let unique_indexes: Vec<String> = query_scalar(
+                    r#"SELECT name FROM pragma_index_list(?1) WHERE origin = 'u' ORDER BY name"#,
+                )
+                .bind(&table.name)
+                .fetch_all(&mut *self)
+                .await?;
+
+                for index in unique_indexes {
+                    // language=SQLite
+                    let columns: Vec<String> =
+                        query_scalar(r#"SELECT name FROM pragma_index_info(?1) ORDER BY seqno"#)
+                            .bind(&index)
+                            .fetch_all(&mut *self)
+                            .await?;
+
+                    table.constraints.push(Constraint {
+                        name: index,
+                        kind: ConstraintKind::Unique,
+                        definition: format!("UNIQUE ({})", quote_idents(dialect, &columns)),
+                    });
+                }
+
+                // language=SQLite
+                let foreign_keys: Vec<(i64, String, String, Option<String>, String, String)> =
+                    query_as(
+                        r#"
+SELECT id, "table", "from", "to", on_update, on_delete
+FROM pragma_foreign_key_list(?1)
+ORDER BY id, seq
+                        "#,
+                    )
+                    .bind(&table.name)
+                    .fetch_all(&mut *self)
+                    .await?;
+
+                let mut grouped: Vec<(i64, String, Vec<String>, Vec<String>, String)> = Vec::new();
+
+                for (id, ref_table, from, to, on_update, on_delete) in foreign_keys {
+                    match grouped.last_mut() {
+                        Some(last) if last.0 == id => {
+                            last.2.push(from);
+                            last.3.extend(to);
+                        }
+
+                        _ => {
+                            let mut actions = String::new();
+
+                            for (event, rule) in [("UPDATE", on_update), ("DELETE", on_delete)] {
+                                if rule != "NO ACTION" {
+                                    actions.push_str(&format!(" ON {} {}", event, rule));
+                                }
+                            }
+
+                            grouped.push((
+                                id,
+                                ref_table,
+                                vec![from],
+                                to.into_iter().collect(),
+                                actions,
+                            ));
+                        }
+                    }
+                }
+
+                for (_, ref_table, from, to, actions) in grouped {
+                    // an empty list of referenced columns refers to the primary key
+                    let to = if to.is_empty() {
+                        String::new()
+                    } else {
+                        format!(" ({})", quote_idents(dialect, &to))
+                    };
+
+                    table.constraints.push(Constraint {
+                        name: format!("{}_{}_fkey", table.name, from.join("_")),
+                        kind: ConstraintKind::ForeignKey,
+                        definition: format!(
+                            "FOREIGN KEY ({}) REFERENCES {}{}{}",
+                            quote_idents(dialect, &from),
+                            dialect.quote_ident(&ref_table),
+                            to,
+                            actions
+                        ),
+                    });
+                }
+
+                // `CHECK` constraints are not reported by any pragma either; those of columns are
+                // kept as table constraints, which are checked the same
+                let checks = definitions.iter().flat_map(|definition| checks(definition));
+
+                for (i, check) in checks.enumerate() {
+                    table.constraints.push(Constraint {
+                        name: format!("{}_check{}", table.name, i + 1),
+                        kind: ConstraintKind::Check,
+                        definition: check,
+                    });
+                }
+
+                table.constraints.sort_by(|a, b| a.name.cmp(&b.name));
+
+                // indexes created implicitly for constraints have no SQL
+                // language=SQLite
+                let indexes: Vec<(String, String)> = query_as(
+                    r#"
+SELECT name, sql
+FROM sqlite_master
+WHERE type = 'index'
+  AND tbl_name = ?1
+  AND sql IS NOT NULL
+ORDER BY name
+                    "#,
+                )
+                .bind(&table.name)
+                .fetch_all(&mut *self)
+                .await?;
+
+                table.indexes = indexes
+                    .into_iter()
+                    .map(|(name, definition)| Index { name, definition })
+                    .collect();
+
+                schema.tables.push(table);
+            }
+
+            // views are kept in creation order as they may depend on each other
+            // language=SQLite
+            let views: Vec<(String, String)> =
+                query_as("SELECT name, sql FROM sqlite_master WHERE type = 'view' ORDER BY rowid")
+                    .fetch_all(&mut *self)
+                    .await?;
+
+            schema.views = views
+                .into_iter()
+                .map(|(name, definition)| View { name, definition })
+                .collect();
+
+            Ok(schema)
+        })
+    }
+}
+
+fn quote_idents(dialect: SchemaDialect, idents: &[String]) -> String {
+    idents
+        .iter()
+        .map(|ident| dialect.quote_ident(ident))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// The column definitions and table constraints in the body of a `CREATE TABLE` statement.
+fn table_definitions(table_sql: &str) -> Vec<&str> {
+    let mut definitions = Vec::new();
+    let mut depth = 0;
+    let mut start = 0;
+
+    for (offset, token) in sql_tokens(table_sql) {
+        match token {
+            SqlToken::Punct('(') if depth == 0 => {
+                depth = 1;
+                start = offset + 1;
+            }
+            SqlToken::Punct('(') => depth += 1,
+            SqlToken::Punct(',') if depth == 1 => {
+                definitions.push(table_sql[start..offset].trim());
+                start = offset + 1;
+            }
+            SqlToken::Punct(')') if depth == 1 => {
+                definitions.push(table_sql[start..offset].trim());
+                break;
+            }
+            SqlToken::Punct(')') => depth -= 1,
+            _ => {}
+        }
+    }
+
+    definitions
+}
+
+/// The definition of `column` among the `definitions` of a table.
+fn column_definition<'a>(definitions: &[&'a str], column: &str) -> Option<&'a str> {
+    definitions
+        .iter()
+        .copied()
+        .find(|definition| match sql_tokens(definition).first() {
+            Some((_, SqlToken::Word(name))) | Some((_, SqlToken::Quoted(name))) => {
+                name.eq_ignore_ascii_case(column)
+            }
+            _ => false,
+        })
+}
+
+/// The positions of `keyword` among `tokens`, outside of any parentheses. A keyword must not be
+/// confused with the same word in a string, a comment or an expression.
+fn keyword_positions(tokens: &[(usize, SqlToken<'_>)], keyword: &str) -> Vec<usize> {
+    let mut positions = Vec::new();
+    let mut depth = 0;
+
+    for (i, (_, token)) in tokens.iter().enumerate() {
+        match token {
+            SqlToken::Punct('(') => depth += 1,
+            SqlToken::Punct(')') => depth -= 1,
+            SqlToken::Word(word) if depth == 0 && word.eq_ignore_ascii_case(keyword) => {
+                positions.push(i)
+            }
+            _ => {}
+        }
+    }
+
+    positions
+}
+
+/// The parenthesized expression in `sql` following the token at `i`, with its parentheses.
+fn parens_after<'a>(sql: &'a str, tokens: &[(usize, SqlToken<'_>)], i: usize) -> Option<&'a str> {
+    let (start, open) = tokens.get(i + 1)?;
+
+    if *open != SqlToken::Punct('(') {
+        return None;
+    }
+
+    let mut depth = 0;
+
+    for (offset, token) in &tokens[i + 1..] {
+        match token {
+            SqlToken::Punct('(') => depth += 1,
+            SqlToken::Punct(')') if depth == 1 => return Some(&sql[*start..=*offset]),
+            SqlToken::Punct(')') => depth -= 1,
+            _ => {}
+        }
+    }
+
+    None
+}
+
+/// Whether a column definition has the `AUTOINCREMENT` keyword.
+fn is_autoincrement(definition: &str) -> bool {
+    !keyword_positions(&sql_tokens(definition), "AUTOINCREMENT").is_empty()
+}
+
+/// The expression a generated column is computed from, with its parentheses.
+fn generated_expr(definition: &str) -> Option<&str> {
+    let tokens = sql_tokens(definition);
+
+    keyword_positions(&tokens, "AS")
+        .into_iter()
+        .find_map(|i| parens_after(definition, &tokens, i))
+}
+
+/// The `CHECK` constraints of a column definition or table constraint.
+fn checks(definition: &str) -> Vec<String> {
+    let tokens = sql_tokens(definition);
+
+    keyword_positions(&tokens, "CHECK")
+        .into_iter()
+        .filter_map(|i| parens_after(definition, &tokens, i))
+        .map(|expr| format!("CHECK {}", expr))
+        .collect()
+}
+
+#[derive(Debug, PartialEq)]
+enum SqlToken<'a> {
+    Word(&'a str),
+    /// A quoted identifier, without its quotes.
+    Quoted(&'a str),
+    Literal,
+    Punct(char),
+}
+
+/// Split SQL into tokens and their offsets, skipping whitespace and comments.
+fn sql_tokens(sql: &str) -> Vec<(usize, SqlToken<'_>)> {
+    let mut tokens = Vec::new();
+    let mut offset = 0;
+
+    while let Some(c) = sql[offset..].chars().next() {
+        let rest = &sql[offset..];
+
+        let (token, len) = match c {
+            _ if c.is_whitespace() => (None, c.len_utf8()),
+            '-' if rest.starts_with("--") => (None, rest.find('\n').unwrap_or(rest.len())),
+            '/' if rest.starts_with("/*") => (None, rest.find("*/").map_or(rest.len(), |i| i + 2)),
+            '\'' | '"' | '`' | '[' => {
+                let close = if c == '[' { ']' } else { c };
+
+                // a doubled quote inside the quotes is an escaped quote
+                let mut end = 1;
+                let inner = loop {
+                    match rest[end..].find(close) {
+                        Some(i) if close != ']' && rest[end + i + 1..].starts_with(close) => {
+                            end += i + 2;
+                        }
+                        Some(i) => {
+                            end += i + 1;
+                            break &rest[1..end - 1];
+                        }
+                        None => {
+                            end = rest.len();
+                            break &rest[1..];
+                        }
+                    }
+                };
+
+                let token = if c == '\'' {
+                    SqlToken::Literal
+                } else {
+                    SqlToken::Quoted(inner)
+                };
+
+                (Some(token), end)
+            }
+            _ if c.is_alphanumeric() || c == '_' || c == '$' => {
+                let len = rest
+                    .find(|c: char| !(c.is_alphanumeric() || c == '_' || c == '$'))
+                    .unwrap_or(rest.len());
+
+                (Some(SqlToken::Word(&rest[..len])), len)
+            }
+            _ => (Some(SqlToken::Punct(c)), c.len_utf8()),
+        };
+
+        tokens.extend(token.map(|token| (offset, token)));
+        offset += len;
+    }
+
+    tokens
 }
@@ -134,6 +134,19 @@ impl StatementHandle {
         }
     }
 
+    pub(crate) fn column_table_name(&self, index: usize) -> Option<&str> {
+        unsafe {
+            // https://sqlite.org/c3ref/column_database_name.html
+            let name = sqlite3_column_table_name(self.0.as_ptr(), index as c_int);
+            if name.is_null() {
+                // the column is an expression or subquery
+                return None;
+            }
+
+            Some(from_utf8_unchecked(CStr::from_ptr(name).to_bytes()))
+        }
+    }
+
     pub(crate) fn column_nullable(&self, index: usize) -> Result<Option<bool>, Error> {
         unsafe {
             // https://sqlite.org/c3ref/column_database_name.html
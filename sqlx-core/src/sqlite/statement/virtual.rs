@@ -153,6 +153,7 @@ impl VirtualStatement {
                         ordinal: i,
                         name: name.clone(),
                         type_info,
+                        origin_table: None,
                     });
 
                     column_names.insert(name, i);
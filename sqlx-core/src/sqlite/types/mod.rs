@@ -37,6 +37,11 @@
 //! | `uuid::Uuid`                          | BLOB, TEXT                                           |
 //! | `uuid::adapter::Hyphenated`           | TEXT                                                 |
 //!
+//! # Lists
+//!
+//! [`InList<T>`](crate::types::InList) binds a list of `T` as one parameter per value, for
+//! `IN (?)`.
+//!
 //! # Nullable
 //!
 //! In addition, `Option<T>` is supported where `T` implements `Type`. An `Option<T>` represents
@@ -48,6 +53,7 @@ mod bytes;
 #[cfg(feature = "chrono")]
 mod chrono;
 mod float;
+mod in_list;
 mod int;
 #[cfg(feature = "json")]
 mod json;
@@ -0,0 +1,160 @@
+//! Support for `#[sqlx::test]`, which runs each test against a database of its own.
+
+use crate::database::Database;
+use crate::error::Error;
+use crate::executor::Executor;
+use crate::migrate::{Migrate, MigrateDatabase, Migrator};
+use crate::pool::{Pool, PoolOptions};
+use sha2::{Digest, Sha256};
+use std::future::Future;
+
+/// A database that `#[sqlx::test]` can create a database per test for.
+pub trait TestSupport: Database + MigrateDatabase {
+    /// Returns the URL of the database that the test runs against, named by
+    /// [`TestArgs::database_name()`].
+    ///
+    /// The database itself is created (and dropped) through [`MigrateDatabase`].
+    fn test_database_url(args: &TestArgs) -> Result<String, Error>;
+}
+
+/// The arguments of a test generated by `#[sqlx::test]`.
+#[derive(Debug)]
+pub struct TestArgs {
+    /// The path of the test function, used to name its database.
+    pub test_path: &'static str,
+
+    /// The environment variable the base URL of the test databases is read from, which is
+    /// `[common] database-url-var` of the configuration.
+    pub database_url_var: &'static str,
+
+    /// The migrations to apply to the database before the test runs.
+    pub migrator: Option<&'static Migrator>,
+
+    /// The fixtures to apply, in order, after the migrations.
+    pub fixtures: &'static [TestFixture],
+}
+
+/// A SQL script applied to the database of a test before it runs.
+#[derive(Debug)]
+pub struct TestFixture {
+    pub path: &'static str,
+    pub contents: &'static str,
+}
+
+/// The return type of a test function, which decides whether its database is dropped.
+pub trait TestTermination {
+    fn is_success(&self) -> bool;
+}
+
+impl TestTermination for () {
+    fn is_success(&self) -> bool {
+        true
+    }
+}
+
+impl<T, E> TestTermination for Result<T, E> {
+    fn is_success(&self) -> bool {
+        self.is_ok()
+    }
+}
+
+impl TestArgs {
+    /// The name of the database of this test.
+    ///
+    /// The name is derived from the path of the test so the database a failed test leaves behind
+    /// can be found, and is replaced the next time the test runs.
+    pub fn database_name(&self) -> String {
+        let hash = Sha256::digest(self.test_path.as_bytes());
+
+        format!("_sqlx_test_{}", hex::encode(&hash[..10]))
+    }
+}
+
+/// Runs a test against a new database that has the migrations and fixtures of `args` applied.
+///
+/// The database is dropped once the test succeeds. When the test panics or returns an error, the
+/// database is kept for inspection.
+pub async fn run_test<DB, F, Fut>(args: TestArgs, test_fn: F) -> Fut::Output
+where
+    DB: TestSupport,
+    DB::Connection: Migrate,
+    for<'c> &'c mut DB::Connection: Executor<'c, Database = DB>,
+    F: FnOnce(Pool<DB>) -> Fut,
+    Fut: Future,
+    Fut::Output: TestTermination,
+{
+    let url = DB::test_database_url(&args)
+        .unwrap_or_else(|e| panic!("failed to determine the test database: {}", e));
+
+    let pool = setup_test_database(&args, &url)
+        .await
+        .unwrap_or_else(|e| panic!("failed to set up test database {:?}: {}", url, e));
+
+    let res = test_fn(pool.clone()).await;
+
+    if res.is_success() {
+        pool.close().await;
+
+        DB::drop_database(&url)
+            .await
+            .unwrap_or_else(|e| panic!("failed to drop test database {:?}: {}", url, e));
+    } else {
+        eprintln!("test database {:?} was kept for inspection", url);
+    }
+
+    res
+}
+
+async fn setup_test_database<DB>(args: &TestArgs, url: &str) -> Result<Pool<DB>, Error>
+where
+    DB: TestSupport,
+    DB::Connection: Migrate,
+    for<'c> &'c mut DB::Connection: Executor<'c, Database = DB>,
+{
+    // the database of a previous failed run
+    if DB::database_exists(url).await? {
+        DB::drop_database(url).await?;
+    }
+
+    DB::create_database(url).await?;
+
+    let pool = PoolOptions::<DB>::new().connect(url).await?;
+
+    if let Some(migrator) = args.migrator {
+        migrator.run(&pool).await?;
+    }
+
+    let mut conn = pool.acquire().await?;
+
+    for fixture in args.fixtures {
+        conn.execute(fixture.contents).await.map_err(|e| {
+            Error::Configuration(format!("failed to apply fixture {}: {}", fixture.path, e).into())
+        })?;
+    }
+
+    Ok(pool)
+}
+
+/// Reads the base URL of the test databases from the configured variable, which may be set in
+/// `.env` like for the query macros.
+#[cfg(any(feature = "postgres", feature = "mysql"))]
+pub(crate) fn database_url(args: &TestArgs) -> Result<String, Error> {
+    dotenv::var(args.database_url_var).map_err(|_| {
+        Error::Configuration(
+            format!(
+                "{} must be set to create the database of a test",
+                args.database_url_var
+            )
+            .into(),
+        )
+    })
+}
+
+/// Replaces the database in the path of a URL, for drivers that have a database per server.
+#[cfg(any(feature = "postgres", feature = "mysql"))]
+pub(crate) fn replace_database(url: &str, name: &str) -> Result<String, Error> {
+    let mut url = url::Url::parse(url).map_err(Error::config)?;
+    url.set_path(name);
+
+    Ok(url.into())
+}
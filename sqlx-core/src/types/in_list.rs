@@ -0,0 +1,232 @@
+#[cfg(any(feature = "mysql", feature = "sqlite"))]
+use std::borrow::Cow;
+use std::iter::FromIterator;
+use std::ops::{Deref, DerefMut};
+
+/// A list of values that is bound as one parameter per value, for `IN (?)` with MySQL and
+/// SQLite, which have no array types.
+///
+/// The placeholder the list is bound to is replaced with one placeholder per value when the
+/// query is executed:
+///
+/// ```rust,ignore
+/// // executes `SELECT * FROM users WHERE id IN (?, ?, ?)`
+/// let users = sqlx::query("SELECT * FROM users WHERE id IN (?)")
+///     .bind(sqlx::InList(vec![1, 2, 3]))
+///     .fetch_all(&mut conn)
+///     .await?;
+/// ```
+///
+/// With Postgres, bind a `Vec<T>` to `= ANY($1)` instead.
+///
+/// An empty list matches no rows with `IN` and every row with `NOT IN` in SQLite. MySQL has no
+/// empty lists, so an empty list is replaced with `NULL` there, which matches no rows with
+/// either. A list in an `Option` is bound the same, and `None` is bound as `NULL`, which also
+/// matches no rows. Other wrappers must forward `Encode::list_values` to the list, or it is bound
+/// as `NULL` as well.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct InList<T>(pub Vec<T>);
+
+impl<T> Deref for InList<T> {
+    type Target = Vec<T>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for InList<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<T> From<Vec<T>> for InList<T> {
+    fn from(values: Vec<T>) -> Self {
+        InList(values)
+    }
+}
+
+impl<T> FromIterator<T> for InList<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        InList(iter.into_iter().collect())
+    }
+}
+
+/// The placeholders of a database, for [`expand_in_lists`].
+#[cfg(any(feature = "mysql", feature = "sqlite"))]
+#[derive(Clone, Copy, PartialEq)]
+pub(crate) enum Placeholders {
+    /// `?`, bound in order. Strings may contain backslash escapes and `#` starts a comment.
+    #[cfg_attr(not(feature = "mysql"), allow(dead_code))]
+    Question,
+
+    /// `?`, bound in order, or `?NNN` and `$NNN`, bound by index.
+    #[cfg_attr(not(feature = "sqlite"), allow(dead_code))]
+    Numbered,
+}
+
+/// Replace the placeholder of each argument that is an [`InList`] with one placeholder per
+/// value, renumbering the numbered placeholders of the arguments after it.
+///
+/// `lists` holds the index of the first value of each list among all bound values, with the
+/// number of values in the list.
+#[cfg(any(feature = "mysql", feature = "sqlite"))]
+pub(crate) fn expand_in_lists<'a>(
+    sql: &'a str,
+    lists: &[(usize, usize)],
+    placeholders: Placeholders,
+) -> Cow<'a, str> {
+    if lists.is_empty() {
+        return Cow::Borrowed(sql);
+    }
+
+    // the argument of each list, with the index of its first value and its number of values;
+    // every argument before it that is not a list has a single value
+    let mut args = Vec::with_capacity(lists.len());
+    let mut list_values = 0;
+
+    for (i, &(start, len)) in lists.iter().enumerate() {
+        args.push((start + i - list_values, start, len));
+        list_values += len;
+    }
+
+    // the index of the first value of an argument
+    let value_index = |arg: usize| {
+        args.iter()
+            .take_while(|&&(list_arg, _, _)| list_arg < arg)
+            .fold(arg, |index, &(_, _, len)| index + len - 1)
+    };
+
+    let bytes = sql.as_bytes();
+    let digits_end = |start: usize| {
+        start
+            + bytes[start..]
+                .iter()
+                .take_while(|b| b.is_ascii_digit())
+                .count()
+    };
+
+    let mut expanded = String::with_capacity(sql.len() + 3 * list_values);
+    let mut next_arg = 0;
+    let mut last = 0;
+    let mut i = 0;
+
+    while i < bytes.len() {
+        let (arg, end, numbered) = match bytes[i] {
+            quote @ b'\'' | quote @ b'"' | quote @ b'`' => {
+                i += 1;
+
+                while i < bytes.len() && bytes[i] != quote {
+                    if bytes[i] == b'\\' && placeholders == Placeholders::Question {
+                        i += 1;
+                    }
+
+                    i += 1;
+                }
+
+                i += 1;
+                continue;
+            }
+
+            b'-' if bytes.get(i + 1) == Some(&b'-') => {
+                i = sql[i..].find('\n').map_or(sql.len(), |end| i + end);
+                continue;
+            }
+
+            b'#' if placeholders == Placeholders::Question => {
+                i = sql[i..].find('\n').map_or(sql.len(), |end| i + end);
+                continue;
+            }
+
+            b'/' if bytes.get(i + 1) == Some(&b'*') => {
+                i = sql[i + 2..].find("*/").map_or(sql.len(), |end| i + end + 4);
+                continue;
+            }
+
+            b'?' | b'$'
+                if placeholders == Placeholders::Numbered
+                    && matches!(bytes.get(i + 1), Some(b) if b.is_ascii_digit()) =>
+            {
+                let end = digits_end(i + 1);
+
+                match sql[i + 1..end].parse::<usize>() {
+                    Ok(n) if n > 0 => (n - 1, end, true),
+                    _ => {
+                        i = end;
+                        continue;
+                    }
+                }
+            }
+
+            b'?' => {
+                next_arg += 1;
+                (next_arg - 1, i + 1, false)
+            }
+
+            _ => {
+                i += 1;
+                continue;
+            }
+        };
+
+        expanded.push_str(&sql[last..i]);
+
+        match args.iter().find(|&&(list_arg, _, _)| list_arg == arg) {
+            Some(&(_, _, 0)) if placeholders == Placeholders::Question => {
+                expanded.push_str("NULL");
+            }
+
+            Some(&(_, start, len)) => {
+                for value in start..start + len {
+                    if value > start {
+                        expanded.push_str(", ");
+                    }
+
+                    if numbered {
+                        expanded.push_str(&format!("?{}", value + 1));
+                    } else {
+                        expanded.push('?');
+                    }
+                }
+            }
+
+            None if numbered => expanded.push_str(&format!("?{}", value_index(arg) + 1)),
+
+            None => expanded.push('?'),
+        }
+
+        last = end;
+        i = end;
+    }
+
+    expanded.push_str(&sql[last..]);
+
+    Cow::Owned(expanded)
+}
+
+#[cfg(all(test, any(feature = "mysql", feature = "sqlite")))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_expands_in_lists() {
+        assert_eq!(
+            expand_in_lists(
+                "SELECT '?', ? -- ?\nWHERE a IN (?) AND b IN (?) AND c = 'it\\'s ?' AND d = ?",
+                &[(1, 3), (4, 0)],
+                Placeholders::Question
+            ),
+            "SELECT '?', ? -- ?\nWHERE a IN (?, ?, ?) AND b IN (NULL) AND c = 'it\\'s ?' AND d = ?"
+        );
+
+        assert_eq!(
+            expand_in_lists(
+                "SELECT ?1 WHERE a IN (?2) AND b = $3 AND c IN (?4) AND d = ?1",
+                &[(1, 2), (4, 0)],
+                Placeholders::Numbered
+            ),
+            "SELECT ?1 WHERE a IN (?2, ?3) AND b = ?4 AND c IN () AND d = ?1"
+        );
+    }
+}
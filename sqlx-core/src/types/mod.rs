@@ -32,6 +32,8 @@ pub mod git2;
 #[cfg_attr(docsrs, doc(cfg(feature = "json")))]
 mod json;
 
+mod in_list;
+
 #[cfg(feature = "uuid")]
 #[cfg_attr(docsrs, doc(cfg(feature = "uuid")))]
 #[doc(no_inline)]
@@ -85,6 +87,10 @@ pub mod mac_address {
 #[cfg(feature = "json")]
 pub use json::Json;
 
+pub use in_list::InList;
+#[cfg(any(feature = "mysql", feature = "sqlite"))]
+pub(crate) use in_list::{expand_in_lists, Placeholders};
+
 /// Indicates that a SQL type is supported for a database.
 ///
 /// ## Compile-time verification
@@ -125,6 +131,10 @@ pub use json::Json;
 ///   given type is different than that of the inferred type (e.g. if you rename the above to
 ///   `VARCHAR`). Affects Postgres only.
 /// * `#[sqlx(rename_all = "<strategy>")]` on struct definition: See [`derive docs in FromRow`](crate::from_row::FromRow#rename_all)
+/// * `#[sqlx(no_pg_array)]` on struct definition: don't implement `PgHasArrayType`, which is
+///   otherwise implemented for any derived type, and for a transparent type requires the inner
+///   type to implement it as well. Needed for a wrapper of a type without an array type in
+///   Postgres, like `Vec<String>`.
 ///
 /// ### Enumeration
 ///
@@ -1,7 +1,29 @@
+use once_cell::sync::Lazy;
 use proc_macro2::Span;
+use sqlx_core::config::Config;
 use std::env;
 use std::path::{Path, PathBuf};
 
+// the configuration of the crate being compiled, from `sqlx.toml` or `[package.metadata.sqlx]`
+static CONFIG: Lazy<Result<Config, String>> = Lazy::new(|| {
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR")
+        .map_err(|_| "CARGO_MANIFEST_DIR is not set; please use Cargo to build".to_owned())?;
+
+    let manifest_dir = Path::new(&manifest_dir);
+
+    // tell the compiler to watch the config file for changes, if applicable
+    #[cfg(procmacro2_semver_exempt)]
+    if let Some(path) = manifest_dir.join(sqlx_core::config::CONFIG_FILE).to_str() {
+        proc_macro::tracked_path::path(path);
+    }
+
+    Config::read_from(manifest_dir).map_err(|e| e.to_string())
+});
+
+pub(crate) fn config() -> crate::Result<&'static Config> {
+    CONFIG.as_ref().map_err(|e| e.clone().into())
+}
+
 pub(crate) fn resolve_path(path: &str, err_span: Span) -> syn::Result<PathBuf> {
     let path = Path::new(path);
 
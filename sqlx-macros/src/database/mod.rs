@@ -7,6 +7,32 @@ pub enum ParamChecking {
     Weak,
 }
 
+/// How the positional parameters of a query are written.
+#[derive(PartialEq, Eq)]
+#[allow(dead_code)]
+pub enum ParamStyle {
+    /// `$1`, `$2`, ...
+    Dollar,
+    /// `?1`, `?2`, ...
+    NumberedQuestion,
+    /// `?`, bound in the order they appear in the query.
+    Question,
+    /// `@p1`, `@p2`, ...
+    AtP,
+}
+
+impl ParamStyle {
+    /// The placeholder for the parameter with the 1-based index `n`.
+    pub fn placeholder(&self, n: usize) -> String {
+        match self {
+            ParamStyle::Dollar => format!("${}", n),
+            ParamStyle::NumberedQuestion => format!("?{}", n),
+            ParamStyle::Question => "?".into(),
+            ParamStyle::AtP => format!("@p{}", n),
+        }
+    }
+}
+
 pub trait DatabaseExt: Database {
     const DATABASE_PATH: &'static str;
     const ROW_PATH: &'static str;
@@ -14,6 +40,8 @@ pub trait DatabaseExt: Database {
 
     const PARAM_CHECKING: ParamChecking;
 
+    const PARAM_STYLE: ParamStyle;
+
     fn db_path() -> syn::Path {
         syn::parse_str(Self::DATABASE_PATH).unwrap()
     }
@@ -35,6 +63,7 @@ macro_rules! impl_database_ext {
             $($(#[$meta:meta])? $ty:ty $(| $input:ty)?),*$(,)?
         },
         ParamChecking::$param_checking:ident,
+        ParamStyle::$param_style:ident,
         feature-types: $ty_info:ident => $get_gate:expr,
         row = $row:path,
         name = $db_name:literal
@@ -43,6 +72,7 @@ macro_rules! impl_database_ext {
             const DATABASE_PATH: &'static str = stringify!($database);
             const ROW_PATH: &'static str = stringify!($row);
             const PARAM_CHECKING: $crate::database::ParamChecking = $crate::database::ParamChecking::$param_checking;
+            const PARAM_STYLE: $crate::database::ParamStyle = $crate::database::ParamStyle::$param_style;
             const NAME: &'static str = $db_name;
 
             fn param_type_for_id(info: &Self::TypeInfo) -> Option<&'static str> {
@@ -54,6 +54,7 @@ impl_database_ext! {
         serde_json::Value,
     },
     ParamChecking::Weak,
+    ParamStyle::Question,
     feature-types: info => info.__type_feature_gate(),
     row = sqlx::mysql::MySqlRow,
     name = "MySQL"
@@ -17,6 +17,7 @@ impl_database_ext! {
         sqlx::types::chrono::DateTime<sqlx::types::chrono::Utc> | sqlx::types::chrono::DateTime<_>,
     },
     ParamChecking::Weak,
+    ParamStyle::NumberedQuestion,
     feature-types: _info => None,
     row = sqlx::sqlite::SqliteRow,
     name = "SQLite"
@@ -0,0 +1,116 @@
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{parse_quote, Data, DataStruct, DeriveInput, Fields, Index, Member};
+
+use super::attributes::parse_child_attributes;
+
+pub fn expand_derive_into_arguments(input: &DeriveInput) -> syn::Result<TokenStream> {
+    let fields = match &input.data {
+        Data::Struct(DataStruct {
+            fields: Fields::Unit,
+            ..
+        }) => {
+            return Err(syn::Error::new_spanned(
+                input,
+                "unit structs are not supported",
+            ))
+        }
+
+        Data::Struct(DataStruct { fields, .. }) => fields,
+
+        Data::Enum(_) => return Err(syn::Error::new_spanned(input, "enums are not supported")),
+
+        Data::Union(_) => return Err(syn::Error::new_spanned(input, "unions are not supported")),
+    };
+
+    let ident = &input.ident;
+
+    let generics = &input.generics;
+
+    let (_, ty_generics, _) = generics.split_for_impl();
+
+    let mut generics = generics.clone();
+    generics
+        .params
+        .insert(0, parse_quote!(DB: ::sqlx::Database));
+    generics.params.insert(0, parse_quote!('q));
+
+    let predicates = &mut generics.make_where_clause().predicates;
+
+    predicates.push(parse_quote!(Self: ::std::marker::Send));
+
+    let mut names = Vec::new();
+    let mut values = Vec::new();
+
+    for (i, field) in fields.iter().enumerate() {
+        let attributes = parse_child_attributes(&field.attrs)?;
+
+        if attributes.rename.is_some()
+            || attributes.default
+            || attributes.flatten
+            || attributes.prefix.is_some()
+            || attributes.try_from.is_some()
+        {
+            return Err(syn::Error::new_spanned(
+                field,
+                "only #[sqlx(skip)] and #[sqlx(json)] are supported by #[derive(IntoArguments)]",
+            ));
+        }
+
+        if attributes.skip {
+            continue;
+        }
+
+        let member = match &field.ident {
+            Some(ident) => Member::Named(ident.clone()),
+            None => Member::Unnamed(Index::from(i)),
+        };
+
+        let ty = &field.ty;
+
+        let (ty, value) = if attributes.json {
+            (
+                parse_quote!(::sqlx::types::Json<#ty>),
+                quote!(::sqlx::types::Json(self.#member)),
+            )
+        } else {
+            (ty.clone(), quote!(self.#member))
+        };
+
+        predicates.push(parse_quote!(
+            #ty: 'q
+                + ::std::marker::Send
+                + ::sqlx::encode::Encode<'q, DB>
+                + ::sqlx::types::Type<DB>
+        ));
+
+        names.push(format_ident!("arg{}", names.len()));
+        values.push(value);
+    }
+
+    let (impl_generics, _, where_clause) = generics.split_for_impl();
+
+    let count = names.len();
+
+    Ok(quote!(
+        #[automatically_derived]
+        impl #impl_generics ::sqlx::IntoArguments<'q, DB> for #ident #ty_generics #where_clause {
+            fn into_arguments(self) -> <DB as ::sqlx::database::HasArguments<'q>>::Arguments {
+                #(let #names = #values;)*
+
+                let mut arguments =
+                    <DB as ::sqlx::database::HasArguments<'q>>::Arguments::default();
+
+                ::sqlx::Arguments::reserve(
+                    &mut arguments,
+                    #count,
+                    0 #(+ ::sqlx::encode::Encode::<DB>::size_hint(&#names))*,
+                );
+
+                #(::sqlx::Arguments::add(&mut arguments, #names);)*
+
+                arguments
+            }
+        }
+    ))
+}
@@ -3,7 +3,9 @@ use quote::{quote, quote_spanned};
 use syn::punctuated::Punctuated;
 use syn::spanned::Spanned;
 use syn::token::Comma;
-use syn::{Attribute, DeriveInput, Field, Lit, Meta, MetaNameValue, NestedMeta, Variant};
+use syn::{
+    Attribute, DeriveInput, Field, Lit, LitStr, Meta, MetaNameValue, NestedMeta, Type, Variant,
+};
 
 macro_rules! assert_attribute {
     ($e:expr, $err:expr, $input:expr) => {
@@ -65,11 +67,20 @@ pub struct SqlxContainerAttributes {
     pub type_name: Option<TypeName>,
     pub rename_all: Option<RenameAll>,
     pub repr: Option<Ident>,
+    pub tag: Option<LitStr>,
+    pub tag_type: Option<Type>,
+    pub no_pg_array: bool,
 }
 
 pub struct SqlxChildAttributes {
     pub rename: Option<String>,
     pub default: bool,
+    pub flatten: bool,
+    pub prefix: Option<String>,
+    pub try_from: Option<Type>,
+    pub skip: bool,
+    pub json: bool,
+    pub tag: Option<Lit>,
 }
 
 pub fn parse_container_attributes(input: &[Attribute]) -> syn::Result<SqlxContainerAttributes> {
@@ -77,6 +88,9 @@ pub fn parse_container_attributes(input: &[Attribute]) -> syn::Result<SqlxContai
     let mut repr = None;
     let mut type_name = None;
     let mut rename_all = None;
+    let mut tag = None;
+    let mut tag_type = None;
+    let mut no_pg_array = None;
 
     for attr in input
         .iter()
@@ -94,6 +108,10 @@ pub fn parse_container_attributes(input: &[Attribute]) -> syn::Result<SqlxContai
                                 try_set!(transparent, true, value)
                             }
 
+                            Meta::Path(p) if p.is_ident("no_pg_array") => {
+                                try_set!(no_pg_array, true, value)
+                            }
+
                             Meta::NameValue(MetaNameValue {
                                 path,
                                 lit: Lit::Str(val),
@@ -113,6 +131,20 @@ pub fn parse_container_attributes(input: &[Attribute]) -> syn::Result<SqlxContai
                                 try_set!(rename_all, val, value)
                             }
 
+                            Meta::NameValue(MetaNameValue {
+                                path,
+                                lit: Lit::Str(val),
+                                ..
+                            }) if path.is_ident("tag") => try_set!(tag, val.clone(), value),
+
+                            Meta::NameValue(MetaNameValue {
+                                path,
+                                lit: Lit::Str(val),
+                                ..
+                            }) if path.is_ident("tag_type") => {
+                                try_set!(tag_type, val.parse()?, value)
+                            }
+
                             Meta::NameValue(MetaNameValue {
                                 path,
                                 lit: Lit::Str(val),
@@ -171,12 +203,21 @@ pub fn parse_container_attributes(input: &[Attribute]) -> syn::Result<SqlxContai
         repr,
         type_name,
         rename_all,
+        tag,
+        tag_type,
+        no_pg_array: no_pg_array.unwrap_or(false),
     })
 }
 
 pub fn parse_child_attributes(input: &[Attribute]) -> syn::Result<SqlxChildAttributes> {
     let mut rename = None;
     let mut default = false;
+    let mut flatten = false;
+    let mut prefix = None;
+    let mut try_from = None;
+    let mut skip = false;
+    let mut json = false;
+    let mut tag = None;
 
     for attr in input.iter().filter(|a| a.path.is_ident("sqlx")) {
         let meta = attr
@@ -192,7 +233,25 @@ pub fn parse_child_attributes(input: &[Attribute]) -> syn::Result<SqlxChildAttri
                             lit: Lit::Str(val),
                             ..
                         }) if path.is_ident("rename") => try_set!(rename, val.value(), value),
+                        Meta::NameValue(MetaNameValue {
+                            path,
+                            lit: Lit::Str(val),
+                            ..
+                        }) if path.is_ident("prefix") => try_set!(prefix, val.value(), value),
+                        Meta::NameValue(MetaNameValue {
+                            path,
+                            lit: Lit::Str(val),
+                            ..
+                        }) if path.is_ident("try_from") => try_set!(try_from, val.parse()?, value),
                         Meta::Path(path) if path.is_ident("default") => default = true,
+                        Meta::Path(path) if path.is_ident("flatten") => flatten = true,
+                        Meta::Path(path) if path.is_ident("skip") => skip = true,
+                        Meta::Path(path) if path.is_ident("json") => json = true,
+                        Meta::NameValue(MetaNameValue { path, lit, .. })
+                            if path.is_ident("tag") =>
+                        {
+                            try_set!(tag, lit.clone(), value)
+                        }
                         u => fail!(u, "unexpected attribute"),
                     },
                     u => fail!(u, "unexpected attribute"),
@@ -201,7 +260,16 @@ pub fn parse_child_attributes(input: &[Attribute]) -> syn::Result<SqlxChildAttri
         }
     }
 
-    Ok(SqlxChildAttributes { rename, default })
+    Ok(SqlxChildAttributes {
+        rename,
+        default,
+        flatten,
+        prefix,
+        try_from,
+        skip,
+        json,
+        tag,
+    })
 }
 
 pub fn check_transparent_attributes(
@@ -216,6 +284,18 @@ pub fn check_transparent_attributes(
         field
     );
 
+    assert_attribute!(
+        attributes.tag.is_none(),
+        "unexpected #[sqlx(tag = ..)]",
+        field
+    );
+
+    assert_attribute!(
+        attributes.tag_type.is_none(),
+        "unexpected #[sqlx(tag_type = ..)]",
+        field
+    );
+
     let ch_attributes = parse_child_attributes(&field.attrs)?;
 
     assert_attribute!(
@@ -236,6 +316,18 @@ pub fn check_enum_attributes(input: &DeriveInput) -> syn::Result<SqlxContainerAt
         input
     );
 
+    assert_attribute!(
+        attributes.tag.is_none(),
+        "unexpected #[sqlx(tag = ..)]",
+        input
+    );
+
+    assert_attribute!(
+        attributes.tag_type.is_none(),
+        "unexpected #[sqlx(tag_type = ..)]",
+        input
+    );
+
     Ok(attributes)
 }
 
@@ -297,6 +389,18 @@ pub fn check_struct_attributes<'a>(
 
     assert_attribute!(attributes.repr.is_none(), "unexpected #[repr(..)]", input);
 
+    assert_attribute!(
+        attributes.tag.is_none(),
+        "unexpected #[sqlx(tag = ..)]",
+        input
+    );
+
+    assert_attribute!(
+        attributes.tag_type.is_none(),
+        "unexpected #[sqlx(tag_type = ..)]",
+        input
+    );
+
     for field in fields {
         let attributes = parse_child_attributes(&field.attrs)?;
 
@@ -1,13 +1,15 @@
+mod arguments;
 mod attributes;
 mod decode;
 mod encode;
 mod row;
 mod r#type;
 
+pub(crate) use arguments::expand_derive_into_arguments;
 pub(crate) use decode::expand_derive_decode;
 pub(crate) use encode::expand_derive_encode;
 pub(crate) use r#type::expand_derive_type;
-pub(crate) use row::expand_derive_from_row;
+pub(crate) use row::{column_check_ident, expand_derive_from_row};
 
 use self::attributes::RenameAll;
 use heck::{CamelCase, KebabCase, MixedCase, ShoutySnakeCase, SnakeCase};
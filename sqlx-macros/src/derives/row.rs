@@ -1,12 +1,13 @@
-use proc_macro2::{Span, TokenStream};
+use proc_macro2::{Ident, Span, TokenStream};
 use quote::quote;
 use syn::{
-    parse_quote, punctuated::Punctuated, token::Comma, Data, DataStruct, DeriveInput, Field,
-    Fields, FieldsNamed, FieldsUnnamed, Lifetime, Stmt,
+    parse_quote, punctuated::Punctuated, token::Comma, Data, DataEnum, DataStruct, DeriveInput,
+    Field, Fields, FieldsNamed, FieldsUnnamed, Lifetime, PathArguments, Stmt, Type, Variant,
+    WherePredicate,
 };
 
 use super::{
-    attributes::{parse_child_attributes, parse_container_attributes},
+    attributes::{parse_child_attributes, parse_container_attributes, SqlxContainerAttributes},
     rename_all,
 };
 
@@ -30,7 +31,7 @@ pub fn expand_derive_from_row(input: &DeriveInput) -> syn::Result<TokenStream> {
             "unit structs are not supported",
         )),
 
-        Data::Enum(_) => Err(syn::Error::new_spanned(input, "enums are not supported")),
+        Data::Enum(DataEnum { variants, .. }) => expand_derive_from_row_enum(input, variants),
 
         Data::Union(_) => Err(syn::Error::new_spanned(input, "unions are not supported")),
     }
@@ -63,48 +64,24 @@ fn expand_derive_from_row_struct(
 
     predicates.push(parse_quote!(&#lifetime ::std::primitive::str: ::sqlx::ColumnIndex<R>));
 
-    for field in fields {
-        let ty = &field.ty;
-
-        predicates.push(parse_quote!(#ty: ::sqlx::decode::Decode<#lifetime, R::Database>));
-        predicates.push(parse_quote!(#ty: ::sqlx::types::Type<R::Database>));
-    }
-
-    let (impl_generics, _, where_clause) = generics.split_for_impl();
-
     let container_attributes = parse_container_attributes(&input.attrs)?;
 
-    let reads = fields.iter().filter_map(|field| -> Option<Stmt> {
-        let id = &field.ident.as_ref()?;
-        let attributes = parse_child_attributes(&field.attrs).unwrap();
-        let id_s = attributes
-            .rename
-            .or_else(|| Some(id.to_string().trim_start_matches("r#").to_owned()))
-            .map(|s| match container_attributes.rename_all {
-                Some(pattern) => rename_all(&s, pattern),
-                None => s,
-            })
-            .unwrap();
+    if let Some(tag) = &container_attributes.tag {
+        return Err(syn::Error::new_spanned(
+            tag,
+            "#[sqlx(tag = ..)] can only be used with enums",
+        ));
+    }
 
-        let ty = &field.ty;
+    let (reads, prefixed_reads) =
+        read_fields(fields, &container_attributes, &lifetime, predicates)?;
 
-        if attributes.default {
-            Some(
-                parse_quote!(let #id: #ty = row.try_get(#id_s).or_else(|e| match e {
-                ::sqlx::Error::ColumnNotFound(_) => {
-                    ::std::result::Result::Ok(Default::default())
-                },
-                e => ::std::result::Result::Err(e)
-            })?;),
-            )
-        } else {
-            Some(parse_quote!(
-                let #id: #ty = row.try_get(#id_s)?;
-            ))
-        }
-    });
+    let (impl_generics, _, where_clause) = generics.split_for_impl();
 
     let names = fields.iter().map(|field| &field.ident);
+    let prefixed_names = names.clone();
+
+    let column_checks = expand_column_checks(input, fields, &container_attributes)?;
 
     Ok(quote!(
         #[automatically_derived]
@@ -116,10 +93,504 @@ fn expand_derive_from_row_struct(
                     #(#names),*
                 })
             }
+
+            fn from_row_prefixed(
+                row: &#lifetime R,
+                prefix: &::std::primitive::str,
+            ) -> ::sqlx::Result<Self> {
+                #(#prefixed_reads)*
+
+                ::std::result::Result::Ok(#ident {
+                    #(#prefixed_names),*
+                })
+            }
+        }
+
+        #column_checks
+    ))
+}
+
+fn expand_derive_from_row_enum(
+    input: &DeriveInput,
+    variants: &Punctuated<Variant, Comma>,
+) -> syn::Result<TokenStream> {
+    let ident = &input.ident;
+
+    let container_attributes = parse_container_attributes(&input.attrs)?;
+
+    let tag =
+        match &container_attributes.tag {
+            Some(tag) => tag.value(),
+            None => return Err(syn::Error::new_spanned(
+                input,
+                "enums require #[sqlx(tag = \"..\")] naming the column that selects the variant",
+            )),
+        };
+
+    let generics = &input.generics;
+
+    let (lifetime, provided) = generics
+        .lifetimes()
+        .next()
+        .map(|def| (def.lifetime.clone(), false))
+        .unwrap_or_else(|| (Lifetime::new("'a", Span::call_site()), true));
+
+    let (_, ty_generics, _) = generics.split_for_impl();
+
+    let mut generics = generics.clone();
+    generics.params.insert(0, parse_quote!(R: ::sqlx::Row));
+
+    if provided {
+        generics.params.insert(0, parse_quote!(#lifetime));
+    }
+
+    let predicates = &mut generics.make_where_clause().predicates;
+
+    // the tag is text unless another type is given, whose values the variants must then give
+    let tag_type = container_attributes.tag_type.as_ref();
+    let tag_ty: Type = tag_type
+        .cloned()
+        .unwrap_or_else(|| parse_quote!(::std::string::String));
+
+    predicates.push(parse_quote!(&#lifetime ::std::primitive::str: ::sqlx::ColumnIndex<R>));
+    predicates.push(parse_quote!(#tag_ty: ::sqlx::decode::Decode<#lifetime, R::Database>));
+    predicates.push(parse_quote!(#tag_ty: ::sqlx::types::Type<R::Database>));
+    predicates.push(parse_quote!(#tag_ty: ::std::fmt::Debug));
+
+    let mut arms = Vec::with_capacity(variants.len());
+    let mut prefixed_arms = Vec::with_capacity(variants.len());
+
+    for variant in variants {
+        let id = &variant.ident;
+        let attributes = parse_child_attributes(&variant.attrs)?;
+
+        let tag_value =
+            match (&attributes.tag, tag_type) {
+                (Some(tag), _) => quote!(#tag),
+                (None, Some(_)) => return Err(syn::Error::new_spanned(
+                    variant,
+                    "variants require #[sqlx(tag = ..)] giving the value of the tag, as its type \
+                     is given with #[sqlx(tag_type = ..)]",
+                )),
+                (None, None) => {
+                    let tag_value = attributes
+                        .rename
+                        .unwrap_or_else(|| match container_attributes.rename_all {
+                            Some(pattern) => rename_all(&id.to_string(), pattern),
+                            None => id.to_string(),
+                        });
+
+                    quote!(#tag_value)
+                }
+            };
+
+        let (construct, prefixed_construct) = match &variant.fields {
+            Fields::Named(FieldsNamed { named, .. }) => {
+                let (reads, prefixed_reads) =
+                    read_fields(named, &container_attributes, &lifetime, predicates)?;
+
+                let names = named.iter().map(|field| &field.ident);
+                let prefixed_names = names.clone();
+
+                (
+                    quote!({
+                        #(#reads)*
+
+                        ::std::result::Result::Ok(#ident::#id { #(#names),* })
+                    }),
+                    quote!({
+                        #(#prefixed_reads)*
+
+                        ::std::result::Result::Ok(#ident::#id { #(#prefixed_names),* })
+                    }),
+                )
+            }
+
+            // a newtype variant is read from the whole row, like a flattened field
+            Fields::Unnamed(FieldsUnnamed { unnamed, .. }) if unnamed.len() == 1 => {
+                let ty = &unnamed[0].ty;
+
+                predicates.push(parse_quote!(#ty: ::sqlx::FromRow<#lifetime, R>));
+
+                (
+                    quote!(<#ty as ::sqlx::FromRow<#lifetime, R>>::from_row(row).map(#ident::#id)),
+                    quote!(
+                        <#ty as ::sqlx::FromRow<#lifetime, R>>::from_row_prefixed(row, prefix)
+                            .map(#ident::#id)
+                    ),
+                )
+            }
+
+            Fields::Unnamed(_) => {
+                return Err(syn::Error::new_spanned(
+                    variant,
+                    "tuple variants must have exactly one field",
+                ))
+            }
+
+            Fields::Unit => (
+                quote!(::std::result::Result::Ok(#ident::#id)),
+                quote!(::std::result::Result::Ok(#ident::#id)),
+            ),
+        };
+
+        arms.push(quote!(#tag_value => #construct,));
+        prefixed_arms.push(quote!(#tag_value => #prefixed_construct,));
+    }
+
+    let (impl_generics, _, where_clause) = generics.split_for_impl();
+
+    let read_tag = |column: TokenStream| {
+        let unknown = format!("no variant of `{}` has the tag {{:?}}", ident);
+
+        quote!(
+            let tag: #tag_ty = row.try_get(#column)?;
+
+            let unknown_tag = || ::sqlx::Error::ColumnDecode {
+                index: ::std::format!("{:?}", #column),
+                source: ::std::format!(#unknown, tag).into(),
+            };
+        )
+    };
+
+    // a string tag is matched against the string literals of the variants
+    let tag_scrutinee = match tag_type {
+        Some(_) => quote!(tag),
+        None => quote!(&*tag),
+    };
+
+    let read_tag_prefixed = read_tag(quote!(::sqlx::PrefixedColumn {
+        prefix,
+        name: #tag,
+    }));
+    let read_tag = read_tag(quote!(#tag));
+
+    Ok(quote!(
+        #[automatically_derived]
+        impl #impl_generics ::sqlx::FromRow<#lifetime, R> for #ident #ty_generics #where_clause {
+            fn from_row(row: &#lifetime R) -> ::sqlx::Result<Self> {
+                #read_tag
+
+                match #tag_scrutinee {
+                    #(#arms)*
+                    _ => ::std::result::Result::Err(unknown_tag()),
+                }
+            }
+
+            fn from_row_prefixed(
+                row: &#lifetime R,
+                prefix: &::std::primitive::str,
+            ) -> ::sqlx::Result<Self> {
+                #read_tag_prefixed
+
+                match #tag_scrutinee {
+                    #(#prefixed_arms)*
+                    _ => ::std::result::Result::Err(unknown_tag()),
+                }
+            }
         }
     ))
 }
 
+/// The statements reading each named field into a variable of the same name, from its column and
+/// from its prefixed column in `from_row_prefixed()`, adding the bounds they need to `predicates`.
+fn read_fields(
+    fields: &Punctuated<Field, Comma>,
+    container_attributes: &SqlxContainerAttributes,
+    lifetime: &Lifetime,
+    predicates: &mut Punctuated<WherePredicate, Comma>,
+) -> syn::Result<(Vec<Stmt>, Vec<Stmt>)> {
+    let mut reads = Vec::with_capacity(fields.len());
+    let mut prefixed_reads = Vec::with_capacity(fields.len());
+
+    for field in fields {
+        let id = match &field.ident {
+            Some(id) => id,
+            None => continue,
+        };
+
+        let ty = &field.ty;
+        let attributes = parse_child_attributes(&field.attrs)?;
+
+        if attributes.skip {
+            let read: Stmt = parse_quote!(let #id: #ty = ::std::default::Default::default(););
+
+            reads.push(read.clone());
+            prefixed_reads.push(read);
+
+            continue;
+        }
+
+        let (read, prefixed_read) = if attributes.flatten {
+            if attributes.rename.is_some() {
+                return Err(syn::Error::new_spanned(
+                    field,
+                    "#[sqlx(rename = ..)] cannot be used with #[sqlx(flatten)]",
+                ));
+            }
+
+            if attributes.try_from.is_some() {
+                return Err(syn::Error::new_spanned(
+                    field,
+                    "#[sqlx(try_from = ..)] cannot be used with #[sqlx(flatten)]",
+                ));
+            }
+
+            if attributes.json {
+                return Err(syn::Error::new_spanned(
+                    field,
+                    "#[sqlx(json)] cannot be used with #[sqlx(flatten)]",
+                ));
+            }
+
+            predicates.push(parse_quote!(#ty: ::sqlx::FromRow<#lifetime, R>));
+
+            let read = match &attributes.prefix {
+                Some(prefix) => {
+                    quote!(<#ty as ::sqlx::FromRow<#lifetime, R>>::from_row_prefixed(row, #prefix))
+                }
+                None => quote!(<#ty as ::sqlx::FromRow<#lifetime, R>>::from_row(row)),
+            };
+
+            let prefix = attributes.prefix.unwrap_or_default();
+
+            (
+                read,
+                quote!(<#ty as ::sqlx::FromRow<#lifetime, R>>::from_row_prefixed(
+                    row,
+                    &[prefix, #prefix].concat(),
+                )),
+            )
+        } else {
+            if attributes.prefix.is_some() {
+                return Err(syn::Error::new_spanned(
+                    field,
+                    "#[sqlx(prefix = ..)] can only be used with #[sqlx(flatten)]",
+                ));
+            }
+
+            if attributes.json && attributes.try_from.is_some() {
+                return Err(syn::Error::new_spanned(
+                    field,
+                    "#[sqlx(try_from = ..)] cannot be used with #[sqlx(json)]",
+                ));
+            }
+
+            // an optional field reads `NULL` as `None` instead of decoding it as JSON, like the
+            // `"col?: json"` override of the query macros, and a JSON `null` as well
+            let optional = is_option(ty);
+
+            let json: Option<Type> = match optional {
+                _ if !attributes.json => None,
+                true => Some(parse_quote!(::std::option::Option<::sqlx::types::Json<#ty>>)),
+                false => Some(parse_quote!(::sqlx::types::Json<#ty>)),
+            };
+
+            // the type the column is decoded as
+            let decode_ty = json.as_ref().or(attributes.try_from.as_ref()).unwrap_or(ty);
+
+            predicates.push(parse_quote!(
+                #decode_ty: ::sqlx::decode::Decode<#lifetime, R::Database>
+            ));
+            predicates.push(parse_quote!(#decode_ty: ::sqlx::types::Type<R::Database>));
+
+            if let Some(try_from) = &attributes.try_from {
+                predicates.push(parse_quote!(#ty: ::std::convert::TryFrom<#try_from>));
+                predicates.push(parse_quote!(
+                    <#ty as ::std::convert::TryFrom<#try_from>>::Error:
+                        ::std::convert::Into<::sqlx::error::BoxDynError>
+                ));
+            }
+
+            let try_from = attributes.try_from;
+
+            let id_s = column_name(id, attributes.rename, container_attributes);
+
+            let read_column = |column: TokenStream| match &try_from {
+                Some(try_from) => quote!(row.try_get::<#try_from, _>(#column).and_then(|value| {
+                    <#ty as ::std::convert::TryFrom<#try_from>>::try_from(value).map_err(|e| {
+                        ::sqlx::Error::ColumnDecode {
+                            index: ::std::format!("{:?}", #column),
+                            source: ::std::convert::Into::into(e),
+                        }
+                    })
+                })),
+                None if json.is_some() && optional => quote!(
+                    row.try_get::<#json, _>(#column).map(|json| json.and_then(|json| json.0))
+                ),
+                None if json.is_some() => {
+                    quote!(row.try_get::<#json, _>(#column).map(|json| json.0))
+                }
+                None => quote!(row.try_get(#column)),
+            };
+
+            (
+                read_column(quote!(#id_s)),
+                read_column(quote!(::sqlx::PrefixedColumn {
+                    prefix,
+                    name: #id_s,
+                })),
+            )
+        };
+
+        let default = attributes.default;
+        let read_field = |read: TokenStream| -> Stmt {
+            if default {
+                parse_quote!(let #id: #ty = #read.or_else(|e| match e {
+                    ::sqlx::Error::ColumnNotFound(_) => {
+                        ::std::result::Result::Ok(Default::default())
+                    },
+                    e => ::std::result::Result::Err(e)
+                })?;)
+            } else {
+                parse_quote!(
+                    let #id: #ty = #read?;
+                )
+            }
+        };
+
+        reads.push(read_field(read));
+        prefixed_reads.push(read_field(prefixed_read));
+    }
+
+    Ok((reads, prefixed_reads))
+}
+
+/// Whether a field is of type `Option<T>`.
+fn is_option(ty: &Type) -> bool {
+    let segment = match ty {
+        Type::Path(path) if path.qself.is_none() => path.path.segments.last(),
+        _ => None,
+    };
+
+    matches!(
+        segment,
+        Some(segment) if segment.ident == "Option"
+            && matches!(segment.arguments, PathArguments::AngleBracketed(_))
+    )
+}
+
+/// The name of the column a field is read from.
+fn column_name(
+    id: &Ident,
+    rename: Option<String>,
+    container_attributes: &SqlxContainerAttributes,
+) -> String {
+    let id_s = rename.unwrap_or_else(|| id.to_string().trim_start_matches("r#").to_owned());
+
+    match container_attributes.rename_all {
+        Some(pattern) => rename_all(&id_s, pattern),
+        None => id_s,
+    }
+}
+
+/// The name of the function that `query_as_from_row!()` calls to check the type of a column.
+pub(crate) fn column_check_ident(column: &str) -> Ident {
+    let mut ident = String::from("__sqlx_column_");
+
+    for c in column.chars() {
+        if c.is_ascii_alphanumeric() || c == '_' {
+            ident.push(c);
+        } else {
+            ident.push_str(&format!("_x{:x}_", u32::from(c)));
+        }
+    }
+
+    Ident::new(&ident, Span::call_site())
+}
+
+/// The functions `query_as_from_row!()` checks the types of columns with, one for each column
+/// read by a field, taking the type of the column as a type parameter. They are defined on a type
+/// that can only be named through the `FromRowColumns` impl of the struct.
+fn expand_column_checks(
+    input: &DeriveInput,
+    fields: &Punctuated<Field, Comma>,
+    container_attributes: &SqlxContainerAttributes,
+) -> syn::Result<TokenStream> {
+    let ident = &input.ident;
+
+    let mut checked = Vec::with_capacity(fields.len());
+    let mut checks = Vec::with_capacity(fields.len());
+    let mut flattened = false;
+
+    for field in fields {
+        let id = match &field.ident {
+            Some(id) => id,
+            None => continue,
+        };
+
+        let attributes = parse_child_attributes(&field.attrs)?;
+
+        if attributes.skip {
+            continue;
+        }
+
+        if attributes.flatten {
+            flattened = true;
+            continue;
+        }
+
+        let check = column_check_ident(&column_name(id, attributes.rename, container_attributes));
+
+        // fields reading the same column would define the same function
+        if checked.contains(&check) {
+            continue;
+        }
+
+        // the type of a JSON column says nothing about the type of the field
+        let bound = if attributes.json {
+            None
+        } else {
+            let ty = attributes.try_from.as_ref().unwrap_or(&field.ty);
+
+            Some(quote!(where #ty: ::sqlx::ty_match::FieldForColumn<__Column>))
+        };
+
+        checks.push(quote!(
+            #[allow(dead_code, non_snake_case)]
+            pub fn #check<__Column>() #bound {}
+        ));
+
+        checked.push(check);
+    }
+
+    let generics = &input.generics;
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let unchecked = if flattened {
+        Some(quote!(
+            impl #impl_generics ::sqlx::ty_match::UncheckedColumns
+                for Columns #ty_generics #where_clause
+            {
+            }
+        ))
+    } else {
+        None
+    };
+
+    // `pub` in an unnamed constant so it can be the associated type of a public impl
+    Ok(quote!(
+        const _: () = {
+            pub struct Columns #generics (
+                ::std::marker::PhantomData<fn() -> #ident #ty_generics>
+            ) #where_clause;
+
+            #[automatically_derived]
+            impl #impl_generics ::sqlx::ty_match::FromRowColumns
+                for #ident #ty_generics #where_clause
+            {
+                type Columns = Columns #ty_generics;
+            }
+
+            impl #impl_generics Columns #ty_generics #where_clause {
+                #(#checks)*
+            }
+
+            #unchecked
+        };
+    ))
+}
+
 fn expand_derive_from_row_struct_unnamed(
     input: &DeriveInput,
     fields: &Punctuated<Field, Comma>,
@@ -70,7 +70,7 @@ fn expand_derive_has_sql_type_transparent(
 
         let (impl_generics, _, where_clause) = generics.split_for_impl();
 
-        return Ok(quote!(
+        let mut tts = quote!(
             #[automatically_derived]
             impl #impl_generics ::sqlx::Type< DB > for #ident #ty_generics #where_clause {
                 fn type_info() -> DB::TypeInfo {
@@ -81,7 +81,34 @@ fn expand_derive_has_sql_type_transparent(
                     <#ty as ::sqlx::Type<DB>>::compatible(ty)
                 }
             }
-        ));
+        );
+
+        // arrays of the inner type; opted out of with `#[sqlx(no_pg_array)]` if the inner type
+        // has no array type in Postgres, as the bound can't hold for a non-generic wrapper
+        if cfg!(feature = "postgres") && !attr.no_pg_array {
+            let mut generics = input.generics.clone();
+            generics
+                .make_where_clause()
+                .predicates
+                .push(parse_quote!(#ty: ::sqlx::postgres::PgHasArrayType));
+
+            let (impl_generics, _, where_clause) = generics.split_for_impl();
+
+            tts.extend(quote!(
+                #[automatically_derived]
+                impl #impl_generics ::sqlx::postgres::PgHasArrayType for #ident #ty_generics #where_clause {
+                    fn array_type_info() -> ::sqlx::postgres::PgTypeInfo {
+                        <#ty as ::sqlx::postgres::PgHasArrayType>::array_type_info()
+                    }
+
+                    fn array_compatible(ty: &::sqlx::postgres::PgTypeInfo) -> ::std::primitive::bool {
+                        <#ty as ::sqlx::postgres::PgHasArrayType>::array_compatible(ty)
+                    }
+                }
+            ));
+        }
+
+        return Ok(tts);
     }
 
     let mut tts = TokenStream::new();
@@ -99,6 +126,19 @@ fn expand_derive_has_sql_type_transparent(
         ));
     }
 
+    if cfg!(feature = "postgres") && !attr.no_pg_array {
+        let array_ty_name = array_type_name(ident, attr.type_name.as_ref());
+
+        tts.extend(quote!(
+            #[automatically_derived]
+            impl ::sqlx::postgres::PgHasArrayType for #ident #ty_generics {
+                fn array_type_info() -> ::sqlx::postgres::PgTypeInfo {
+                    ::sqlx::postgres::PgTypeInfo::with_name(#array_ty_name)
+                }
+            }
+        ));
+    }
+
     Ok(tts)
 }
 
@@ -109,7 +149,7 @@ fn expand_derive_has_sql_type_weak_enum(
     let attr = check_weak_enum_attributes(input, variants)?;
     let repr = attr.repr.unwrap();
     let ident = &input.ident;
-    let ts = quote!(
+    let mut ts = quote!(
         #[automatically_derived]
         impl<DB: ::sqlx::Database> ::sqlx::Type<DB> for #ident
         where
@@ -125,6 +165,24 @@ fn expand_derive_has_sql_type_weak_enum(
         }
     );
 
+    // arrays of the representation, for those that have an array type in Postgres
+    let has_pg_array = ["i8", "i16", "i32", "i64"].iter().any(|ty| repr == ty);
+
+    if cfg!(feature = "postgres") && has_pg_array && !attr.no_pg_array {
+        ts.extend(quote!(
+            #[automatically_derived]
+            impl ::sqlx::postgres::PgHasArrayType for #ident {
+                fn array_type_info() -> ::sqlx::postgres::PgTypeInfo {
+                    <#repr as ::sqlx::postgres::PgHasArrayType>::array_type_info()
+                }
+
+                fn array_compatible(ty: &::sqlx::postgres::PgTypeInfo) -> ::std::primitive::bool {
+                    <#repr as ::sqlx::postgres::PgHasArrayType>::array_compatible(ty)
+                }
+            }
+        ));
+    }
+
     Ok(ts)
 }
 
@@ -165,6 +223,19 @@ fn expand_derive_has_sql_type_strong_enum(
         ));
     }
 
+    if cfg!(feature = "postgres") && !attributes.no_pg_array {
+        let array_ty_name = array_type_name(ident, attributes.type_name.as_ref());
+
+        tts.extend(quote!(
+            #[automatically_derived]
+            impl ::sqlx::postgres::PgHasArrayType for #ident {
+                fn array_type_info() -> ::sqlx::postgres::PgTypeInfo {
+                    ::sqlx::postgres::PgTypeInfo::with_name(#array_ty_name)
+                }
+            }
+        ));
+    }
+
     if cfg!(feature = "sqlite") {
         tts.extend(quote!(
             #[automatically_derived]
@@ -205,9 +276,33 @@ fn expand_derive_has_sql_type_struct(
         ));
     }
 
+    if cfg!(feature = "postgres") && !attributes.no_pg_array {
+        let array_ty_name = array_type_name(ident, attributes.type_name.as_ref());
+
+        tts.extend(quote!(
+            #[automatically_derived]
+            impl ::sqlx::postgres::PgHasArrayType for #ident {
+                fn array_type_info() -> ::sqlx::postgres::PgTypeInfo {
+                    ::sqlx::postgres::PgTypeInfo::with_name(#array_ty_name)
+                }
+            }
+        ));
+    }
+
     Ok(tts)
 }
 
+/// The name of the array type of a Postgres type, which is prefixed with an underscore.
+fn array_type_name(ident: &Ident, explicit_name: Option<&TypeName>) -> String {
+    let name = explicit_name.map_or_else(|| ident.to_string(), |tn| tn.val.clone());
+
+    // the underscore goes after the schema of a qualified name
+    match name.rfind('.') {
+        Some(i) => format!("{}_{}", &name[..=i], &name[i + 1..]),
+        None => format!("_{}", name),
+    }
+}
+
 fn type_name(ident: &Ident, explicit_name: Option<&TypeName>) -> TokenStream {
     explicit_name.map(|tn| tn.get()).unwrap_or_else(|| {
         let s = ident.to_string();
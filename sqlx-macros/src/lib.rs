@@ -20,6 +20,7 @@ mod common;
 mod database;
 mod derives;
 mod query;
+mod test_attr;
 
 #[cfg(feature = "migrate")]
 mod migrate;
@@ -78,13 +79,29 @@ pub fn derive_from_row(input: TokenStream) -> TokenStream {
     }
 }
 
+#[proc_macro_derive(IntoArguments, attributes(sqlx))]
+pub fn derive_into_arguments(input: TokenStream) -> TokenStream {
+    let input = syn::parse_macro_input!(input as syn::DeriveInput);
+
+    match derives::expand_derive_into_arguments(&input) {
+        Ok(ts) => ts.into(),
+        Err(e) => e.to_compile_error().into(),
+    }
+}
+
 #[cfg(feature = "migrate")]
 #[proc_macro]
 pub fn migrate(input: TokenStream) -> TokenStream {
     use syn::LitStr;
 
-    let input = syn::parse_macro_input!(input as LitStr);
-    match migrate::expand_migrator_from_dir(input) {
+    let result = if input.is_empty() {
+        migrate::expand_migrator_from_config()
+    } else {
+        let input = syn::parse_macro_input!(input as LitStr);
+        migrate::expand_migrator_from_dir(input)
+    };
+
+    match result {
         Ok(ts) => ts.into(),
         Err(e) => {
             if let Some(parse_err) = e.downcast_ref::<syn::Error>() {
@@ -97,49 +114,20 @@ pub fn migrate(input: TokenStream) -> TokenStream {
     }
 }
 
-#[doc(hidden)]
 #[proc_macro_attribute]
-pub fn test(_attr: TokenStream, input: TokenStream) -> TokenStream {
+pub fn test(args: TokenStream, input: TokenStream) -> TokenStream {
+    let args = syn::parse_macro_input!(args as syn::AttributeArgs);
     let input = syn::parse_macro_input!(input as syn::ItemFn);
 
-    let ret = &input.sig.output;
-    let name = &input.sig.ident;
-    let body = &input.block;
-    let attrs = &input.attrs;
-
-    let result = if cfg!(feature = "_rt-tokio") {
-        quote! {
-            #[test]
-            #(#attrs)*
-            fn #name() #ret {
-                ::sqlx_rt::tokio::runtime::Builder::new_multi_thread()
-                    .enable_io()
-                    .enable_time()
-                    .build()
-                    .unwrap()
-                    .block_on(async { #body })
-            }
-        }
-    } else if cfg!(feature = "_rt-async-std") {
-        quote! {
-            #[test]
-            #(#attrs)*
-            fn #name() #ret {
-                ::sqlx_rt::async_std::task::block_on(async { #body })
-            }
-        }
-    } else if cfg!(feature = "_rt-actix") {
-        quote! {
-            #[test]
-            #(#attrs)*
-            fn #name() #ret {
-                ::sqlx_rt::actix_rt::System::new()
-                    .block_on(async { #body })
+    match test_attr::expand(args, input) {
+        Ok(ts) => ts.into(),
+        Err(e) => {
+            if let Some(parse_err) = e.downcast_ref::<syn::Error>() {
+                parse_err.to_compile_error().into()
+            } else {
+                let msg = e.to_string();
+                quote!(::std::compile_error!(#msg)).into()
             }
         }
-    } else {
-        panic!("one of 'runtime-actix', 'runtime-async-std' or 'runtime-tokio' features must be enabled");
-    };
-
-    result.into()
+    }
 }
@@ -3,6 +3,7 @@ use quote::{quote, ToTokens, TokenStreamExt};
 use sha2::{Digest, Sha384};
 use sqlx_core::migrate::MigrationType;
 use std::fs;
+use std::path::Path;
 use syn::LitStr;
 
 pub struct QuotedMigrationType(MigrationType);
@@ -25,6 +26,8 @@ struct QuotedMigration {
     description: String,
     migration_type: QuotedMigrationType,
     path: String,
+    #[cfg(feature = "sqlite")]
+    sql: String,
     checksum: Vec<u8>,
 }
 
@@ -36,6 +39,7 @@ impl ToTokens for QuotedMigration {
             migration_type,
             path,
             checksum,
+            ..
         } = &self;
 
         let ts = quote! {
@@ -55,12 +59,100 @@ impl ToTokens for QuotedMigration {
     }
 }
 
-// mostly copied from sqlx-core/src/migrate/source.rs
 pub(crate) fn expand_migrator_from_dir(dir: LitStr) -> crate::Result<TokenStream> {
     let path = crate::common::resolve_path(&dir.value(), dir.span())?;
+
+    expand_migrator(&path)
+}
+
+// the migrations directory set in the configuration of the crate, `migrations` by default
+pub(crate) fn expand_migrator_from_config() -> crate::Result<TokenStream> {
+    let dir = crate::common::config()?.migrate.migrations_dir();
+    let manifest_dir = std::env::var("CARGO_MANIFEST_DIR")
+        .map_err(|_| "CARGO_MANIFEST_DIR is not set; please use Cargo to build")?;
+
+    expand_migrator(&Path::new(&manifest_dir).join(dir))
+}
+
+fn expand_migrator(path: &Path) -> crate::Result<TokenStream> {
+    let migrations = read_migrations(path)?;
+    let table_name = crate::common::config()?.migrate.table_name();
+
+    #[cfg(any(sqlx_macros_unstable, procmacro2_semver_exempt))]
+    {
+        let path = path.canonicalize()?;
+        let path = path.to_str().ok_or_else(|| {
+            format!(
+                "migration directory path cannot be represented as a string: {:?}",
+                path
+            )
+        })?;
+
+        proc_macro::tracked_path::path(path);
+    }
+
+    Ok(quote! {
+        ::sqlx::migrate::Migrator::with_table_name(
+            ::std::borrow::Cow::Borrowed(&[
+                #(#migrations),*
+            ]),
+            false,
+            #table_name,
+        )
+    })
+}
+
+/// Apply the migrations in a directory to a new in-memory SQLite database.
+#[cfg(feature = "sqlite")]
+pub(crate) async fn migrated_sqlite(
+    path: &Path,
+) -> crate::Result<sqlx_core::sqlite::SqliteConnection> {
+    use sqlx_core::connection::Connection;
+    use sqlx_core::migrate::{Migrate, Migration};
+    use sqlx_core::sqlite::SqliteConnection;
+    use std::borrow::Cow;
+
+    if !path.is_dir() {
+        return Err(format!(
+            "migrations directory {} to check queries against SQLite with does not exist",
+            path.display()
+        )
+        .into());
+    }
+
+    let mut conn = SqliteConnection::connect("sqlite::memory:").await?;
+    let table_name = crate::common::config()?.migrate.table_name();
+
+    conn.ensure_migrations_table_in(table_name).await?;
+
+    for migration in read_migrations(path)? {
+        if migration.migration_type.0.is_down_migration() {
+            continue;
+        }
+
+        let migration = Migration::new(
+            migration.version,
+            Cow::Owned(migration.description),
+            migration.migration_type.0,
+            Cow::Owned(migration.sql),
+        );
+
+        conn.apply_in(table_name, &migration).await.map_err(|e| {
+            format!(
+                "failed to apply migration {} to the SQLite database for checking queries: {}",
+                migration.version, e
+            )
+        })?;
+    }
+
+    Ok(conn)
+}
+
+// mostly copied from sqlx-core/src/migrate/source.rs
+fn read_migrations(path: &Path) -> crate::Result<Vec<QuotedMigration>> {
     let mut migrations = Vec::new();
 
-    for entry in fs::read_dir(&path)? {
+    for entry in fs::read_dir(path)? {
         let entry = entry?;
         if !fs::metadata(entry.path())?.is_file() {
             // not a file; ignore
@@ -107,6 +199,8 @@ pub(crate) fn expand_migrator_from_dir(dir: LitStr) -> crate::Result<TokenStream
             description,
             migration_type: QuotedMigrationType(migration_type),
             path,
+            #[cfg(feature = "sqlite")]
+            sql,
             checksum,
         })
     }
@@ -114,25 +208,5 @@ pub(crate) fn expand_migrator_from_dir(dir: LitStr) -> crate::Result<TokenStream
     // ensure that we are sorted by `VERSION ASC`
     migrations.sort_by_key(|m| m.version);
 
-    #[cfg(any(sqlx_macros_unstable, procmacro2_semver_exempt))]
-    {
-        let path = path.canonicalize()?;
-        let path = path.to_str().ok_or_else(|| {
-            format!(
-                "migration directory path cannot be represented as a string: {:?}",
-                path
-            )
-        })?;
-
-        proc_macro::tracked_path::path(path);
-    }
-
-    Ok(quote! {
-        ::sqlx::migrate::Migrator {
-            migrations: ::std::borrow::Cow::Borrowed(&[
-                #(#migrations),*
-            ]),
-            ignore_missing: false,
-        }
-    })
+    Ok(migrations)
 }
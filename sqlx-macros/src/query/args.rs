@@ -1,11 +1,17 @@
-use crate::database::DatabaseExt;
+use std::borrow::Cow;
+use std::ops::Range;
+
+use crate::database::{DatabaseExt, ParamStyle};
 use crate::query::QueryMacroInput;
 use either::Either;
-use proc_macro2::TokenStream;
-use quote::{format_ident, quote, quote_spanned};
+use proc_macro2::{Ident, Span, TokenStream};
+use quote::{format_ident, quote, quote_spanned, ToTokens};
 use sqlx_core::describe::Describe;
 use syn::spanned::Spanned;
-use syn::{Expr, ExprCast, ExprGroup, ExprType, Type};
+use syn::{
+    parse_quote, Expr, ExprAssign, ExprCast, ExprGroup, ExprPath, ExprRange, ExprType, RangeLimits,
+    Type,
+};
 
 /// Returns a tokenstream which typechecks the arguments passed to the macro
 /// and binds them to `DB::Arguments` with the ident `query_args`.
@@ -28,10 +34,28 @@ pub fn quote_args<DB: DatabaseExt>(
     let arg_name = &arg_names;
     let arg_expr = input.arg_exprs.iter().cloned().map(strip_wildcard);
 
+    // `..value` is evaluated once, ahead of the arguments its fields are taken from
+    let fields_of = input.fields_of.as_ref().map(|fields_of| {
+        let ident = fields_of_ident();
+        // unused if every named parameter is given an argument
+        quote! {
+            #[allow(unused_variables)]
+            let #ident = &(#fields_of);
+        }
+    });
+
     let arg_bindings = quote! {
+        #fields_of
         #(let #arg_name = &(#arg_expr);)*
     };
 
+    // a named parameter that appears more than once is bound once per appearance with `?`
+    let bound_args = bound_args::<DB>(input);
+    let bound_name = bound_args
+        .iter()
+        .map(|&i| &arg_names[i])
+        .collect::<Vec<_>>();
+
     let args_check = match info.parameters() {
         None | Some(Either::Right(_)) => {
             // all we can do is check arity which we did
@@ -46,31 +70,34 @@ pub fn quote_args<DB: DatabaseExt>(
         Some(Either::Left(params)) => {
             params
                 .iter()
-                .zip(arg_names.iter().zip(&input.arg_exprs))
+                .zip(bound_args.iter().map(|&i| (&arg_names[i], &input.arg_exprs[i])))
                 .enumerate()
                 .map(|(i, (param_ty, (name, expr)))| -> crate::Result<_> {
                     let param_ty = match get_type_override(expr) {
                         // cast or type ascription will fail to compile if the type does not match
                         // and we strip casts to wildcard
                         Some(_) => return Ok(quote!()),
-                        None => {
-                            DB::param_type_for_id(&param_ty)
-                                .ok_or_else(|| {
-                                    if let Some(feature_gate) = <DB as DatabaseExt>::get_feature_gate(&param_ty) {
-                                        format!(
-                                            "optional feature `{}` required for type {} of param #{}",
-                                            feature_gate,
-                                            param_ty,
-                                            i + 1,
-                                        )
-                                    } else {
-                                        format!("unsupported type {} for param #{}", param_ty, i + 1)
-                                    }
-                                })?
-                                .parse::<TokenStream>()
-                                .map_err(|_| format!("Rust type mapping for {} not parsable", param_ty))?
-
-                        }
+                        None => match super::output::type_override(param_ty)? {
+                            // a type configured for the SQL type takes precedence
+                            Some(ty) => ty.to_token_stream(),
+                            None => {
+                                DB::param_type_for_id(param_ty)
+                                    .ok_or_else(|| {
+                                        if let Some(feature_gate) = <DB as DatabaseExt>::get_feature_gate(param_ty) {
+                                            format!(
+                                                "optional feature `{}` required for type {} of param #{}",
+                                                feature_gate,
+                                                param_ty,
+                                                i + 1,
+                                            )
+                                        } else {
+                                            format!("unsupported type {} for param #{}", param_ty, i + 1)
+                                        }
+                                    })?
+                                    .parse::<TokenStream>()
+                                    .map_err(|_| format!("Rust type mapping for {} not parsable", param_ty))?
+                            }
+                        },
                     };
 
                     Ok(quote_spanned!(expr.span() =>
@@ -98,7 +125,7 @@ pub fn quote_args<DB: DatabaseExt>(
         }
     };
 
-    let args_count = input.arg_exprs.len();
+    let args_count = bound_args.len();
 
     Ok(quote! {
         #arg_bindings
@@ -108,12 +135,359 @@ pub fn quote_args<DB: DatabaseExt>(
         let mut query_args = <#db_path as ::sqlx::database::HasArguments>::Arguments::default();
         query_args.reserve(
             #args_count,
-            0 #(+ ::sqlx::encode::Encode::<#db_path>::size_hint(#arg_name))*
+            0 #(+ ::sqlx::encode::Encode::<#db_path>::size_hint(#bound_name))*
         );
-        #(query_args.add(#arg_name);)*
+        #(query_args.add(#bound_name);)*
     })
 }
 
+/// A named parameter like `:user_id` or `$user_id` in a query.
+struct NamedParam<'a> {
+    name: &'a str,
+    /// The range of the parameter in the query, including the leading `:` or `$`.
+    range: Range<usize>,
+}
+
+fn is_ident_start(b: u8) -> bool {
+    b.is_ascii_alphabetic() || b == b'_'
+}
+
+fn is_ident(b: u8) -> bool {
+    b.is_ascii_alphanumeric() || b == b'_'
+}
+
+/// Whether a backslash escapes the character after it in the string literal or quoted identifier
+/// starting at `start`: in every string with MySQL, and in `E'...'` strings with Postgres.
+fn backslash_escapes<DB: DatabaseExt>(bytes: &[u8], start: usize) -> bool {
+    match DB::PARAM_STYLE {
+        ParamStyle::Question => bytes[start] != b'`',
+        ParamStyle::Dollar => {
+            bytes[start] == b'\''
+                && start > 0
+                && matches!(bytes[start - 1], b'E' | b'e')
+                && (start == 1 || !is_ident(bytes[start - 2]))
+        }
+        _ => false,
+    }
+}
+
+/// Find the named parameters of a query for `DB`.
+fn named_params<DB: DatabaseExt>(sql: &str) -> Vec<NamedParam<'_>> {
+    scan_params::<DB>(sql).0
+}
+
+/// The first positional parameter of a query for `DB`, like `$1`, `?` or `?1`.
+fn positional_param<DB: DatabaseExt>(sql: &str) -> Option<&str> {
+    scan_params::<DB>(sql).1
+}
+
+/// Find the named parameters and the first positional parameter of a query for `DB`, skipping
+/// string literals, quoted identifiers and comments. `::` (a Postgres cast), `$tag$` (a Postgres
+/// dollar-quoted string) and `$` or `:` within an identifier are not parameters, nor is `?` with
+/// Postgres, where it is a JSON operator.
+fn scan_params<DB: DatabaseExt>(sql: &str) -> (Vec<NamedParam<'_>>, Option<&str>) {
+    let bytes = sql.as_bytes();
+
+    // the end of the identifier starting at `start`
+    let ident_end =
+        |start: usize| start + bytes[start..].iter().take_while(|&&b| is_ident(b)).count();
+
+    // the index after the first occurrence of `needle` at or after `start`
+    let skip_past = |start: usize, needle: &str| {
+        sql[start..]
+            .find(needle)
+            .map_or(sql.len(), |i| start + i + needle.len())
+    };
+
+    // the end of the number starting at `start`
+    let number_end = |start: usize| {
+        start
+            + bytes[start..]
+                .iter()
+                .take_while(|b| b.is_ascii_digit())
+                .count()
+    };
+
+    let mut params = Vec::new();
+    let mut positional = None;
+    let mut i = 0;
+
+    while i < bytes.len() {
+        match bytes[i] {
+            quote @ b'\'' | quote @ b'"' | quote @ b'`' => {
+                let escapes = backslash_escapes::<DB>(bytes, i);
+
+                // an escaped quote is doubled, which just starts the literal over
+                i += 1;
+
+                while i < bytes.len() && bytes[i] != quote {
+                    if bytes[i] == b'\\' && escapes {
+                        i += 1;
+                    }
+
+                    i += 1;
+                }
+
+                i += 1;
+            }
+
+            b'#' if DB::PARAM_STYLE == ParamStyle::Question => i = skip_past(i + 1, "\n"),
+
+            b'-' if bytes.get(i + 1) == Some(&b'-') => i = skip_past(i + 2, "\n"),
+
+            b'/' if bytes.get(i + 1) == Some(&b'*') => i = skip_past(i + 2, "*/"),
+
+            b':' if bytes.get(i + 1) == Some(&b':') => i += 2,
+
+            b'$' if bytes.get(i + 1) == Some(&b'$') => i = skip_past(i + 2, "$$"),
+
+            b'$' if matches!(bytes.get(i + 1), Some(b) if b.is_ascii_digit())
+                && (i == 0 || !is_ident(bytes[i - 1])) =>
+            {
+                let end = number_end(i + 1);
+                positional = positional.or(Some(&sql[i..end]));
+                i = end;
+            }
+
+            b'?' if matches!(
+                DB::PARAM_STYLE,
+                ParamStyle::Question | ParamStyle::NumberedQuestion
+            ) =>
+            {
+                let end = number_end(i + 1);
+                positional = positional.or(Some(&sql[i..end]));
+                i = end;
+            }
+
+            sigil @ b':' | sigil @ b'$'
+                if matches!(bytes.get(i + 1), Some(&b) if is_ident_start(b))
+                    && (i == 0 || !is_ident(bytes[i - 1])) =>
+            {
+                let end = ident_end(i + 1);
+
+                if sigil == b'$' && bytes.get(end) == Some(&b'$') {
+                    let tag = &sql[i..=end];
+                    i = skip_past(end + 1, tag);
+                } else {
+                    params.push(NamedParam {
+                        name: &sql[i + 1..end],
+                        range: i..end,
+                    });
+
+                    i = end;
+                }
+            }
+
+            _ => i += 1,
+        }
+    }
+
+    (params, positional)
+}
+
+/// The names of the named parameters of a query, in order of their first appearance.
+fn param_names<DB: DatabaseExt>(sql: &str) -> Vec<&str> {
+    let mut names = Vec::new();
+
+    for param in named_params::<DB>(sql) {
+        if !names.contains(&param.name) {
+            names.push(param.name);
+        }
+    }
+
+    names
+}
+
+/// Replace the named parameters of a query, if any, with the positional parameters of `DB`.
+///
+/// Each name is numbered by its first appearance, matching the order of the arguments returned
+/// by [`named_args`].
+pub fn positional_sql<DB: DatabaseExt>(sql: &str) -> Cow<'_, str> {
+    let params = named_params::<DB>(sql);
+
+    if params.is_empty() {
+        return Cow::Borrowed(sql);
+    }
+
+    let names = param_names::<DB>(sql);
+    let mut positional = String::with_capacity(sql.len());
+    let mut last = 0;
+
+    for param in params {
+        let n = names.iter().position(|&name| name == param.name).unwrap() + 1;
+
+        positional.push_str(&sql[last..param.range.start]);
+        positional.push_str(&DB::PARAM_STYLE.placeholder(n));
+        last = param.range.end;
+    }
+
+    positional.push_str(&sql[last..]);
+
+    Cow::Owned(positional)
+}
+
+/// The indices into `input.arg_exprs` of the arguments to bind to the positional parameters of
+/// the query for `DB`, in order.
+pub fn bound_args<DB: DatabaseExt>(input: &QueryMacroInput) -> Vec<usize> {
+    let names = param_names::<DB>(&input.sql);
+
+    if names.is_empty() || DB::PARAM_STYLE != ParamStyle::Question {
+        return (0..input.arg_exprs.len()).collect();
+    }
+
+    named_params::<DB>(&input.sql)
+        .iter()
+        .map(|param| names.iter().position(|&name| name == param.name).unwrap())
+        .collect()
+}
+
+/// If the query uses named parameters, take the arguments given as `name = value` in order of the
+/// first appearance of each name in the query. Names without an argument are taken from variables
+/// of the same name, resolved at `src_span`.
+///
+/// With `..value` as the last argument, names without an argument are taken from the fields of
+/// `value` instead, which is returned to be bound once by `quote_args()`.
+///
+/// Arguments given without names are taken in order of the first appearance of each name, which
+/// is how SQLite binds its own `:name` and `$name` parameters.
+pub fn named_args<DB: DatabaseExt>(
+    sql: &str,
+    src_span: Span,
+    mut args: Vec<Expr>,
+) -> syn::Result<(Vec<Expr>, Option<Expr>)> {
+    let names = param_names::<DB>(sql);
+
+    let fields_of = match args.pop() {
+        Some(Expr::Range(ExprRange {
+            from: None,
+            limits: RangeLimits::HalfOpen(_),
+            to: Some(to),
+            ..
+        })) => Some(*to),
+        Some(arg) => {
+            args.push(arg);
+            None
+        }
+        None => None,
+    };
+
+    // a positional parameter would be numbered along with the names, and bind one of their
+    // arguments instead of its own
+    if let Some(param) = positional_param::<DB>(sql).filter(|_| !names.is_empty()) {
+        return Err(syn::Error::new(
+            src_span,
+            format!(
+                "the query uses named parameters, so it cannot use the positional parameter `{}` \
+                 as well",
+                param
+            ),
+        ));
+    }
+
+    if let Some(fields_of) = &fields_of {
+        if names.is_empty() {
+            return Err(syn::Error::new_spanned(
+                fields_of,
+                "`..value` can only be used with a query that uses named parameters",
+            ));
+        }
+    }
+
+    if fields_of.is_none()
+        && (names.is_empty() || !args.is_empty())
+        && !args.iter().any(|arg| matches!(arg, Expr::Assign(_)))
+    {
+        if !names.is_empty() && args.len() != names.len() {
+            return Err(syn::Error::new(
+                src_span,
+                format!(
+                    "the query has {} named parameters but {} arguments were given",
+                    names.len(),
+                    args.len()
+                ),
+            ));
+        }
+
+        return Ok((args, None));
+    }
+
+    let mut named: Vec<(Ident, Expr)> = Vec::with_capacity(args.len());
+
+    for arg in args {
+        let (left, right) =
+            match arg {
+                Expr::Assign(ExprAssign { left, right, .. }) => (left, right),
+                arg => return Err(syn::Error::new_spanned(
+                    arg,
+                    "the query uses named parameters, so arguments must be given as `name = value`",
+                )),
+            };
+
+        let ident = match &*left {
+            Expr::Path(ExprPath { path, .. }) if path.get_ident().is_some() => {
+                path.get_ident().unwrap().clone()
+            }
+            _ => return Err(syn::Error::new_spanned(left, "expected a parameter name")),
+        };
+
+        if !names.contains(&&*ident.to_string()) {
+            return Err(syn::Error::new(
+                ident.span(),
+                format!("the query has no parameter `:{0}` or `${0}`", ident),
+            ));
+        }
+
+        if named.iter().any(|(name, _)| *name == ident) {
+            return Err(syn::Error::new(
+                ident.span(),
+                format!("parameter `{}` is given more than once", ident),
+            ));
+        }
+
+        named.push((ident, *right));
+    }
+
+    let args = names
+        .into_iter()
+        .map(|name| {
+            if let Some(i) = named.iter().position(|(ident, _)| ident == name) {
+                return Ok(named.swap_remove(i).1);
+            }
+
+            // a keyword like `:type` cannot name a variable
+            let mut ident = syn::parse_str::<Ident>(name).map_err(|_| {
+                syn::Error::new(
+                    src_span,
+                    format!("parameter `{}` must be given as `{} = value`", name, name),
+                )
+            })?;
+
+            if let Some(fields_of) = &fields_of {
+                ident.set_span(fields_of.span());
+                let fields_of = fields_of_ident();
+
+                return Ok(parse_quote!(#fields_of.#ident));
+            }
+
+            // resolve the variable where the query is written, like `format!()` does
+            ident.set_span(src_span);
+
+            Ok(Expr::Path(ExprPath {
+                attrs: Vec::new(),
+                qself: None,
+                path: ident.into(),
+            }))
+        })
+        .collect::<syn::Result<_>>()?;
+
+    Ok((args, fields_of))
+}
+
+/// The local `..value` is bound to, named so that it won't shadow a variable the arguments use.
+fn fields_of_ident() -> Ident {
+    Ident::new("__sqlx_fields_of", Span::call_site())
+}
+
 fn get_type_override(expr: &Expr) -> Option<&Type> {
     match expr {
         Expr::Group(group) => get_type_override(&group.expr),
@@ -2,6 +2,9 @@ use sqlx_core::database::Database;
 use sqlx_core::describe::Describe;
 use sqlx_core::executor::Executor;
 
+use crate::database::DatabaseExt;
+use crate::query::args;
+
 #[cfg_attr(feature = "offline", derive(serde::Deserialize, serde::Serialize))]
 #[cfg_attr(
     feature = "offline",
@@ -19,14 +22,17 @@ pub struct QueryData<DB: Database> {
     pub(super) hash: String,
 }
 
-impl<DB: Database> QueryData<DB> {
+impl<DB: DatabaseExt> QueryData<DB> {
+    /// Describe the query, with any named parameters replaced by positional ones. The data is
+    /// saved under the query as written, so offline builds can find it before they know the
+    /// database.
     pub async fn from_db(
         conn: impl Executor<'_, Database = DB>,
         query: &str,
     ) -> crate::Result<Self> {
         Ok(QueryData {
             query: query.into(),
-            describe: conn.describe(query).await?,
+            describe: conn.describe(&args::positional_sql::<DB>(query)).await?,
             #[cfg(feature = "offline")]
             hash: offline::hash_string(query),
         })
@@ -37,6 +43,7 @@ impl<DB: Database> QueryData<DB> {
 pub mod offline {
     use super::QueryData;
     use crate::database::DatabaseExt;
+    use crate::query::QuerySource;
 
     use std::fmt::{self, Formatter};
     use std::fs::File;
@@ -49,7 +56,9 @@ pub mod offline {
 
     #[derive(serde::Deserialize)]
     pub struct DynQueryData {
-        #[serde(skip)]
+        // only present in the per-query files of a data directory;
+        // a shared data file stores it once at the top level
+        #[serde(rename = "db", default)]
         pub db_name: String,
         pub query: String,
         pub describe: serde_json::Value,
@@ -88,6 +97,43 @@ pub mod offline {
         }
     }
 
+    impl DynQueryData {
+        /// Find and deserialize the data for this query from a `sqlx-data` directory, which
+        /// contains one `query-<hash>.json` file per query named by the SHA-256 hash of the query
+        /// in hex.
+        pub fn from_data_dir(dir: impl AsRef<Path>, query: &str) -> crate::Result<Self> {
+            let hash = hash_string(query);
+            let path = dir.as_ref().join(format!("query-{}.json", hash));
+
+            let file = File::open(&path).map_err(|e| {
+                format!(
+                    "failed to find data for query {} at {}: {}",
+                    hash,
+                    path.display(),
+                    e
+                )
+            })?;
+
+            let mut query_data: DynQueryData = serde_json::from_reader(BufReader::new(file))?;
+
+            if query_data.db_name.is_empty() {
+                return Err(format!("expected \"db\" key in {}", path.display()).into());
+            }
+
+            if query_data.query != query {
+                return Err(format!(
+                    "hash collision for stored queries:\n{:?}\n{:?}",
+                    query, query_data.query
+                )
+                .into());
+            }
+
+            query_data.hash = hash;
+
+            Ok(query_data)
+        }
+    }
+
     impl<DB: DatabaseExt> QueryData<DB>
     where
         Describe<DB>: serde::Serialize + serde::de::DeserializeOwned,
@@ -132,6 +178,46 @@ pub mod offline {
         }
     }
 
+    /// Save the location of a query macro invocation, the hash of its query and where the data
+    /// to check it against came from, for `cargo sqlx prepare --list`.
+    pub fn save_call_site(
+        dir: impl AsRef<Path>,
+        span: Span,
+        query: &str,
+        source: QuerySource,
+    ) -> crate::Result<()> {
+        // the location is only known on compilers that support `proc_macro::Span::file()`
+        #[cfg(feature = "query-locations")]
+        let (file, line) = (Some(span.file()), Some(span.start().line));
+        #[cfg(not(feature = "query-locations"))]
+        let (file, line): (Option<String>, Option<usize>) = (None, None);
+
+        let path = dir.as_ref().join(format!(
+            "call-{}.json",
+            hash_string(&format!("{:?}:{:?}:{:?}", file, line, span))
+        ));
+
+        let source = match source {
+            QuerySource::Online => "online",
+            QuerySource::Offline => "offline",
+            QuerySource::Missing => "missing",
+        };
+
+        serde_json::to_writer_pretty(
+            BufWriter::new(
+                File::create(&path)
+                    .map_err(|e| format!("failed to open path {}: {}", path.display(), e))?,
+            ),
+            &serde_json::json!({
+                "file": file,
+                "line": line,
+                "hash": hash_string(query),
+                "source": source,
+            }),
+        )
+        .map_err(Into::into)
+    }
+
     pub fn hash_string(query: &str) -> String {
         // picked `sha2` because it's already in the dependency tree for both MySQL and Postgres
         use sha2::{Digest, Sha256};
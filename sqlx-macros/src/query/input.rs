@@ -10,15 +10,22 @@ use syn::{ExprArray, Type};
 pub struct QueryMacroInput {
     pub(super) sql: String,
 
-    #[cfg_attr(not(feature = "offline"), allow(dead_code))]
     pub(super) src_span: Span,
 
     pub(super) record_type: RecordType,
 
+    /// The arguments as given, until they are taken in order of the first appearance of their
+    /// names once the database is known, if the query uses named parameters.
     pub(super) arg_exprs: Vec<Expr>,
 
+    /// The `..value` the arguments of the remaining named parameters are fields of.
+    pub(super) fields_of: Option<Expr>,
+
     pub(super) checked: bool,
 
+    /// Whether the record is decoded through its `FromRow` impl instead of by column index.
+    pub(super) from_row: bool,
+
     pub(super) file_path: Option<String>,
 }
 
@@ -28,7 +35,7 @@ enum QuerySrc {
 }
 
 pub enum RecordType {
-    Given(Type),
+    Given(Box<Type>),
     Scalar,
     Generated,
 }
@@ -39,6 +46,7 @@ impl Parse for QueryMacroInput {
         let mut args: Option<Vec<Expr>> = None;
         let mut record_type = RecordType::Generated;
         let mut checked = true;
+        let mut from_row = false;
 
         let mut expect_comma = false;
 
@@ -52,11 +60,11 @@ impl Parse for QueryMacroInput {
             let _ = input.parse::<syn::token::Eq>()?;
 
             if key == "source" {
-                let span = input.span();
-                let query_str = Punctuated::<LitStr, Token![+]>::parse_separated_nonempty(input)?
-                    .iter()
-                    .map(LitStr::value)
-                    .collect();
+                let lit_strs = Punctuated::<LitStr, Token![+]>::parse_separated_nonempty(input)?;
+                // the span of the group `query!()` wraps the literals in is that of `query!()`
+                // itself, so take the span of the first literal in the caller's code
+                let span = lit_strs[0].span();
+                let query_str = lit_strs.iter().map(LitStr::value).collect();
                 query_src = Some((QuerySrc::String(query_str), span));
             } else if key == "source_file" {
                 let lit_str = input.parse::<LitStr>()?;
@@ -69,7 +77,7 @@ impl Parse for QueryMacroInput {
                     return Err(input.error("colliding `scalar` or `record` key"));
                 }
 
-                record_type = RecordType::Given(input.parse()?);
+                record_type = RecordType::Given(Box::new(input.parse()?));
             } else if key == "scalar" {
                 if !matches!(record_type, RecordType::Generated) {
                     return Err(input.error("colliding `scalar` or `record` key"));
@@ -83,6 +91,9 @@ impl Parse for QueryMacroInput {
             } else if key == "checked" {
                 let lit_bool = input.parse::<LitBool>()?;
                 checked = lit_bool.value;
+            } else if key == "from_row" {
+                let lit_bool = input.parse::<LitBool>()?;
+                from_row = lit_bool.value;
             } else {
                 let message = format!("unexpected input key: {}", key);
                 return Err(syn::Error::new_spanned(key, message));
@@ -94,16 +105,22 @@ impl Parse for QueryMacroInput {
         let (src, src_span) =
             query_src.ok_or_else(|| input.error("expected `source` or `source_file` key"))?;
 
-        let arg_exprs = args.unwrap_or_default();
+        if from_row && !matches!(record_type, RecordType::Given(_)) {
+            return Err(input.error("`from_row` requires a `record` key"));
+        }
 
         let file_path = src.file_path(src_span)?;
 
+        let sql = src.resolve(src_span)?;
+
         Ok(QueryMacroInput {
-            sql: src.resolve(src_span)?,
+            sql,
             src_span,
             record_type,
-            arg_exprs,
+            arg_exprs: args.unwrap_or_default(),
+            fields_of: None,
             checked,
+            from_row,
             file_path,
         })
     }
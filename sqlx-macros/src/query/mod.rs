@@ -1,9 +1,12 @@
+#[cfg(feature = "offline")]
+use std::path::Path;
 use std::path::PathBuf;
 #[cfg(feature = "offline")]
 use std::sync::{Arc, Mutex};
 
 use once_cell::sync::Lazy;
 use proc_macro2::TokenStream;
+use sqlx_core::config::Config;
 use syn::Type;
 use url::Url;
 
@@ -27,12 +30,18 @@ mod output;
 struct Metadata {
     #[allow(unused)]
     manifest_dir: PathBuf,
+    config: &'static Config,
     offline: bool,
     database_url: Option<String>,
+    #[cfg(all(feature = "sqlite", feature = "migrate"))]
+    sqlite_migrations: Option<String>,
     #[cfg(feature = "offline")]
     target_dir: PathBuf,
     #[cfg(feature = "offline")]
     workspace_root: Arc<Mutex<Option<PathBuf>>>,
+    // set by `cargo sqlx prepare --list` to have every invocation saved with how it was resolved
+    #[cfg(feature = "offline")]
+    list_queries: bool,
 }
 
 #[cfg(feature = "offline")]
@@ -102,58 +111,134 @@ static METADATA: Lazy<Metadata> = Lazy::new(|| {
         .map(|s| s.eq_ignore_ascii_case("true") || s == "1")
         .unwrap_or(false);
 
-    let database_url = env("DATABASE_URL").ok();
+    let config = crate::common::config().unwrap_or_else(|e| panic!("{}", e));
+
+    let database_url = env(config.common.database_url_var()).ok();
+
+    // the migrations directory, relative to `CARGO_MANIFEST_DIR`, to check queries against
+    // an in-memory SQLite database with if the database URL is not set
+    #[cfg(all(feature = "sqlite", feature = "migrate"))]
+    let sqlite_migrations = env("SQLX_SQLITE_MIGRATIONS").ok().or_else(|| {
+        config
+            .macros
+            .sqlite_migrations
+            .then(|| config.migrate.migrations_dir().to_owned())
+    });
+
+    #[cfg(feature = "offline")]
+    let list_queries = env("SQLX_LIST_QUERIES")
+        .map(|s| s.eq_ignore_ascii_case("true") || s == "1")
+        .unwrap_or(false);
 
     Metadata {
         manifest_dir,
+        config,
         offline,
         database_url,
+        #[cfg(all(feature = "sqlite", feature = "migrate"))]
+        sqlite_migrations,
         #[cfg(feature = "offline")]
         target_dir,
         #[cfg(feature = "offline")]
         workspace_root: Arc::new(Mutex::new(None)),
+        #[cfg(feature = "offline")]
+        list_queries,
     }
 });
 
+/// Where the data to check a query against came from.
+#[cfg_attr(not(feature = "offline"), allow(dead_code))]
+#[derive(Debug, Clone, Copy)]
+enum QuerySource {
+    /// The database, or the migrations applied to an in-memory SQLite database.
+    Online,
+    /// The data saved by `cargo sqlx prepare`.
+    Offline,
+    /// Neither; the query could not be checked.
+    Missing,
+}
+
 pub fn expand_input(input: QueryMacroInput) -> crate::Result<TokenStream> {
+    #[cfg(feature = "offline")]
+    let (src_span, sql) = (input.src_span, input.sql.clone());
+
+    #[cfg_attr(not(feature = "offline"), allow(unused_variables))]
+    let (source, result) = resolve_input(input);
+
+    #[cfg(feature = "offline")]
+    if METADATA.list_queries {
+        let save_dir = METADATA.target_dir.join("sqlx");
+        std::fs::create_dir_all(&save_dir)?;
+        data::offline::save_call_site(save_dir, src_span, &sql, source)?;
+    }
+
+    result
+}
+
+fn resolve_input(input: QueryMacroInput) -> (QuerySource, crate::Result<TokenStream>) {
+    let database_url_var = METADATA.config.common.database_url_var();
+
     match &*METADATA {
         Metadata {
             offline: false,
             database_url: Some(db_url),
             ..
-        } => expand_from_db(input, &db_url),
+        } => (QuerySource::Online, expand_from_db(input, db_url)),
+
+        #[cfg(all(feature = "sqlite", feature = "migrate"))]
+        Metadata {
+            offline: false,
+            database_url: None,
+            sqlite_migrations: Some(migrations),
+            ..
+        } => (
+            QuerySource::Online,
+            expand_from_migrations(input, migrations),
+        ),
 
         #[cfg(feature = "offline")]
         _ => {
-            let data_file_path = METADATA.manifest_dir.join("sqlx-data.json");
-
-            if data_file_path.exists() {
-                expand_from_file(input, data_file_path)
-            } else {
-                let workspace_data_file_path = METADATA.workspace_root().join("sqlx-data.json");
-                if workspace_data_file_path.exists() {
-                    expand_from_file(input, workspace_data_file_path)
-                } else {
-                    Err(
-                        "`DATABASE_URL` must be set, or `cargo sqlx prepare` must have been run \
-                     and sqlx-data.json must exist, to use query macros"
-                            .into(),
-                    )
-                }
+            // a configured directory replaces the crate and workspace root
+            let data_path = match &METADATA.config.macros.offline_dir {
+                Some(dir) => offline_data_path(&METADATA.manifest_dir.join(dir)),
+                None => offline_data_path(&METADATA.manifest_dir)
+                    .or_else(|| offline_data_path(&METADATA.workspace_root())),
+            };
+
+            let query_data = match data_path {
+                Some(data_path) => read_offline_data(&data_path, &input.sql),
+                None => Err(format!(
+                    "`{}` must be set, or `cargo sqlx prepare` must have been run \
+                     and sqlx-data.json or sqlx-data/ must exist, to use query macros",
+                    database_url_var
+                )
+                .into()),
+            };
+
+            match query_data {
+                Ok(query_data) => (
+                    QuerySource::Offline,
+                    expand_from_offline_data(input, query_data),
+                ),
+                Err(e) => (QuerySource::Missing, Err(e)),
             }
         }
 
         #[cfg(not(feature = "offline"))]
-        Metadata { offline: true, .. } => {
-            Err("The cargo feature `offline` has to be enabled to use `SQLX_OFFLINE`".into())
-        }
+        Metadata { offline: true, .. } => (
+            QuerySource::Missing,
+            Err("The cargo feature `offline` has to be enabled to use `SQLX_OFFLINE`".into()),
+        ),
 
         #[cfg(not(feature = "offline"))]
         Metadata {
             offline: false,
             database_url: None,
             ..
-        } => Err("`DATABASE_URL` must be set to use query macros".into()),
+        } => (
+            QuerySource::Missing,
+            Err(format!("`{}` must be set to use query macros", database_url_var).into()),
+        ),
     }
 }
 
@@ -220,11 +305,57 @@ fn expand_from_db(input: QueryMacroInput, db_url: &str) -> crate::Result<TokenSt
     }
 }
 
+// describes the query against an in-memory SQLite database with the migrations applied,
+// which is created once per compilation of the crate
+#[cfg(all(feature = "sqlite", feature = "migrate"))]
+fn expand_from_migrations(input: QueryMacroInput, migrations: &str) -> crate::Result<TokenStream> {
+    use sqlx_core::sqlite::SqliteConnection;
+    use std::sync::Mutex;
+
+    static CONNECTION: Lazy<Mutex<Option<SqliteConnection>>> = Lazy::new(Default::default);
+
+    let mut conn = CONNECTION
+        .lock()
+        .map_err(|_| "the SQLite database for checking queries is poisoned")?;
+
+    let conn = match &mut *conn {
+        Some(conn) => conn,
+        slot @ None => {
+            let path = METADATA.manifest_dir.join(migrations);
+            slot.insert(block_on(crate::migrate::migrated_sqlite(&path))?)
+        }
+    };
+
+    let data = block_on(QueryData::from_db(conn, &input.sql))?;
+
+    expand_with_data(input, data, false)
+}
+
+// a `sqlx-data` directory with one file per query takes precedence over `sqlx-data.json`
+#[cfg(feature = "offline")]
+fn offline_data_path(dir: &Path) -> Option<PathBuf> {
+    ["sqlx-data", "sqlx-data.json"]
+        .iter()
+        .map(|name| dir.join(name))
+        .find(|path| path.exists())
+}
+
 #[cfg(feature = "offline")]
-pub fn expand_from_file(input: QueryMacroInput, file: PathBuf) -> crate::Result<TokenStream> {
+fn read_offline_data(path: &Path, sql: &str) -> crate::Result<data::offline::DynQueryData> {
     use data::offline::DynQueryData;
 
-    let query_data = DynQueryData::from_data_file(file, &input.sql)?;
+    if path.is_dir() {
+        DynQueryData::from_data_dir(path, sql)
+    } else {
+        DynQueryData::from_data_file(path, sql)
+    }
+}
+
+#[cfg(feature = "offline")]
+fn expand_from_offline_data(
+    input: QueryMacroInput,
+    query_data: data::offline::DynQueryData,
+) -> crate::Result<TokenStream> {
     assert!(!query_data.db_name.is_empty());
 
     match &*query_data.db_name {
@@ -271,13 +402,19 @@ trait DescribeExt {}
 impl<DB: Database> DescribeExt for Describe<DB> {}
 
 fn expand_with_data<DB: DatabaseExt>(
-    input: QueryMacroInput,
+    mut input: QueryMacroInput,
     data: QueryData<DB>,
     #[allow(unused_variables)] offline: bool,
 ) -> crate::Result<TokenStream>
 where
     Describe<DB>: DescribeExt,
 {
+    // named parameters are found once the database is known, as its strings escape differently
+    let arg_exprs = std::mem::take(&mut input.arg_exprs);
+    let (arg_exprs, fields_of) = args::named_args::<DB>(&input.sql, input.src_span, arg_exprs)?;
+    input.arg_exprs = arg_exprs;
+    input.fields_of = fields_of;
+
     // validate at the minimum that our args match the query's input parameters
     let num_parameters = match data.describe.parameters() {
         Some(Either::Left(params)) => Some(params.len()),
@@ -286,11 +423,11 @@ where
         None => None,
     };
 
+    let num_args = args::bound_args::<DB>(&input).len();
+
     if let Some(num) = num_parameters {
-        if num != input.arg_exprs.len() {
-            return Err(
-                format!("expected {} parameters, got {}", num, input.arg_exprs.len()).into(),
-            );
+        if num != num_args {
+            return Err(format!("expected {} parameters, got {}", num, num_args).into());
         }
     }
 
@@ -305,7 +442,7 @@ where
         .all(|it| it.type_info().is_void())
     {
         let db_path = DB::db_path();
-        let sql = &input.sql;
+        let sql = args::positional_sql::<DB>(&input.sql);
 
         quote! {
             ::sqlx::query_with::<#db_path, _>(#sql, #query_args)
@@ -325,6 +462,14 @@ where
                                 .into(),
                         );
                     }
+
+                    if rust_col.type_.is_json() {
+                        return Err(
+                            "`json` overrides are only allowed with an explicit record type, \
+                             e.g. `query_as!()` and its variants"
+                                .into(),
+                        );
+                    }
                 }
 
                 let record_fields = columns.iter().map(
@@ -335,8 +480,10 @@ where
                      }| quote!(#ident: #type_,),
                 );
 
+                let record_derives = output::quote_record_derives()?;
+
                 let mut record_tokens = quote! {
-                    #[derive(Debug)]
+                    #record_derives
                     struct #record_name {
                         #(#record_fields)*
                     }
@@ -351,6 +498,9 @@ where
 
                 record_tokens
             }
+            RecordType::Given(ref out_ty) if input.from_row => {
+                output::quote_query_as_from_row::<DB>(&input, out_ty, &query_args, &data.describe)?
+            }
             RecordType::Given(ref out_ty) => {
                 let columns = output::columns_to_rust::<DB>(&data.describe)?;
 
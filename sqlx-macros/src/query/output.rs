@@ -4,10 +4,12 @@ use syn::Type;
 
 use sqlx_core::column::Column;
 use sqlx_core::describe::Describe;
+use sqlx_core::type_info::TypeInfo;
 
 use crate::database::DatabaseExt;
 
-use crate::query::QueryMacroInput;
+use crate::query::{args, QueryMacroInput};
+use std::borrow::Cow;
 use std::fmt::{self, Display, Formatter};
 use syn::parse::{Parse, ParseStream};
 use syn::Token;
@@ -22,11 +24,17 @@ pub(super) enum ColumnType {
     Exact(TokenStream),
     Wildcard,
     OptWildcard,
+    Json,
+    OptJson,
 }
 
 impl ColumnType {
     pub(super) fn is_wildcard(&self) -> bool {
-        !matches!(self, ColumnType::Exact(_))
+        matches!(self, ColumnType::Wildcard | ColumnType::OptWildcard)
+    }
+
+    pub(super) fn is_json(&self) -> bool {
+        matches!(self, ColumnType::Json | ColumnType::OptJson)
     }
 }
 
@@ -36,6 +44,10 @@ impl ToTokens for ColumnType {
             ColumnType::Exact(type_) => type_.clone().into_iter(),
             ColumnType::Wildcard => quote! { _ }.into_iter(),
             ColumnType::OptWildcard => quote! { ::std::option::Option<_> }.into_iter(),
+            ColumnType::Json => quote! { ::sqlx::types::Json<_> }.into_iter(),
+            ColumnType::OptJson => {
+                quote! { ::std::option::Option<::sqlx::types::Json<_>> }.into_iter()
+            }
         })
     }
 }
@@ -64,8 +76,9 @@ enum ColumnNullabilityOverride {
 }
 
 enum ColumnTypeOverride {
-    Exact(Type),
+    Exact(Box<Type>),
     Wildcard,
+    Json,
     None,
 }
 
@@ -88,7 +101,19 @@ fn column_to_rust<DB: DatabaseExt>(describe: &Describe<DB>, i: usize) -> crate::
     let decl = ColumnDecl::parse(&column.name())
         .map_err(|e| format!("column name {:?} is invalid: {}", column.name(), e))?;
 
-    let ColumnOverride { nullability, type_ } = decl.r#override;
+    let ColumnOverride {
+        nullability,
+        mut type_,
+    } = decl.r#override;
+
+    // columns without an explicit type take the type configured for their name, if any
+    if let ColumnTypeOverride::None = type_ {
+        let name = decl.ident.to_string();
+
+        if let Some(ty) = column_type_override::<DB>(column, name.trim_start_matches("r#"))? {
+            type_ = ColumnTypeOverride::Exact(Box::new(ty));
+        }
+    }
 
     let nullable = match nullability {
         ColumnNullabilityOverride::NonNull => false,
@@ -104,6 +129,9 @@ fn column_to_rust<DB: DatabaseExt>(describe: &Describe<DB>, i: usize) -> crate::
         (ColumnTypeOverride::Wildcard, false) => ColumnType::Wildcard,
         (ColumnTypeOverride::Wildcard, true) => ColumnType::OptWildcard,
 
+        (ColumnTypeOverride::Json, false) => ColumnType::Json,
+        (ColumnTypeOverride::Json, true) => ColumnType::OptJson,
+
         (ColumnTypeOverride::None, _) => {
             let type_ = get_column_type::<DB>(i, column);
             if !nullable {
@@ -123,6 +151,44 @@ fn column_to_rust<DB: DatabaseExt>(describe: &Describe<DB>, i: usize) -> crate::
     })
 }
 
+/// The derives of the records of `query!()`: `Debug`, and `record-derives` from the configuration.
+pub fn quote_record_derives() -> crate::Result<TokenStream> {
+    let mut derives = vec![quote!(Debug)];
+    let mut serde_crate = None;
+
+    for derive in &crate::common::config()?.macros.record_derives {
+        match derive.as_str() {
+            "Debug" => continue,
+
+            "Serialize" | "serde::Serialize" => {
+                if !cfg!(feature = "json") {
+                    return Err(
+                        "deriving `Serialize` for query records requires the `json` \
+                                feature of SQLx"
+                            .into(),
+                    );
+                }
+
+                derives.push(quote!(::sqlx::serde::Serialize));
+                serde_crate = Some(quote!(#[serde(crate = "::sqlx::serde")]));
+            }
+
+            _ => {
+                let path: syn::Path = syn::parse_str(derive).map_err(|e| {
+                    format!("invalid derive {:?} in `record-derives`: {}", derive, e)
+                })?;
+
+                derives.push(path.to_token_stream());
+            }
+        }
+    }
+
+    Ok(quote! {
+        #[derive(#(#derives),*)]
+        #serde_crate
+    })
+}
+
 pub fn quote_query_as<DB: DatabaseExt>(
     input: &QueryMacroInput,
     out_ty: &Type,
@@ -151,6 +217,15 @@ pub fn quote_query_as<DB: DatabaseExt>(
                 (true, ColumnType::OptWildcard) => {
                     quote! ( let #var_name = row.try_get::<::std::option::Option<_>, _>(#i)?; )
                 }
+                // the field is decoded from JSON, inferring its type from the struct
+                (_, ColumnType::Json) => quote! {
+                    let #var_name = row.try_get::<::sqlx::types::Json<_>, _>(#i)?.0;
+                },
+                (_, ColumnType::OptJson) => quote! {
+                    let #var_name = row
+                        .try_get::<::std::option::Option<::sqlx::types::Json<_>>, _>(#i)?
+                        .map(|json| json.0);
+                },
                 // macro is the `_unchecked!()` variant so this will die in decoding if it's wrong
                 (false, _) => quote!( let #var_name = row.try_get_unchecked(#i)?; ),
             }
@@ -163,13 +238,7 @@ pub fn quote_query_as<DB: DatabaseExt>(
     let db_path = DB::db_path();
     let row_path = DB::row_path();
 
-    // if this query came from a file, use `include_str!()` to tell the compiler where it came from
-    let sql = if let Some(ref path) = &input.file_path {
-        quote::quote_spanned! { input.src_span => include_str!(#path) }
-    } else {
-        let sql = &input.sql;
-        quote! { #sql }
-    };
+    let sql = quote_sql::<DB>(input);
 
     quote! {
         ::sqlx::query_with::<#db_path, _>(#sql, #bind_args).try_map(|row: #row_path| {
@@ -182,6 +251,84 @@ pub fn quote_query_as<DB: DatabaseExt>(
     }
 }
 
+/// `query_as!()` decoding through the `FromRow` impl of `out_ty`, checking the type of each
+/// column against the field it is read into.
+pub fn quote_query_as_from_row<DB: DatabaseExt>(
+    input: &QueryMacroInput,
+    out_ty: &Type,
+    bind_args: &Ident,
+    describe: &Describe<DB>,
+) -> crate::Result<TokenStream> {
+    let mut checks = Vec::<Ident>::with_capacity(describe.columns().len());
+    let mut types = Vec::with_capacity(describe.columns().len());
+
+    for (i, column) in describe.columns().iter().enumerate() {
+        let name = column.name();
+
+        // `FromRow` reads columns by name, which overrides would change
+        if name.contains(&[':', '!', '?'][..]) {
+            return Err(syn::Error::new(
+                input.src_span,
+                format!(
+                    "{} has an override, but overrides cannot be used when decoding through \
+                     `FromRow`",
+                    DisplayColumn { idx: i, name }
+                ),
+            )
+            .into());
+        }
+
+        let check = crate::derives::column_check_ident(name);
+
+        let ty = match column_type_override::<DB>(column, name)? {
+            Some(ty) => ty.to_token_stream(),
+            None => get_column_type::<DB>(i, column),
+        };
+
+        // `FromRow` reads the last of several columns of the same name
+        match checks.iter().position(|other| *other == check) {
+            Some(j) => types[j] = ty,
+            None => {
+                checks.push(check);
+                types.push(ty);
+            }
+        }
+    }
+
+    let db_path = DB::db_path();
+    let sql = quote_sql::<DB>(input);
+
+    // a column that no field of the struct reads is an error, unless the struct has flattened
+    // fields that might read it
+    Ok(quote::quote_spanned! { input.src_span => {
+        #[allow(non_snake_case)]
+        trait UncheckedColumns {
+            #(fn #checks<__Column>() {})*
+        }
+
+        impl<R: ::sqlx::ty_match::UncheckedColumns> UncheckedColumns for R {}
+
+        #(<<#out_ty as ::sqlx::ty_match::FromRowColumns>::Columns>::#checks::<#types>();)*
+
+        ::sqlx::query_as_with::<#db_path, #out_ty, _>(#sql, #bind_args)
+    }})
+}
+
+// if this query came from a file, use `include_str!()` to tell the compiler where it came from
+fn quote_sql<DB: DatabaseExt>(input: &QueryMacroInput) -> TokenStream {
+    match (&input.file_path, args::positional_sql::<DB>(&input.sql)) {
+        (Some(path), Cow::Borrowed(_)) => {
+            quote::quote_spanned! { input.src_span => include_str!(#path) }
+        }
+        // the named parameters of the file were replaced, but it should still be tracked
+        (Some(path), Cow::Owned(sql)) => quote::quote_spanned! { input.src_span => {
+            const _: &str = include_str!(#path);
+            #sql
+        }},
+        (None, sql) => quote! { #sql },
+    }
+}
+
 pub fn quote_query_scalar<DB: DatabaseExt>(
     input: &QueryMacroInput,
     bind_args: &Ident,
@@ -199,6 +346,15 @@ pub fn quote_query_scalar<DB: DatabaseExt>(
 
     // attempt to parse a column override, otherwise fall back to the inferred type of the column
     let ty = if let Ok(rust_col) = column_to_rust(describe, 0) {
+        if rust_col.type_.is_json() {
+            return Err(syn::Error::new(
+                input.src_span,
+                "`json` overrides are only allowed with an explicit record type, \
+                 e.g. `query_as!()` and its variants",
+            )
+            .into());
+        }
+
         rust_col.type_.to_token_stream()
     } else if input.checked {
         let ty = get_column_type::<DB>(0, &columns[0]);
@@ -212,16 +368,65 @@ pub fn quote_query_scalar<DB: DatabaseExt>(
     };
 
     let db = DB::db_path();
-    let query = &input.sql;
+    let query = args::positional_sql::<DB>(&input.sql);
 
     Ok(quote! {
         ::sqlx::query_scalar_with::<#db, #ty, _>(#query, #bind_args)
     })
 }
 
+/// The Rust type configured for a column in `[macros.column-types]`, if any.
+fn column_type_override<DB: DatabaseExt>(
+    column: &DB::Column,
+    name: &str,
+) -> crate::Result<Option<Type>> {
+    let config = &crate::common::config()?.macros;
+
+    let ty = match config.column_type(column.origin_table(), name) {
+        Some(ty) => ty,
+        None => return Ok(None),
+    };
+
+    let ty = syn::parse_str(ty).map_err(|e| {
+        format!(
+            "invalid type {:?} configured for column {:?}: {}",
+            ty, name, e
+        )
+    })?;
+
+    Ok(Some(ty))
+}
+
+/// The Rust type configured for an SQL type in `[macros.type-overrides]`, if any.
+pub(super) fn type_override(type_info: &impl TypeInfo) -> crate::Result<Option<Type>> {
+    let config = &crate::common::config()?.macros;
+
+    let ty = match config.type_override(type_info.name()) {
+        Some(ty) => ty,
+        None => return Ok(None),
+    };
+
+    let ty = syn::parse_str(ty).map_err(|e| {
+        format!(
+            "invalid type {:?} configured for SQL type {}: {}",
+            ty,
+            type_info.name(),
+            e
+        )
+    })?;
+
+    Ok(Some(ty))
+}
+
 fn get_column_type<DB: DatabaseExt>(i: usize, column: &DB::Column) -> TokenStream {
     let type_info = &*column.type_info();
 
+    match type_override(type_info) {
+        Ok(Some(ty)) => return ty.to_token_stream(),
+        Ok(None) => (),
+        Err(e) => return syn::Error::new(Span::call_site(), e).to_compile_error(),
+    }
+
     <DB as DatabaseExt>::return_type_for_id(&type_info).map_or_else(
         || {
             let message =
@@ -299,10 +504,12 @@ impl Parse for ColumnOverride {
 
             let ty = Type::parse(input)?;
 
-            if let Type::Infer(_) = ty {
-                ColumnTypeOverride::Wildcard
-            } else {
-                ColumnTypeOverride::Exact(ty)
+            match ty {
+                Type::Infer(_) => ColumnTypeOverride::Wildcard,
+                Type::Path(ref path) if path.qself.is_none() && path.path.is_ident("json") => {
+                    ColumnTypeOverride::Json
+                }
+                _ => ColumnTypeOverride::Exact(Box::new(ty)),
             }
         } else {
             ColumnTypeOverride::None
@@ -0,0 +1,204 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{AttributeArgs, ItemFn};
+
+#[cfg(feature = "migrate")]
+use syn::{Lit, LitStr, Meta, NestedMeta};
+
+pub(crate) fn expand(args: AttributeArgs, input: ItemFn) -> crate::Result<TokenStream> {
+    if input.sig.inputs.is_empty() {
+        if let Some(arg) = args.first() {
+            return Err(syn::Error::new_spanned(
+                arg,
+                "arguments of `#[sqlx::test]` are only allowed on tests that take a `Pool`",
+            )
+            .into());
+        }
+
+        let body = &input.block;
+
+        return Ok(expand_with_runtime(&input, quote!(async { #body })));
+    }
+
+    #[cfg(feature = "migrate")]
+    return expand_with_pool(args, input);
+
+    #[cfg(not(feature = "migrate"))]
+    return Err(syn::Error::new_spanned(
+        &input.sig.inputs,
+        "tests that take a `Pool` require the `migrate` feature",
+    )
+    .into());
+}
+
+fn expand_with_runtime(input: &ItemFn, future: TokenStream) -> TokenStream {
+    let ret = &input.sig.output;
+    let name = &input.sig.ident;
+    let attrs = &input.attrs;
+
+    if cfg!(feature = "_rt-tokio") {
+        quote! {
+            #[test]
+            #(#attrs)*
+            fn #name() #ret {
+                ::sqlx_rt::tokio::runtime::Builder::new_multi_thread()
+                    .enable_io()
+                    .enable_time()
+                    .build()
+                    .unwrap()
+                    .block_on(#future)
+            }
+        }
+    } else if cfg!(feature = "_rt-async-std") {
+        quote! {
+            #[test]
+            #(#attrs)*
+            fn #name() #ret {
+                ::sqlx_rt::async_std::task::block_on(#future)
+            }
+        }
+    } else if cfg!(feature = "_rt-actix") {
+        quote! {
+            #[test]
+            #(#attrs)*
+            fn #name() #ret {
+                ::sqlx_rt::actix_rt::System::new()
+                    .block_on(#future)
+            }
+        }
+    } else {
+        panic!("one of 'runtime-actix', 'runtime-async-std' or 'runtime-tokio' features must be enabled");
+    }
+}
+
+#[cfg(feature = "migrate")]
+enum Migrations {
+    // `migrate!()` if the migrations directory of the crate exists
+    Default,
+    Disabled,
+    Dir(LitStr),
+    Migrator(syn::Path),
+}
+
+#[cfg(feature = "migrate")]
+fn expand_with_pool(args: AttributeArgs, input: ItemFn) -> crate::Result<TokenStream> {
+    let mut migrations = Migrations::Default;
+    let mut fixtures = Vec::new();
+
+    for arg in args {
+        match arg {
+            NestedMeta::Meta(Meta::NameValue(meta)) if meta.path.is_ident("migrations") => {
+                migrations = match meta.lit {
+                    Lit::Bool(b) if !b.value => Migrations::Disabled,
+                    Lit::Str(dir) => Migrations::Dir(dir),
+                    lit => {
+                        return Err(
+                            syn::Error::new_spanned(lit, "expected a directory or `false`").into(),
+                        )
+                    }
+                };
+            }
+
+            NestedMeta::Meta(Meta::NameValue(meta)) if meta.path.is_ident("migrator") => {
+                migrations = match meta.lit {
+                    Lit::Str(path) => Migrations::Migrator(path.parse()?),
+                    lit => {
+                        return Err(syn::Error::new_spanned(
+                            lit,
+                            "expected the path of a `Migrator` static",
+                        )
+                        .into())
+                    }
+                };
+            }
+
+            NestedMeta::Meta(Meta::List(list)) if list.path.is_ident("fixtures") => {
+                for fixture in list.nested {
+                    match fixture {
+                        NestedMeta::Lit(Lit::Str(name)) => {
+                            let name = name.value();
+
+                            if name.ends_with(".sql") {
+                                fixtures.push(name);
+                            } else {
+                                fixtures.push(format!("fixtures/{}.sql", name));
+                            }
+                        }
+
+                        fixture => {
+                            return Err(
+                                syn::Error::new_spanned(fixture, "expected a fixture name").into()
+                            )
+                        }
+                    }
+                }
+            }
+
+            arg => {
+                return Err(syn::Error::new_spanned(
+                    arg,
+                    "expected `migrations`, `migrator` or `fixtures`",
+                )
+                .into())
+            }
+        }
+    }
+
+    let migrator = match migrations {
+        Migrations::Default => {
+            let dir = crate::common::config()?.migrate.migrations_dir();
+            let manifest_dir = std::env::var("CARGO_MANIFEST_DIR")
+                .map_err(|_| "CARGO_MANIFEST_DIR is not set; please use Cargo to build")?;
+
+            if std::path::Path::new(&manifest_dir).join(dir).is_dir() {
+                let migrator = crate::migrate::expand_migrator_from_config()?;
+
+                quote! {{
+                    static MIGRATOR: ::sqlx::migrate::Migrator = #migrator;
+                    ::std::option::Option::Some(&MIGRATOR)
+                }}
+            } else {
+                quote!(::std::option::Option::None)
+            }
+        }
+        Migrations::Disabled => quote!(::std::option::Option::None),
+        Migrations::Dir(dir) => {
+            let migrator = crate::migrate::expand_migrator_from_dir(dir)?;
+
+            quote! {{
+                static MIGRATOR: ::sqlx::migrate::Migrator = #migrator;
+                ::std::option::Option::Some(&MIGRATOR)
+            }}
+        }
+        Migrations::Migrator(path) => quote!(::std::option::Option::Some(&#path)),
+    };
+
+    let database_url_var = crate::common::config()?.common.database_url_var();
+
+    let name = &input.sig.ident;
+
+    // the test itself becomes a function inside of the generated `#[test]`
+    let mut inner = input.clone();
+    inner.attrs.clear();
+    inner.sig.ident = syn::Ident::new("inner", name.span());
+
+    let future = quote! {{
+        #inner
+
+        let args = ::sqlx::testing::TestArgs {
+            test_path: ::std::concat!(::std::module_path!(), "::", ::std::stringify!(#name)),
+            database_url_var: #database_url_var,
+            migrator: #migrator,
+            fixtures: &[
+                #(::sqlx::testing::TestFixture {
+                    path: #fixtures,
+                    contents: ::std::include_str!(#fixtures),
+                }),*
+            ],
+        };
+
+        ::sqlx::testing::run_test(args, inner)
+    }};
+
+    Ok(expand_with_runtime(&input, future))
+}
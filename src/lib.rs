@@ -15,6 +15,8 @@ pub use sqlx_core::acquire::Acquire;
 pub use sqlx_core::arguments::{Arguments, IntoArguments};
 pub use sqlx_core::column::Column;
 pub use sqlx_core::column::ColumnIndex;
+#[doc(hidden)]
+pub use sqlx_core::column::PrefixedColumn;
 pub use sqlx_core::connection::{ConnectOptions, Connection};
 pub use sqlx_core::database::{self, Database};
 pub use sqlx_core::describe::Describe;
@@ -28,7 +30,7 @@ pub use sqlx_core::row::Row;
 pub use sqlx_core::statement::Statement;
 pub use sqlx_core::transaction::{Transaction, TransactionManager};
 pub use sqlx_core::type_info::TypeInfo;
-pub use sqlx_core::types::Type;
+pub use sqlx_core::types::{InList, Type};
 pub use sqlx_core::value::{Value, ValueRef};
 
 #[doc(inline)]
@@ -37,6 +39,12 @@ pub use sqlx_core::error::{self, Error, Result};
 #[cfg(feature = "migrate")]
 pub use sqlx_core::migrate;
 
+#[cfg(feature = "migrate")]
+pub use sqlx_core::testing;
+
+#[cfg(feature = "config")]
+pub use sqlx_core::config;
+
 #[cfg(all(
     any(
         feature = "mysql",
@@ -71,7 +79,42 @@ pub extern crate sqlx_macros;
 // derives
 #[cfg(feature = "macros")]
 #[doc(hidden)]
-pub use sqlx_macros::{FromRow, Type};
+pub use sqlx_macros::{FromRow, IntoArguments, Type};
+
+/// Marks an `async fn` as a test, run with the runtime of the enabled `runtime-*` feature.
+///
+/// A test that takes a [`Pool`] gets a database of its own: a new database is created on the
+/// server of `DATABASE_URL`, or the variable set by `database-url-var` in `sqlx.toml`, which may
+/// also be set in `.env` (for SQLite, a file in `sqlx/test-dbs/` of the target directory), the
+/// migrations of the crate are applied to it with [`migrate!()`], followed by the fixtures of the test, and it is
+/// dropped again once the test passes. The database of a test that panics or returns an error is
+/// kept for inspection, and replaced the next time the test runs.
+///
+/// ```rust,ignore
+/// #[sqlx::test(fixtures("users"))]
+/// async fn it_finds_users(pool: PgPool) -> sqlx::Result<()> {
+///     let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM users")
+///         .fetch_one(&pool)
+///         .await?;
+///
+///     assert_eq!(count, 2);
+///
+///     Ok(())
+/// }
+/// ```
+///
+/// The following arguments are supported, and require the `migrate` feature:
+///
+///  * `migrations = "dir"` applies the migrations of another directory, relative to the
+///    crate root, and `migrations = false` applies none.
+///  * `migrator = "path::to::MIGRATOR"` applies the migrations of a [`Migrator`] static.
+///  * `fixtures("users", "posts")` applies `fixtures/users.sql` and `fixtures/posts.sql`,
+///    relative to the file of the test, in order. Names ending in `.sql` are used as given.
+///
+/// [`migrate!()`]: crate::migrate!
+/// [`Migrator`]: crate::migrate::Migrator
+#[cfg(feature = "macros")]
+pub use sqlx_macros::test;
 
 #[cfg(feature = "macros")]
 mod macros;
@@ -81,6 +124,10 @@ mod macros;
 #[doc(hidden)]
 pub mod ty_match;
 
+#[cfg(all(feature = "macros", feature = "json"))]
+#[doc(hidden)]
+pub use sqlx_core::serde;
+
 /// Conversions between Rust and SQL types.
 ///
 /// To see how each SQL type maps to a Rust type, see the corresponding `types` module for each
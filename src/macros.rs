@@ -28,6 +28,16 @@
 /// # fn main() {}
 /// ```
 ///
+/// More derives for the anonymous struct can be listed in the configuration of the crate, in
+/// `sqlx.toml` or the `[package.metadata.sqlx]` table of `Cargo.toml`. `Serialize` is provided
+/// by SQLx with the `json` feature (the types of the columns must implement it as well); other
+/// derives are resolved in the crate:
+///
+/// ```toml
+/// [macros]
+/// record-derives = ["Clone", "Serialize"]
+/// ```
+///
 /// **The method you want to call depends on how many rows you're expecting.**
 ///
 /// | Number of Rows | Method to Call*             | Returns                                             | Notes |
@@ -48,6 +58,15 @@
 ///     * Or, `sqlx-data.json` must exist at the workspace root. See [Offline Mode](#offline-mode)
 ///       below.
 ///
+///     * Or, for SQLite, `SQLX_SQLITE_MIGRATIONS` must be set to the migrations directory
+///       relative to `CARGO_MANIFEST_DIR` (e.g. `SQLX_SQLITE_MIGRATIONS=migrations`), or
+///       `sqlite-migrations = true` must be set under `[macros]` in `sqlx.toml` to use the
+///       `migrations-dir` configured there. The queries are then checked against an in-memory
+///       database with the migrations applied, so no database file or `sqlx-data.json` is
+///       needed. Requires the `migrate` feature.
+///       The macros are not re-run when a migration changes unless a build script is used;
+///       see `sqlx migrate build-script`.
+///
 /// * The query must be a string literal, or concatenation of string literals using `+` (useful
 /// for queries generated by macro), or else it cannot be introspected (and thus cannot be dynamic
 /// or the result of another macro).
@@ -96,6 +115,47 @@
 /// * Postgres: `$N` where `N` is the 1-based positional argument index
 /// * MySQL/SQLite: `?` which matches arguments in order that it appears in the query
 ///
+/// MySQL and SQLite have no array types, so to bind a list to `IN (?)`, wrap it in
+/// [`InList`](crate::InList), which expands the placeholder to one per value. Its values must be of
+/// a type supported by the database; with Postgres, use a `Vec<T>` with `= ANY($1)` instead.
+///
+/// ## Named Parameters
+/// Instead, bind parameters may be named as `:name` or `$name`, which works the same for every
+/// database. Arguments for them are given as `name = value`, and a name without an argument is
+/// taken from the variable with that name, like `format!()` does:
+///
+/// ```rust,ignore
+/// let name = "Herp Derpinson";
+///
+/// let account = sqlx::query!(
+///         "select * from accounts where id = :id and name = :name and :id > 0",
+///         id = 1i32
+///     )
+///     .fetch_one(&mut conn)
+///     .await?;
+/// ```
+///
+/// With `..value` as the last argument, names without an argument are taken from the fields of
+/// `value` instead, which is type-checked field by field:
+///
+/// ```rust,ignore
+/// sqlx::query!(
+///         "insert into accounts (id, name) values (:id, :name)",
+///         ..account
+///     )
+///     .execute(&mut conn)
+///     .await?;
+/// ```
+///
+/// The names are replaced with the parameters of the database when the macro is expanded; a
+/// name used more than once is bound to the same argument each time. Names in string literals,
+/// quoted identifiers and comments are left alone, as are Postgres casts (`::int4`). A query
+/// with named parameters cannot use positional ones (`$1`, `?` or `?1`) as well; the macro
+/// rejects it, as they would be numbered along with the names.
+///
+/// Arguments may also all be given without names, in which case they are taken in order of the
+/// first appearance of each name, as SQLite does for its own `:name` and `$name` parameters.
+///
 /// ## Nullability: Bind Parameters
 /// For a given expected type `T`, both `T` and `Option<T>` are allowed (as well as either
 /// behind references). `Option::None` will be bound as `NULL`, so if binding a type behind `Option`
@@ -273,6 +333,23 @@
 /// | `foo!: T` | Forced not-null | Overridden |
 /// | `foo?: T` | Forced nullable | Overridden |
 ///
+/// ## Type Overrides: Configuration
+/// Types can also be overridden for every query of a crate in `sqlx.toml` (or the
+/// `[package.metadata.sqlx]` table of `Cargo.toml`), by SQL type for columns and bind parameters
+/// alike, or by column name, optionally qualified with its table, for output columns:
+///
+/// ```toml
+/// [macros.type-overrides]
+/// uuid = "crate::Id"
+/// citext = "String"
+///
+/// [macros.column-types]
+/// "users.id" = "crate::UserId"
+/// ```
+///
+/// Overrides in the query itself take precedence over `column-types`, which take precedence over
+/// `type-overrides`. The table of a column is known when it is selected directly from a table.
+///
 /// ## Offline Mode (requires the `offline` feature)
 /// The macros can be configured to not require a live database connection for compilation,
 /// but it requires a couple extra steps:
@@ -280,7 +357,8 @@
 /// * Run `cargo install sqlx-cli`.
 /// * In your project with `DATABASE_URL` set (or in a `.env` file) and the database server running,
 ///   run `cargo sqlx prepare`.
-/// * Check the generated `sqlx-data.json` file into version control.
+/// * Check the generated `sqlx-data.json` file into version control. Alternatively, run
+///   `cargo sqlx prepare --split` to generate a `sqlx-data/` directory with one file per query.
 /// * Don't have `DATABASE_URL` set during compilation.
 ///
 /// Your project can now be built without a database connection (you must omit `DATABASE_URL` or
@@ -291,12 +369,18 @@
 /// project and your database schema itself, run
 /// `cargo install sqlx-cli && cargo sqlx prepare --check` in your Continuous Integration script.
 ///
+/// With SQLite, queries can instead be checked against the migrations of the crate without any
+/// generated files, by setting `SQLX_SQLITE_MIGRATIONS` or `sqlite-migrations = true` in
+/// `sqlx.toml` (see [Requirements](#requirements)). `SQLX_OFFLINE=true` still uses the
+/// generated files.
+///
 /// See [the README for `sqlx-cli`](https://crates.io/crates/sqlx-cli) for more information.
 ///
 /// ## See Also
 /// * [query_as!] if you want to use a struct you can name,
 /// * [query_file!] if you want to define the SQL query out-of-line,
-/// * [query_file_as!] if you want both of the above.
+/// * [query_file_as!] if you want both of the above,
+/// * [query_as_from_row!] if you want the struct to be decoded through its `FromRow` impl.
 #[macro_export]
 #[cfg_attr(docsrs, doc(cfg(feature = "macros")))]
 macro_rules! query (
@@ -495,6 +579,29 @@ macro_rules! query_file_unchecked (
 /// assert_eq!(record.id, MyInt4(1));
 /// ```
 ///
+/// ### Column Type Override: Decode from JSON
+/// Similarly, selecting a column `foo as "foo: json"` (Postgres/SQLite) or `` foo as `foo: json` ``
+/// (MySQL) decodes the column as [`Json<T>`](crate::types::Json), inferring `T` from the type of
+/// the corresponding field, which only has to implement `serde::Deserialize`. This requires the
+/// `json` feature. A nullable column is decoded into an `Option<T>` field, where `NULL` is `None`:
+///
+/// ```rust,ignore
+/// #[derive(serde::Deserialize)]
+/// struct Settings {
+///     theme: String,
+/// }
+///
+/// struct User {
+///     id: i32,
+///     settings: Settings,
+/// }
+///
+/// // Postgres/SQLite
+/// let user = sqlx::query_as!(User, r#"select id, settings as "settings: json" from users"#)
+///     .fetch_one(&mut conn)
+///     .await?;
+/// ```
+///
 /// ### Troubleshooting: "error: mismatched types"
 /// If you get a "mismatched types" error from an invocation of this macro and the error
 /// isn't pointing specifically at a parameter.
@@ -629,6 +736,71 @@ macro_rules! query_file_as_unchecked (
     })
 );
 
+/// A variant of [query_as!] which decodes rows through the [`FromRow`][crate::FromRow] impl of the
+/// given struct, so the attributes of `#[derive(FromRow)]` such as `#[sqlx(rename = ..)]`,
+/// `#[sqlx(default)]` and `#[sqlx(flatten)]` are honored.
+///
+/// The query and its parameters are checked as with [query_as!], and the type of each column is
+/// checked against the type of the field that reads it, which is found by the name of the column
+/// after any renaming:
+///
+/// * The struct must `#[derive(FromRow)]`, which generates the functions the types of columns are
+/// checked with.
+/// * A column that no field reads is an error, unless the struct has `#[sqlx(flatten)]` fields.
+/// The columns read by flattened fields are not checked.
+/// * Fields with `#[sqlx(try_from = ..)]` are checked against the type they are converted from,
+/// and fields with `#[sqlx(json)]` are not checked.
+/// * Nullability is not checked: a field does not have to be an `Option<_>` for a column that may
+/// be `NULL`, but decoding a `NULL` into it is an error at runtime.
+/// * Column overrides cannot be used, as they change the names the columns are read by.
+///
+/// ```rust,ignore
+/// #[derive(sqlx::FromRow)]
+/// #[sqlx(rename_all = "camelCase")]
+/// struct Account {
+///     account_id: i64,
+///     #[sqlx(rename = "username")]
+///     name: String,
+///     #[sqlx(default)]
+///     is_active: bool,
+/// }
+///
+/// // Postgres/SQLite
+/// let account = sqlx::query_as_from_row!(
+///         Account,
+///         r#"select id as "accountId", name as username from accounts where id = $1"#,
+///         1i64
+///     )
+///     .fetch_one(&mut conn)
+///     .await?;
+/// ```
+#[macro_export]
+#[cfg_attr(docsrs, doc(cfg(feature = "macros")))]
+macro_rules! query_as_from_row (
+    ($out_struct:path, $query:expr) => ( {
+        $crate::sqlx_macros::expand_query!(record = $out_struct, source = $query, from_row = true)
+    });
+
+    ($out_struct:path, $query:expr, $($args:tt)*) => ( {
+        $crate::sqlx_macros::expand_query!(record = $out_struct, source = $query, args = [$($args)*], from_row = true)
+    })
+);
+
+/// Combines the syntaxes of [query_as_from_row!] and [query_file!].
+///
+/// Enforces requirements of both macros; see them for details.
+#[macro_export]
+#[cfg_attr(docsrs, doc(cfg(feature = "macros")))]
+macro_rules! query_file_as_from_row (
+    ($out_struct:path, $path:literal) => ( {
+        $crate::sqlx_macros::expand_query!(record = $out_struct, source_file = $path, from_row = true)
+    });
+
+    ($out_struct:path, $path:literal, $($args:tt)*) => ( {
+        $crate::sqlx_macros::expand_query!(record = $out_struct, source_file = $path, args = [$($args)*], from_row = true)
+    })
+);
+
 /// A variant of [query!] which expects a single column from the query and evaluates to an
 /// instance of [QueryScalar][crate::query::QueryScalar].
 ///
@@ -718,6 +890,9 @@ macro_rules! query_file_scalar_unchecked (
 /// unlike `include_str!()` which uses compiler internals to get the path of the file where it
 /// was invoked.
 ///
+/// Without a directory, the `migrations-dir` set in `sqlx.toml` or `[package.metadata.sqlx]` is
+/// used, if any. The `table-name` set there is used to track applied migrations either way.
+///
 /// See [MigrationSource][crate::migrate::MigrationSource] for details on structure of the ./migrations directory.
 ///
 /// ## Triggering Recompilation on Migration Changes
@@ -789,6 +964,6 @@ macro_rules! migrate {
     }};
 
     () => {{
-        $crate::sqlx_macros::migrate!("./migrations")
+        $crate::sqlx_macros::migrate!()
     }};
 }
@@ -118,6 +118,30 @@ impl<T, U> MatchBorrowExt for &'_ MatchBorrow<T, U> {
     type Matched = U;
 }
 
+// `query_as_from_row!()` decodes its record through `FromRow`, but still checks each column
+// against the field that reads it: `#[derive(FromRow)]` generates a function for every column on a
+// type of its own, bounded by the type of its field, which the macro calls with the type it
+// inferred for the column. Nullability is not checked, as a `NULL` for a non-optional field
+// already fails in decoding.
+
+/// Implemented by `#[derive(FromRow)]` to name the type with the functions checking its columns.
+pub trait FromRowColumns {
+    type Columns;
+}
+
+/// Implemented by the types of fields that can be read from a column of type `T`.
+pub trait FieldForColumn<T> {}
+
+impl<T> FieldForColumn<T> for T {}
+
+impl<T> FieldForColumn<T> for Option<T> {}
+
+impl<T> FieldForColumn<Option<T>> for T {}
+
+/// Implemented by `#[derive(FromRow)]` for the columns of structs with flattened fields, whose
+/// columns are not known to the derive and so are left unchecked.
+pub trait UncheckedColumns {}
+
 pub fn conjure_value<T>() -> T {
     panic!()
 }
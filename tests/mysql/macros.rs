@@ -33,6 +33,37 @@ async fn macro_select_from_cte_bind() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[sqlx_macros::test]
+async fn macro_select_from_cte_named_bind() -> anyhow::Result<()> {
+    let mut conn = new::<MySql>().await?;
+
+    let name = "Herp Derpinson";
+
+    // MySQL only has `?`, so `:id` is bound once for each time it appears
+    let account = sqlx::query!(
+        "select * from (select (1) as id, 'Herp Derpinson' as name) accounts \
+         where id = :id and name = :name and id < :id + 1",
+        id = 1i32
+    )
+    .fetch_one(&mut conn)
+    .await?;
+
+    assert_eq!(account.id, 1);
+    assert_eq!(account.name, "Herp Derpinson");
+
+    // a backslash escapes a quote in MySQL strings
+    let account = sqlx::query!(
+        r#"select * from (select (1) as id, 'it\'s :name' as name) accounts where id = :id"#,
+        id = 1i32
+    )
+    .fetch_one(&mut conn)
+    .await?;
+
+    assert_eq!(account.name, "it's :name");
+
+    Ok(())
+}
+
 #[derive(Debug)]
 struct RawAccount {
     r#type: i32,
@@ -272,6 +272,44 @@ async fn it_can_bind_only_null_issue_540() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[sqlx_macros::test]
+async fn it_binds_in_lists() -> anyhow::Result<()> {
+    let mut conn = new::<MySql>().await?;
+
+    let row = sqlx::query("SELECT 2 IN (?), 5 IN (?), 2 IN (?), ?")
+        .bind(sqlx::InList(vec![1_i32, 2, 3]))
+        .bind(sqlx::InList(vec![1_i32, 2, 3]))
+        .bind(sqlx::InList(Vec::<i32>::new()))
+        .bind(None::<i32>)
+        .fetch_one(&mut conn)
+        .await?;
+
+    let found: Option<i64> = row.get(0);
+    let not_found: Option<i64> = row.get(1);
+    let empty: Option<i64> = row.get(2);
+    let null: Option<i32> = row.get(3);
+
+    assert_eq!(found, Some(1));
+    assert_eq!(not_found, Some(0));
+    assert_eq!(empty, None);
+    assert_eq!(null, None);
+
+    // a list in an `Option` is expanded the same, and `None` binds `NULL`
+    let row = sqlx::query("SELECT 2 IN (?), 2 IN (?)")
+        .bind(Some(sqlx::InList(vec![1_i32, 2, 3])))
+        .bind(None::<sqlx::InList<i32>>)
+        .fetch_one(&mut conn)
+        .await?;
+
+    let found: Option<i64> = row.get(0);
+    let null: Option<i64> = row.get(1);
+
+    assert_eq!(found, Some(1));
+    assert_eq!(null, None);
+
+    Ok(())
+}
+
 #[sqlx_macros::test]
 async fn it_can_bind_and_return_years() -> anyhow::Result<()> {
     let mut conn = new::<MySql>().await?;
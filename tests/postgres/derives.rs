@@ -2,6 +2,7 @@ use futures::TryStreamExt;
 use sqlx::{Connection, Executor, FromRow, Postgres};
 use sqlx_core::postgres::types::PgRange;
 use sqlx_test::{new, test_type};
+use std::convert::TryFrom;
 use std::fmt::Debug;
 use std::ops::Bound;
 
@@ -10,6 +11,11 @@ use std::ops::Bound;
 #[sqlx(transparent)]
 struct Transparent(i32);
 
+// a wrapper of a type without an array type in Postgres
+#[derive(PartialEq, Debug, sqlx::Type)]
+#[sqlx(transparent, no_pg_array)]
+struct TransparentArray(Vec<String>);
+
 // "Weak" enums map to an integer type indicated by #[repr]
 #[derive(PartialEq, Copy, Clone, Debug, sqlx::Type)]
 #[repr(i32)]
@@ -125,6 +131,11 @@ test_type!(transparent<Transparent>(Postgres,
     "23523" == Transparent(23523)
 ));
 
+test_type!(transparent_array<TransparentArray>(Postgres,
+    "'{}'::text[]" == TransparentArray(vec![]),
+    "'{a,b}'::text[]" == TransparentArray(vec!["a".to_owned(), "b".to_owned()])
+));
+
 test_type!(weak_enum<Weak>(Postgres,
     "0::int4" == Weak::One,
     "2::int4" == Weak::Two,
@@ -332,6 +343,20 @@ SELECT id, mood FROM people WHERE id = $1
     assert!(rec.0);
     assert_eq!(rec.1, ColorPascalCase::RedGreen);
 
+    // Arrays of the enum are `_mood`
+
+    let rec: (bool, Vec<Mood>) = sqlx::query_as(
+        "
+    SELECT $1 = '{happy, sad}'::mood[], $1
+            ",
+    )
+    .bind(vec![Mood::Happy, Mood::Sad])
+    .fetch_one(&mut conn)
+    .await?;
+
+    assert!(rec.0);
+    assert_eq!(rec.1, [Mood::Happy, Mood::Sad]);
+
     Ok(())
 }
 
@@ -360,6 +385,49 @@ SELECT $1 = ROW('fuzzy dice', 42, 199)::inventory_item, $1
     Ok(())
 }
 
+#[sqlx_macros::test]
+async fn test_array_type() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    let items = vec![
+        InventoryItem {
+            name: "fuzzy dice".to_owned(),
+            supplier_id: Some(42),
+            price: Some(199),
+        },
+        InventoryItem {
+            name: "spare tire".to_owned(),
+            supplier_id: None,
+            price: None,
+        },
+    ];
+
+    let rec: (bool, Vec<InventoryItem>) = sqlx::query_as(
+        "
+SELECT $1 = ARRAY[ROW('fuzzy dice', 42, 199), ROW('spare tire', NULL, NULL)]::inventory_item[], $1
+        ",
+    )
+    .bind(&items)
+    .fetch_one(&mut conn)
+    .await?;
+
+    assert!(rec.0);
+    assert_eq!(rec.1, items);
+
+    let rec: (Vec<Weak>, Vec<Strong>, Vec<Transparent>) = sqlx::query_as("SELECT $1, $2, $3")
+        .bind(&[Weak::One, Weak::Three][..])
+        .bind(vec![Strong::Two, Strong::Three])
+        .bind(vec![Transparent(0), Transparent(23523)])
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(rec.0, [Weak::One, Weak::Three]);
+    assert_eq!(rec.1, [Strong::Two, Strong::Three]);
+    assert_eq!(rec.2, [Transparent(0), Transparent(23523)]);
+
+    Ok(())
+}
+
 #[cfg(feature = "macros")]
 #[sqlx_macros::test]
 async fn test_from_row() -> anyhow::Result<()> {
@@ -559,3 +627,237 @@ async fn test_default() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[cfg(feature = "macros")]
+#[sqlx_macros::test]
+async fn test_from_row_flatten() -> anyhow::Result<()> {
+    #[derive(Debug, PartialEq, sqlx::FromRow)]
+    struct Address {
+        country: String,
+        city: String,
+    }
+
+    #[derive(Debug, sqlx::FromRow)]
+    #[sqlx(rename_all = "camelCase")]
+    struct Contact {
+        contact_name: String,
+        #[sqlx(flatten, prefix = "home_")]
+        home: Address,
+    }
+
+    #[derive(Debug, sqlx::FromRow)]
+    struct Order {
+        id: i32,
+        #[sqlx(flatten)]
+        billing: Address,
+        #[sqlx(flatten, prefix = "shipping_")]
+        shipping: Address,
+        #[sqlx(flatten, prefix = "contact_")]
+        contact: Contact,
+    }
+
+    let mut conn = new::<Postgres>().await?;
+
+    let order: Order = sqlx::query_as(
+        r#"
+SELECT * from (VALUES (1, 'NL', 'Amsterdam', 'BE', 'Brussels', 'Jane', 'FR', 'Paris'))
+    orders(id, country, city, shipping_country, shipping_city, "contact_contactName", contact_home_country, contact_home_city)
+        "#,
+    )
+    .fetch_one(&mut conn)
+    .await?;
+    println!("{:?}", order);
+
+    assert_eq!(order.id, 1);
+    assert_eq!(order.billing.country, "NL");
+    assert_eq!(order.billing.city, "Amsterdam");
+    assert_eq!(order.shipping.country, "BE");
+    assert_eq!(order.shipping.city, "Brussels");
+    assert_eq!(order.contact.contact_name, "Jane");
+    assert_eq!(order.contact.home.country, "FR");
+    assert_eq!(order.contact.home.city, "Paris");
+
+    let err = sqlx::query_as::<_, Order>(
+        "SELECT 1 AS id, 'NL' AS country, 'Amsterdam' AS city, 'BE' AS shipping_country",
+    )
+    .fetch_one(&mut conn)
+    .await
+    .unwrap_err();
+
+    assert!(matches!(err, sqlx::Error::ColumnNotFound(name) if name == "shipping_city"));
+
+    Ok(())
+}
+
+#[cfg(feature = "macros")]
+#[sqlx_macros::test]
+async fn test_from_row_try_from_and_skip() -> anyhow::Result<()> {
+    #[derive(Debug, PartialEq)]
+    struct Name(String);
+
+    impl TryFrom<String> for Name {
+        type Error = &'static str;
+
+        fn try_from(name: String) -> Result<Self, Self::Error> {
+            if name.is_empty() {
+                Err("a name cannot be empty")
+            } else {
+                Ok(Name(name))
+            }
+        }
+    }
+
+    #[derive(Debug, sqlx::FromRow)]
+    struct Account {
+        #[sqlx(try_from = "i64")]
+        id: u64,
+        #[sqlx(try_from = "String")]
+        name: Name,
+        #[sqlx(skip)]
+        tags: Vec<String>,
+    }
+
+    let mut conn = new::<Postgres>().await?;
+
+    let account: Account = sqlx::query_as("SELECT 1::int8 AS id, 'Herp Derpinson' AS name")
+        .fetch_one(&mut conn)
+        .await?;
+    println!("{:?}", account);
+
+    assert_eq!(account.id, 1);
+    assert_eq!(account.name, Name("Herp Derpinson".to_owned()));
+    assert!(account.tags.is_empty());
+
+    let err = sqlx::query_as::<_, Account>("SELECT -1::int8 AS id, 'Herp Derpinson' AS name")
+        .fetch_one(&mut conn)
+        .await
+        .unwrap_err();
+
+    assert!(matches!(err, sqlx::Error::ColumnDecode { index, .. } if index == "\"id\""));
+
+    let err = sqlx::query_as::<_, Account>("SELECT 1::int8 AS id, '' AS name")
+        .fetch_one(&mut conn)
+        .await
+        .unwrap_err();
+
+    assert_eq!(
+        err.to_string(),
+        "error occurred while decoding column \"name\": a name cannot be empty"
+    );
+
+    Ok(())
+}
+
+#[cfg(feature = "macros")]
+#[sqlx_macros::test]
+async fn test_from_row_enum() -> anyhow::Result<()> {
+    #[derive(Debug, PartialEq, sqlx::FromRow)]
+    struct Polygon {
+        sides: i32,
+    }
+
+    #[derive(Debug, PartialEq, sqlx::FromRow)]
+    #[sqlx(tag = "kind", rename_all = "lowercase")]
+    enum Shape {
+        Circle {
+            radius: f64,
+        },
+        #[sqlx(rename = "rect")]
+        Rectangle {
+            width: f64,
+            #[sqlx(default)]
+            height: Option<f64>,
+        },
+        Point,
+        Polygon(Polygon),
+    }
+
+    #[derive(Debug, sqlx::FromRow)]
+    struct Drawing {
+        id: i32,
+        #[sqlx(flatten, prefix = "shape_")]
+        shape: Shape,
+    }
+
+    let mut conn = new::<Postgres>().await?;
+
+    let shapes: Vec<Shape> = sqlx::query_as(
+        r#"
+SELECT * from (VALUES
+    ('circle', 1.5::float8, NULL::float8, NULL::int4),
+    ('rect', NULL, 2.0, NULL),
+    ('point', NULL, NULL, NULL),
+    ('polygon', NULL, NULL, 6)
+) shapes(kind, radius, width, sides)
+        "#,
+    )
+    .fetch_all(&mut conn)
+    .await?;
+
+    assert_eq!(
+        shapes,
+        [
+            Shape::Circle { radius: 1.5 },
+            Shape::Rectangle {
+                width: 2.0,
+                height: None
+            },
+            Shape::Point,
+            Shape::Polygon(Polygon { sides: 6 }),
+        ]
+    );
+
+    let drawing: Drawing =
+        sqlx::query_as("SELECT 1 AS id, 'circle' AS shape_kind, 2.5::float8 AS shape_radius")
+            .fetch_one(&mut conn)
+            .await?;
+
+    assert_eq!(drawing.id, 1);
+    assert_eq!(drawing.shape, Shape::Circle { radius: 2.5 });
+
+    let err = sqlx::query_as::<_, Shape>("SELECT 'triangle' AS kind")
+        .fetch_one(&mut conn)
+        .await
+        .unwrap_err();
+
+    assert_eq!(
+        err.to_string(),
+        "error occurred while decoding column \"kind\": no variant of `Shape` has the tag \"triangle\""
+    );
+
+    Ok(())
+}
+
+#[cfg(feature = "macros")]
+#[sqlx_macros::test]
+async fn test_from_row_enum_tag_type() -> anyhow::Result<()> {
+    #[derive(Debug, PartialEq, sqlx::FromRow)]
+    #[sqlx(tag = "kind", tag_type = "i32")]
+    enum Shape {
+        #[sqlx(tag = 1)]
+        Circle { radius: f64 },
+        #[sqlx(tag = 2)]
+        Point,
+    }
+
+    let mut conn = new::<Postgres>().await?;
+
+    let shapes: Vec<Shape> =
+        sqlx::query_as("SELECT * from (VALUES (1, 1.5::float8), (2, NULL)) shapes(kind, radius)")
+            .fetch_all(&mut conn)
+            .await?;
+
+    assert_eq!(shapes, [Shape::Circle { radius: 1.5 }, Shape::Point]);
+
+    let err = sqlx::query_as::<_, Shape>("SELECT 3 AS kind")
+        .fetch_one(&mut conn)
+        .await
+        .unwrap_err();
+
+    assert_eq!(
+        err.to_string(),
+        "error occurred while decoding column \"kind\": no variant of `Shape` has the tag 3"
+    );
+
+    Ok(())
+}
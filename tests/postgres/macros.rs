@@ -20,6 +20,38 @@ async fn test_query() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[sqlx_macros::test]
+async fn test_query_named_params() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    let name = "Herp Derpinson";
+
+    let account = sqlx::query!(
+        "SELECT * from (VALUES (1, 'Herp Derpinson')) accounts(id, name) \
+         where id = :id::int4 and name = $name and id < :id + 1",
+        id = 1i32
+    )
+    .fetch_one(&mut conn)
+    .await?;
+
+    assert_eq!(account.id, Some(1));
+    assert_eq!(account.name.as_deref(), Some("Herp Derpinson"));
+
+    // a backslash only escapes a quote in `E'...'` strings
+    let row = sqlx::query!(
+        r#"SELECT E'it\'s :a' as "escaped!", '\' as "plain!", :b::int4 as "b!""#,
+        b = 2i32
+    )
+    .fetch_one(&mut conn)
+    .await?;
+
+    assert_eq!(row.escaped, "it's :a");
+    assert_eq!(row.plain, "\\");
+    assert_eq!(row.b, 2);
+
+    Ok(())
+}
+
 #[sqlx_macros::test]
 async fn test_non_null() -> anyhow::Result<()> {
     let mut conn = new::<Postgres>().await?;
@@ -162,6 +194,36 @@ async fn test_query_as_raw() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[derive(Debug, sqlx::FromRow)]
+#[sqlx(rename_all = "camelCase")]
+struct RenamedAccount {
+    account_id: i32,
+    #[sqlx(try_from = "i32")]
+    balance: i64,
+    #[sqlx(rename = "username")]
+    name: Option<String>,
+}
+
+#[sqlx_macros::test]
+async fn test_query_as_from_row() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    let account = sqlx::query_as_from_row!(
+        RenamedAccount,
+        r#"SELECT id "accountId", balance, name username
+           from (VALUES (1, 100, $1)) accounts(id, balance, name)"#,
+        "Herp Derpinson"
+    )
+    .fetch_one(&mut conn)
+    .await?;
+
+    assert_eq!(1, account.account_id);
+    assert_eq!(100, account.balance);
+    assert_eq!(Some("Herp Derpinson"), account.name.as_deref());
+
+    Ok(())
+}
+
 #[sqlx_macros::test]
 async fn test_query_file_as() -> anyhow::Result<()> {
     let mut conn = new::<Postgres>().await?;
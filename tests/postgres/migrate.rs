@@ -0,0 +1,96 @@
+use sqlx::migrate::Migrate;
+use sqlx::{Connection, Executor, PgPool, Postgres};
+use sqlx_test::new;
+
+#[sqlx_macros::test]
+async fn it_dumps_schema() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+    let mut tx = conn.begin().await?;
+
+    // everything happens in a throwaway schema that is rolled back at the end
+    tx.execute(
+        r#"
+CREATE SCHEMA dump_schema_test;
+SET LOCAL search_path TO dump_schema_test;
+
+CREATE TYPE mood AS ENUM ('sad', 'ok', 'happy');
+
+CREATE TYPE address AS (street TEXT, zip VARCHAR(10));
+
+CREATE TABLE users (
+    id BIGSERIAL PRIMARY KEY,
+    email VARCHAR(255) NOT NULL UNIQUE,
+    mood mood
+);
+
+CREATE TABLE posts (
+    id INT GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY,
+    user_id BIGINT NOT NULL REFERENCES users (id) ON DELETE CASCADE,
+    body TEXT NOT NULL DEFAULT '' CHECK (length(body) < 1000)
+);
+
+CREATE INDEX posts_user_id ON posts (user_id);
+
+CREATE VIEW user_posts AS SELECT users.email, posts.body FROM users JOIN posts ON posts.user_id = users.id;
+        "#,
+    )
+    .await?;
+
+    let schema = (&mut *tx).schema("_sqlx_migrations").await?;
+
+    assert_eq!(
+        schema.to_string(),
+        r#"CREATE TYPE "mood" AS ENUM ('sad', 'ok', 'happy');
+
+CREATE TYPE "address" AS ("street" text, "zip" character varying(10));
+
+CREATE TABLE "posts" (
+    "id" integer GENERATED BY DEFAULT AS IDENTITY NOT NULL,
+    "user_id" bigint NOT NULL,
+    "body" text NOT NULL DEFAULT ''::text,
+    CONSTRAINT "posts_body_check" CHECK ((length(body) < 1000)),
+    CONSTRAINT "posts_pkey" PRIMARY KEY (id)
+);
+
+CREATE INDEX posts_user_id ON dump_schema_test.posts USING btree (user_id);
+
+CREATE TABLE "users" (
+    "id" bigserial NOT NULL,
+    "email" character varying(255) NOT NULL,
+    "mood" mood,
+    CONSTRAINT "users_email_key" UNIQUE (email),
+    CONSTRAINT "users_pkey" PRIMARY KEY (id)
+);
+
+ALTER TABLE "posts" ADD CONSTRAINT "posts_user_id_fkey" FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE;
+
+CREATE VIEW "user_posts" AS
+SELECT users.email,
+    posts.body
+   FROM users
+     JOIN posts ON posts.user_id = users.id;
+"#
+    );
+
+    tx.rollback().await?;
+
+    Ok(())
+}
+
+#[sqlx_macros::test(migrations = "tests/migrate/migrations", fixtures("accounts"))]
+async fn it_runs_tests_against_a_migrated_database(pool: PgPool) -> anyhow::Result<()> {
+    let names: Vec<String> = sqlx::query_scalar("SELECT name FROM accounts ORDER BY id")
+        .fetch_all(&pool)
+        .await?;
+
+    assert_eq!(names, ["Herp Derpinson", "Bob Smith"]);
+
+    // the database of this test only
+    let database: String = sqlx::query_scalar("SELECT current_database()::text")
+        .fetch_one(&pool)
+        .await?;
+
+    assert!(database.starts_with("_sqlx_test_"));
+
+    Ok(())
+}
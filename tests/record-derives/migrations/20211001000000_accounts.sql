@@ -0,0 +1,7 @@
+CREATE TABLE accounts (
+    id INTEGER NOT NULL PRIMARY KEY,
+    name TEXT NOT NULL,
+    bio TEXT
+);
+
+INSERT INTO accounts (id, name, bio) VALUES (1, 'Herp Derpinson', NULL);
@@ -0,0 +1,2 @@
+//! Checks the `record-derives` of `sqlx.toml` on the records of `query!()`, which needs a crate
+//! of its own as the configuration applies to every query of a crate.
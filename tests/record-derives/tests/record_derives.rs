@@ -0,0 +1,22 @@
+use sqlx::{Connection, SqliteConnection};
+
+#[tokio::test]
+async fn it_derives_for_records() -> anyhow::Result<()> {
+    let mut conn = SqliteConnection::connect(":memory:").await?;
+
+    sqlx::migrate!().run(&mut conn).await?;
+
+    let account = sqlx::query!("SELECT id, name, bio FROM accounts")
+        .fetch_one(&mut conn)
+        .await?;
+
+    // `Clone` is resolved in this crate and `Serialize` through `sqlx::serde`
+    let json = serde_json::to_value(account.clone())?;
+
+    assert_eq!(
+        json,
+        serde_json::json!({ "id": 1, "name": "Herp Derpinson", "bio": null })
+    );
+
+    Ok(())
+}
@@ -1,5 +1,5 @@
 use sqlx::Sqlite;
-use sqlx_test::test_type;
+use sqlx_test::{new, test_type};
 
 #[derive(Debug, PartialEq, sqlx::Type)]
 #[repr(u32)]
@@ -12,3 +12,34 @@ test_type!(origin_enum<Origin>(Sqlite,
     "1" == Origin::Foo,
     "2" == Origin::Bar,
 ));
+
+#[derive(sqlx::IntoArguments)]
+struct AccountFilter<'a> {
+    name: &'a str,
+    #[sqlx(skip)]
+    _comment: &'a str,
+    id: i32,
+}
+
+#[sqlx_macros::test]
+async fn test_into_arguments() -> anyhow::Result<()> {
+    let mut conn = new::<Sqlite>().await?;
+
+    let filter = AccountFilter {
+        name: "Herp Derpinson",
+        _comment: "not bound",
+        id: 1,
+    };
+
+    let (id, name): (i32, String) = sqlx::query_as_with(
+        "SELECT id, name FROM accounts WHERE name = ? AND id = ?",
+        filter,
+    )
+    .fetch_one(&mut conn)
+    .await?;
+
+    assert_eq!(id, 1);
+    assert_eq!(name, "Herp Derpinson");
+
+    Ok(())
+}
@@ -0,0 +1,3 @@
+INSERT INTO accounts (id, name, is_active)
+VALUES (1, 'Herp Derpinson', TRUE),
+       (2, 'Bob Smith', FALSE);
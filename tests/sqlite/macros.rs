@@ -88,6 +88,130 @@ async fn macro_select_bind() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[sqlx_macros::test]
+async fn macro_select_named_bind() -> anyhow::Result<()> {
+    let mut conn = new::<Sqlite>().await?;
+
+    let name = "Herp Derpinson";
+
+    // `:name` is taken from the variable in scope and `$id` appears twice but is bound once
+    let account = sqlx::query!(
+        "select id, name, ':id' as label from accounts where id = $id and name = :name and $id > 0",
+        id = 1i32
+    )
+    .fetch_one(&mut conn)
+    .await?;
+
+    assert_eq!(1, account.id);
+    assert_eq!("Herp Derpinson", account.name);
+    assert_eq!(":id", account.label);
+
+    // arguments without names are taken in order of the first appearance of each name
+    let account = sqlx::query!(
+        "select id, name from accounts where id = :id and name = :name and :id > 0",
+        1i32,
+        "Herp Derpinson"
+    )
+    .fetch_one(&mut conn)
+    .await?;
+
+    assert_eq!(1, account.id);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn macro_select_named_bind_from_fields() -> anyhow::Result<()> {
+    struct Filter {
+        id: i32,
+        name: &'static str,
+    }
+
+    let mut conn = new::<Sqlite>().await?;
+
+    let filter = Filter {
+        id: 1,
+        name: "Herp Derpinson",
+    };
+
+    // `:name` is given explicitly and `:id` is taken from `filter.id`
+    let account = sqlx::query!(
+        "select id, name from accounts where id = :id and name = :name",
+        name = "Herp Derpinson",
+        ..filter
+    )
+    .fetch_one(&mut conn)
+    .await?;
+
+    assert_eq!(1, account.id);
+    assert_eq!(filter.name, account.name);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn macro_select_named_bind_from_fields_evaluates_once() -> anyhow::Result<()> {
+    struct Filter {
+        id: i32,
+        name: &'static str,
+    }
+
+    let mut conn = new::<Sqlite>().await?;
+
+    let value = Filter {
+        id: 1,
+        name: "Herp Derpinson",
+    };
+
+    let mut calls = 0;
+    let mut filter = || {
+        calls += 1;
+        &value
+    };
+
+    let account = sqlx::query!(
+        "select id, name from accounts where id = :id and name = :name",
+        ..filter()
+    )
+    .fetch_one(&mut conn)
+    .await?;
+
+    assert_eq!(1, account.id);
+    assert_eq!(1, calls);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn macro_select_in_list() -> anyhow::Result<()> {
+    let mut conn = new::<Sqlite>().await?;
+
+    let ids = sqlx::InList(vec![1i64, 2, 3]);
+
+    let accounts = sqlx::query!(
+        "select id, name from accounts where id in (?) order by id",
+        ids
+    )
+    .fetch_all(&mut conn)
+    .await?;
+
+    assert_eq!(accounts.len(), 1);
+    assert_eq!(1, accounts[0].id);
+
+    let ids = Some(sqlx::InList(vec![1i64, 2, 3]));
+
+    let accounts = sqlx::query!(
+        "select id, name from accounts where id in (?) order by id",
+        ids
+    )
+    .fetch_all(&mut conn)
+    .await?;
+
+    assert_eq!(accounts.len(), 1);
+
+    Ok(())
+}
+
 #[derive(Debug)]
 struct RawAccount {
     id: i64,
@@ -110,6 +234,62 @@ async fn test_query_as_raw() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[derive(Debug, sqlx::FromRow)]
+struct AccountStatus {
+    #[sqlx(rename = "is_active")]
+    active: Option<bool>,
+    #[sqlx(default)]
+    tweets: i64,
+}
+
+#[derive(Debug, sqlx::FromRow)]
+struct Account {
+    #[sqlx(rename = "id")]
+    account_id: i64,
+    name: String,
+    #[sqlx(flatten)]
+    status: AccountStatus,
+}
+
+#[sqlx_macros::test]
+async fn test_query_as_from_row() -> anyhow::Result<()> {
+    let mut conn = new::<Sqlite>().await?;
+
+    let account = sqlx::query_as_from_row!(
+        Account,
+        "SELECT id, name, is_active from accounts where id = ?",
+        1i64
+    )
+    .fetch_one(&mut conn)
+    .await?;
+
+    assert_eq!(account.account_id, 1);
+    assert_eq!(account.name, "Herp Derpinson");
+    assert_eq!(account.status.active, Some(true));
+    assert_eq!(account.status.tweets, 0);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn test_query_as_from_row_duplicate_columns() -> anyhow::Result<()> {
+    let mut conn = new::<Sqlite>().await?;
+
+    // `FromRow` reads the last `id`, so that is the column checked against `account_id: i64`
+    let account = sqlx::query_as_from_row!(
+        Account,
+        "SELECT name AS id, id, name, is_active from accounts where id = ?",
+        1i64
+    )
+    .fetch_one(&mut conn)
+    .await?;
+
+    assert_eq!(account.account_id, 1);
+    assert_eq!(account.name, "Herp Derpinson");
+
+    Ok(())
+}
+
 #[sqlx_macros::test]
 async fn test_query_scalar() -> anyhow::Result<()> {
     let mut conn = new::<Sqlite>().await?;
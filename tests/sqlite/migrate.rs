@@ -0,0 +1,263 @@
+use sqlx::migrate::{Migrate, MigrateError, Migration, MigrationType, Migrator, Seeder};
+use sqlx::{Connection, Executor, SqliteConnection, SqlitePool};
+use std::borrow::Cow;
+use std::path::Path;
+
+#[sqlx_macros::test]
+async fn it_dumps_schema() -> anyhow::Result<()> {
+    let mut conn = SqliteConnection::connect(":memory:").await?;
+
+    conn.ensure_migrations_table().await?;
+
+    conn.execute(
+        r#"
+CREATE TABLE users (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    email TEXT NOT NULL UNIQUE,
+    name TEXT
+);
+
+CREATE TABLE posts (
+    id INTEGER NOT NULL,
+    user_id INTEGER NOT NULL REFERENCES users (id) ON DELETE CASCADE,
+    body TEXT NOT NULL DEFAULT '',
+    PRIMARY KEY (id)
+);
+
+CREATE INDEX posts_user_id ON posts (user_id);
+
+-- not AUTOINCREMENT, though the word appears in the table
+CREATE TABLE tags (
+    id INTEGER PRIMARY KEY,
+    name TEXT NOT NULL DEFAULT 'AUTOINCREMENT' /* AUTOINCREMENT */
+);
+
+CREATE VIEW user_posts AS SELECT users.name, posts.body FROM users JOIN posts ON posts.user_id = users.id;
+        "#,
+    )
+    .await?;
+
+    let schema = conn.schema("_sqlx_migrations").await?;
+
+    assert_eq!(
+        schema.to_string(),
+        r#"CREATE TABLE "posts" (
+    "id" INTEGER NOT NULL,
+    "user_id" INTEGER NOT NULL,
+    "body" TEXT NOT NULL DEFAULT '',
+    PRIMARY KEY ("id"),
+    FOREIGN KEY ("user_id") REFERENCES "users" ("id") ON DELETE CASCADE
+);
+
+CREATE INDEX posts_user_id ON posts (user_id);
+
+CREATE TABLE "tags" (
+    "id" INTEGER,
+    "name" TEXT NOT NULL DEFAULT 'AUTOINCREMENT',
+    PRIMARY KEY ("id")
+);
+
+CREATE TABLE "users" (
+    "id" INTEGER PRIMARY KEY AUTOINCREMENT,
+    "email" TEXT NOT NULL,
+    "name" TEXT,
+    UNIQUE ("email")
+);
+
+CREATE VIEW user_posts AS SELECT users.name, posts.body FROM users JOIN posts ON posts.user_id = users.id;
+"#
+    );
+
+    // the output must be stable across runs
+    assert_eq!(conn.schema("_sqlx_migrations").await?, schema);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_round_trips_checks_and_generated_columns() -> anyhow::Result<()> {
+    let mut conn = SqliteConnection::connect(":memory:").await?;
+
+    conn.ensure_migrations_table().await?;
+
+    conn.execute(
+        r#"
+CREATE TABLE items (
+    id INTEGER PRIMARY KEY,
+    price INTEGER NOT NULL CHECK (price > 0),
+    quantity INTEGER NOT NULL,
+    total INTEGER GENERATED ALWAYS AS (price * quantity) STORED,
+    label TEXT AS ('item ' || id),
+    CONSTRAINT sane_quantity CHECK (quantity BETWEEN 0 AND 1000)
+);
+        "#,
+    )
+    .await?;
+
+    let schema = conn.schema("_sqlx_migrations").await?;
+
+    assert_eq!(
+        schema.to_string(),
+        r#"CREATE TABLE "items" (
+    "id" INTEGER,
+    "price" INTEGER NOT NULL,
+    "quantity" INTEGER NOT NULL,
+    "total" INTEGER GENERATED ALWAYS AS (price * quantity) STORED,
+    "label" TEXT GENERATED ALWAYS AS ('item ' || id) VIRTUAL,
+    CHECK (price > 0),
+    CHECK (quantity BETWEEN 0 AND 1000),
+    PRIMARY KEY ("id")
+);
+"#
+    );
+
+    // the dump recreates the same schema, with its constraints enforced
+    let mut copy = SqliteConnection::connect(":memory:").await?;
+
+    copy.ensure_migrations_table().await?;
+    copy.execute(&*schema.to_string()).await?;
+
+    assert_eq!(copy.schema("_sqlx_migrations").await?, schema);
+
+    let res = copy
+        .execute("INSERT INTO items (price, quantity) VALUES (0, 1)")
+        .await;
+
+    assert!(res.is_err());
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_applies_baseline() -> anyhow::Result<()> {
+    let mut conn = SqliteConnection::connect(":memory:").await?;
+
+    conn.ensure_migrations_table().await?;
+
+    for (version, sql) in [
+        (1, "CREATE TABLE a (id INTEGER)"),
+        (2, "CREATE TABLE b (id INTEGER)"),
+    ] {
+        conn.apply(&Migration::new(
+            version,
+            Cow::Borrowed("original"),
+            MigrationType::Simple,
+            Cow::Borrowed(sql),
+        ))
+        .await?;
+    }
+
+    let baseline = Migration::new(
+        1,
+        Cow::Borrowed("squashed"),
+        MigrationType::Simple,
+        Cow::Borrowed("CREATE TABLE a (id INTEGER);"),
+    );
+
+    conn.apply_baseline("_sqlx_migrations", &baseline).await?;
+
+    let applied = conn.list_applied_migrations().await?;
+
+    assert_eq!(
+        applied.iter().map(|m| m.version).collect::<Vec<_>>(),
+        vec![1, 2]
+    );
+    assert_eq!(applied[0].checksum, baseline.checksum);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_seeds() -> anyhow::Result<()> {
+    let mut conn = SqliteConnection::connect(":memory:").await?;
+
+    let mut seeder = Seeder::new(Path::new("tests/migrate/seeds")).await?;
+
+    // applying the seeds again must not insert their rows twice
+    seeder.run(&mut conn).await?;
+    seeder.run(&mut conn).await?;
+
+    let users: Vec<(i64, String, Option<String>)> =
+        sqlx::query_as("SELECT id, name, bio FROM users ORDER BY id")
+            .fetch_all(&mut conn)
+            .await?;
+
+    assert_eq!(
+        users,
+        vec![
+            (1, "O'Brien, Pat".to_owned(), None),
+            (2, "Sam".to_owned(), Some("likes \"quotes\"".to_owned())),
+        ]
+    );
+
+    let applied = conn.list_applied_seeds().await?;
+
+    assert_eq!(applied.len(), 2);
+    assert_eq!(applied[0].name, "01_schema.sql");
+    assert_eq!(applied[1].name, "02_users.csv");
+
+    // a seed that changed after it was applied is an error
+    seeder.seeds[1].checksum = Cow::Owned(vec![0; 48]);
+
+    assert!(matches!(
+        seeder.run(&mut conn).await,
+        Err(MigrateError::SeedMismatch(name)) if name == "02_users.csv"
+    ));
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_migrates_with_table_name() -> anyhow::Result<()> {
+    let mut conn = SqliteConnection::connect(":memory:").await?;
+
+    let mut migrator = Migrator::new(Path::new("tests/migrate/migrations")).await?;
+    // the name is quoted wherever it is interpolated
+    migrator.set_table_name("_other-migrations");
+
+    migrator.run(&mut conn).await?;
+
+    let applied = conn.list_applied_migrations_in("_other-migrations").await?;
+
+    assert_eq!(applied.len(), 2);
+
+    // the default table was never created
+    let count: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*) FROM sqlite_master WHERE name IN ('_sqlx_migrations', '_other-migrations')",
+    )
+    .fetch_one(&mut conn)
+    .await?;
+
+    assert_eq!(count, 1);
+
+    Ok(())
+}
+
+#[sqlx_macros::test(migrations = "tests/migrate/migrations", fixtures("accounts"))]
+async fn it_runs_tests_against_a_migrated_database(pool: SqlitePool) -> anyhow::Result<()> {
+    let names: Vec<String> = sqlx::query_scalar("SELECT name FROM accounts ORDER BY id")
+        .fetch_all(&pool)
+        .await?;
+
+    assert_eq!(names, ["Herp Derpinson", "Bob Smith"]);
+
+    // the database of this test only
+    let database: String = sqlx::query_scalar("SELECT file FROM pragma_database_list")
+        .fetch_one(&pool)
+        .await?;
+
+    assert!(database.contains("_sqlx_test_"));
+
+    Ok(())
+}
+
+#[sqlx_macros::test(migrations = false)]
+async fn it_runs_tests_without_migrations(pool: SqlitePool) -> anyhow::Result<()> {
+    let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM sqlite_master")
+        .fetch_one(&pool)
+        .await?;
+
+    assert_eq!(count, 0);
+
+    Ok(())
+}
@@ -172,6 +172,48 @@ async fn it_binds_positional_parameters_issue_467() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[sqlx_macros::test]
+async fn it_binds_in_lists() -> anyhow::Result<()> {
+    let mut conn = new::<Sqlite>().await?;
+
+    let ids: Vec<i64> = sqlx::query_scalar(
+        "select id from (select 1 as id union select 2 union select 3 union select 4) \
+         where id in (?) and id not in (?) and id > ?",
+    )
+    .bind(sqlx::InList(vec![1_i64, 2, 3]))
+    .bind(sqlx::InList(Vec::<i64>::new()))
+    .bind(1_i64)
+    .fetch_all(&mut conn)
+    .await?;
+
+    assert_eq!(ids, vec![2, 3]);
+
+    // numbered parameters after a list refer to the same arguments as before
+    let row: (i32, i32, i32) =
+        sqlx::query_as("select ?3, ?1, count(*) from tweet where id in (?2)")
+            .bind(5_i32)
+            .bind(sqlx::InList(vec![1_i64, 2, 3]))
+            .bind(7_i32)
+            .fetch_one(&mut conn)
+            .await?;
+
+    assert_eq!(row, (7, 5, 1));
+
+    // a list in an `Option` is expanded the same, and `None` binds `NULL`
+    let ids: Vec<i64> = sqlx::query_scalar(
+        "select id from (select 1 as id union select 2 union select 3) \
+         where id in (?) or id in (?)",
+    )
+    .bind(Some(sqlx::InList(vec![1_i64, 3])))
+    .bind(None::<sqlx::InList<i64>>)
+    .fetch_all(&mut conn)
+    .await?;
+
+    assert_eq!(ids, vec![1, 3]);
+
+    Ok(())
+}
+
 #[sqlx_macros::test]
 async fn it_fetches_in_loop() -> anyhow::Result<()> {
     // this is trying to check for any data races
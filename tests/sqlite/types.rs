@@ -87,6 +87,69 @@ mod json_tests {
 
         Ok(())
     }
+
+    #[sqlx_macros::test]
+    async fn it_decodes_json_fields() -> anyhow::Result<()> {
+        #[derive(Debug, sqlx::FromRow)]
+        struct Account {
+            id: i64,
+            #[sqlx(json)]
+            friend: Friend,
+            #[sqlx(json)]
+            tags: Vec<String>,
+            #[sqlx(json)]
+            enemy: Option<Friend>,
+            #[sqlx(json)]
+            rival: Option<Friend>,
+            #[sqlx(json)]
+            mentor: Option<Friend>,
+        }
+
+        let mut conn = new::<Sqlite>().await?;
+
+        let account: Account = sqlx::query_as(
+            r#"SELECT 1 AS id, '{"name":"Joe","age":33}' AS friend, '["a","b"]' AS tags,
+                NULL AS enemy, 'null' AS rival, '{"name":"Ann","age":60}' AS mentor"#,
+        )
+        .fetch_one(&mut conn)
+        .await?;
+
+        assert_eq!(account.id, 1);
+        assert_eq!(account.friend.name, "Joe");
+        assert_eq!(account.friend.age, 33);
+        assert_eq!(account.tags, ["a", "b"]);
+        assert_eq!(account.enemy, None);
+        assert_eq!(account.rival, None);
+        assert_eq!(
+            account.mentor.map(|mentor| mentor.name).as_deref(),
+            Some("Ann")
+        );
+
+        Ok(())
+    }
+
+    #[sqlx_macros::test]
+    async fn it_decodes_json_columns_in_query_as() -> anyhow::Result<()> {
+        struct Account {
+            friend: Friend,
+            enemy: Option<Friend>,
+        }
+
+        let mut conn = new::<Sqlite>().await?;
+
+        let account = sqlx::query_as!(
+            Account,
+            r#"SELECT '{"name":"Joe","age":33}' AS "friend!: json", NULL AS "enemy?: json""#
+        )
+        .fetch_one(&mut conn)
+        .await?;
+
+        assert_eq!(account.friend.name, "Joe");
+        assert_eq!(account.friend.age, 33);
+        assert_eq!(account.enemy, None);
+
+        Ok(())
+    }
 }
 
 #[cfg(feature = "chrono")]
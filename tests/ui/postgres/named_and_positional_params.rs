@@ -0,0 +1,4 @@
+fn main() {
+    let b = 0i32;
+    let _ = sqlx::query!("select $1::int4 + :b", 1i32, b = b);
+}
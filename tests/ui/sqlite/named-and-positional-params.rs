@@ -0,0 +1,4 @@
+fn main() {
+    let b = 0i64;
+    let _ = sqlx::query!("select id from accounts where id = ? and id = :b", 1i64, b = b);
+}